@@ -0,0 +1,8 @@
+-- Метаданные ссылки и токен, которым она создана
+ALTER TABLE links
+    ADD COLUMN IF NOT EXISTS title TEXT NULL,
+    ADD COLUMN IF NOT EXISTS notes TEXT NULL,
+    ADD COLUMN IF NOT EXISTS created_by BIGINT NULL REFERENCES api_tokens(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS links_created_by_idx
+    ON links (created_by);
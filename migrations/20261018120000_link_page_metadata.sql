@@ -0,0 +1,6 @@
+-- Метаданные целевой страницы (заполняются фоновым воркером)
+ALTER TABLE links
+    ADD COLUMN IF NOT EXISTS og_title TEXT NULL,
+    ADD COLUMN IF NOT EXISTS og_description TEXT NULL,
+    ADD COLUMN IF NOT EXISTS og_image TEXT NULL,
+    ADD COLUMN IF NOT EXISTS metadata_fetched_at TIMESTAMPTZ NULL;
@@ -0,0 +1,12 @@
+-- Права (scopes) API токенов
+ALTER TABLE api_tokens
+    ADD COLUMN IF NOT EXISTS scopes TEXT[] NOT NULL DEFAULT '{}';
+
+-- Существующие токены сохраняют полный доступ
+UPDATE api_tokens
+SET scopes = ARRAY['links:write', 'links:read', 'stats:read', 'domains:admin']
+WHERE scopes = '{}';
+
+ALTER TABLE api_tokens
+    ADD CONSTRAINT api_tokens_scopes_check
+    CHECK (scopes <@ ARRAY['links:write', 'links:read', 'stats:read', 'domains:admin']);
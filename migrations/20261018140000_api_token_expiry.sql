@@ -0,0 +1,6 @@
+-- Срок действия API токенов (NULL - бессрочный)
+ALTER TABLE api_tokens
+    ADD COLUMN IF NOT EXISTS expires_at TIMESTAMPTZ NULL;
+
+CREATE INDEX IF NOT EXISTS api_tokens_expires_at_idx
+    ON api_tokens (expires_at);
@@ -0,0 +1,12 @@
+-- Ограничение токенов набором доменов для создания ссылок
+ALTER TABLE api_tokens
+    ADD COLUMN IF NOT EXISTS restrict_domains BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE TABLE IF NOT EXISTS api_token_domains (
+    token_id  BIGINT NOT NULL REFERENCES api_tokens (id) ON DELETE CASCADE,
+    domain_id BIGINT NOT NULL REFERENCES domains (id) ON DELETE CASCADE,
+    PRIMARY KEY (token_id, domain_id)
+);
+
+CREATE INDEX IF NOT EXISTS api_token_domains_domain_id_idx
+    ON api_token_domains (domain_id);
@@ -0,0 +1,10 @@
+-- Индивидуальные лимиты токенов (NULL - значения по умолчанию / без квоты)
+ALTER TABLE api_tokens
+    ADD COLUMN IF NOT EXISTS rate_limit_per_minute INTEGER NULL
+        CHECK (rate_limit_per_minute > 0),
+    ADD COLUMN IF NOT EXISTS daily_link_quota INTEGER NULL
+        CHECK (daily_link_quota > 0);
+
+-- Подсчёт ссылок, созданных токеном за день
+CREATE INDEX IF NOT EXISTS links_created_by_created_at_idx
+    ON links (created_by, created_at);
@@ -0,0 +1,15 @@
+-- Сессии дашборда (cookie содержит случайный идентификатор, в БД - его хэш)
+CREATE TABLE IF NOT EXISTS dashboard_sessions (
+    id BIGSERIAL PRIMARY KEY,
+    session_hash TEXT NOT NULL UNIQUE,
+    token_id BIGINT NOT NULL REFERENCES api_tokens(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL,
+    last_seen_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    revoked_at TIMESTAMPTZ NULL,
+    ip TEXT NULL,
+    user_agent TEXT NULL
+);
+
+CREATE INDEX IF NOT EXISTS dashboard_sessions_token_id_idx ON dashboard_sessions (token_id);
+CREATE INDEX IF NOT EXISTS dashboard_sessions_expires_at_idx ON dashboard_sessions (expires_at);
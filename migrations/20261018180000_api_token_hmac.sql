@@ -0,0 +1,8 @@
+-- Хэши токенов: 1 - SHA-256 без соли (старый формат), 2 - HMAC-SHA256 с pepper.
+-- Старые хэши заменяются новыми при следующей успешной аутентификации.
+ALTER TABLE api_tokens
+    ADD COLUMN IF NOT EXISTS hash_version SMALLINT NOT NULL DEFAULT 1
+        CHECK (hash_version IN (1, 2)),
+    ADD COLUMN IF NOT EXISTS lookup_id TEXT NULL UNIQUE;
+
+ALTER TABLE api_tokens ALTER COLUMN hash_version SET DEFAULT 2;
@@ -0,0 +1,37 @@
+-- Пользователи дашборда и владельцы токенов/ссылок
+CREATE TABLE IF NOT EXISTS users (
+    id BIGSERIAL PRIMARY KEY,
+    username TEXT NOT NULL UNIQUE,
+    password_hash TEXT NOT NULL,
+    role TEXT NOT NULL CHECK (role IN ('admin', 'editor', 'viewer')),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    disabled_at TIMESTAMPTZ NULL,
+    last_login_at TIMESTAMPTZ NULL
+);
+
+-- Домены, на которых редактор может создавать ссылки
+CREATE TABLE IF NOT EXISTS user_domains (
+    user_id BIGINT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    domain_id BIGINT NOT NULL REFERENCES domains(id) ON DELETE CASCADE,
+    PRIMARY KEY (user_id, domain_id)
+);
+
+-- Токен, выпущенный для пользователя (NULL - сервисный токен)
+ALTER TABLE api_tokens
+    ADD COLUMN IF NOT EXISTS user_id BIGINT NULL REFERENCES users(id) ON DELETE CASCADE;
+
+CREATE INDEX IF NOT EXISTS api_tokens_user_id_idx ON api_tokens (user_id);
+
+-- Владелец ссылки
+ALTER TABLE links
+    ADD COLUMN IF NOT EXISTS owner_id BIGINT NULL REFERENCES users(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS links_owner_id_idx ON links (owner_id);
+
+-- Сессия дашборда: по токену или по логину/паролю пользователя
+ALTER TABLE dashboard_sessions
+    ALTER COLUMN token_id DROP NOT NULL,
+    ADD COLUMN IF NOT EXISTS user_id BIGINT NULL REFERENCES users(id) ON DELETE CASCADE,
+    ADD CONSTRAINT dashboard_sessions_subject_check
+        CHECK ((token_id IS NULL) <> (user_id IS NULL));
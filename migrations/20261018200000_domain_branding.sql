@@ -0,0 +1,5 @@
+-- Оформление страниц ошибок домена (404/410 для браузеров)
+ALTER TABLE domains
+    ADD COLUMN IF NOT EXISTS logo_url TEXT NULL,
+    ADD COLUMN IF NOT EXISTS not_found_message TEXT NULL,
+    ADD COLUMN IF NOT EXISTS fallback_url TEXT NULL;
@@ -0,0 +1,4 @@
+-- Куда вести посетителя с корня домена и с несуществующих кодов
+ALTER TABLE domains
+    ADD COLUMN IF NOT EXISTS root_redirect_url TEXT NULL,
+    ADD COLUMN IF NOT EXISTS not_found_redirect_url TEXT NULL;
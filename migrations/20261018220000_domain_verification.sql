@@ -0,0 +1,18 @@
+-- Проверка владения доменом (DNS TXT или HTTP challenge)
+ALTER TABLE domains
+    ADD COLUMN IF NOT EXISTS verification_token TEXT NULL,
+    ADD COLUMN IF NOT EXISTS verified_at TIMESTAMPTZ NULL,
+    ADD COLUMN IF NOT EXISTS verification_method TEXT NULL
+        CHECK (verification_method IN ('dns', 'http', 'manual')),
+    ADD COLUMN IF NOT EXISTS verification_checked_at TIMESTAMPTZ NULL,
+    ADD COLUMN IF NOT EXISTS verification_error TEXT NULL;
+
+-- Домены, добавленные до появления проверки, считаются подтверждёнными
+UPDATE domains
+SET verified_at = created_at, verification_method = 'manual'
+WHERE verified_at IS NULL;
+
+-- Неподтверждённый домен не может обслуживать ссылки
+ALTER TABLE domains
+    ADD CONSTRAINT domains_active_requires_verification
+        CHECK (NOT is_active OR verified_at IS NOT NULL);
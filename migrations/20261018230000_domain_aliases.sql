@@ -0,0 +1,10 @@
+-- Дополнительные имена домена: алиасы (www.s.example.com) и wildcard (*.go.example.com).
+-- Все они ведут в пространство ссылок основного домена.
+CREATE TABLE IF NOT EXISTS domain_aliases (
+    id BIGSERIAL PRIMARY KEY,
+    domain_id BIGINT NOT NULL REFERENCES domains(id) ON DELETE CASCADE,
+    host TEXT NOT NULL UNIQUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS domain_aliases_domain_id_idx ON domain_aliases (domain_id);
@@ -0,0 +1,7 @@
+-- Имена доменов хранятся в канонической форме (нижний регистр, punycode).
+-- Домены, добавленные в обход сервиса, приводятся к нижнему регистру,
+-- если это не создаёт дубликат.
+UPDATE domains d
+SET domain = lower(d.domain)
+WHERE d.domain <> lower(d.domain)
+  AND NOT EXISTS (SELECT 1 FROM domains o WHERE o.domain = lower(d.domain));
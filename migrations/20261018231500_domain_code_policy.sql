@@ -0,0 +1,13 @@
+-- Политика кодов ссылок домена: длина и алфавит генерируемых кодов,
+-- границы длины кастомных
+ALTER TABLE domains
+    ADD COLUMN IF NOT EXISTS code_length SMALLINT NOT NULL DEFAULT 12
+        CHECK (code_length BETWEEN 4 AND 32),
+    ADD COLUMN IF NOT EXISTS code_alphabet TEXT NOT NULL DEFAULT 'base62'
+        CHECK (code_alphabet IN ('base62', 'lowercase', 'no_lookalike')),
+    ADD COLUMN IF NOT EXISTS custom_code_min_length SMALLINT NOT NULL DEFAULT 8
+        CHECK (custom_code_min_length BETWEEN 3 AND 64),
+    ADD COLUMN IF NOT EXISTS custom_code_max_length SMALLINT NOT NULL DEFAULT 15
+        CHECK (custom_code_max_length BETWEEN 3 AND 64),
+    ADD CONSTRAINT domains_custom_code_length_check
+        CHECK (custom_code_min_length <= custom_code_max_length);
@@ -0,0 +1,14 @@
+-- Стратегия генерации кодов домена (NULL - глобальная CODE_STRATEGY)
+ALTER TABLE domains
+    ADD COLUMN IF NOT EXISTS code_strategy TEXT
+        CHECK (code_strategy IN ('random', 'sequential', 'words'));
+
+-- Счётчики последовательных кодов: свой для каждого домена и пространства
+-- кодов (алфавит + длина), чтобы исчерпание проверялось по пространству домена
+CREATE TABLE IF NOT EXISTS link_code_sequences (
+    domain_id BIGINT NOT NULL REFERENCES domains(id) ON DELETE CASCADE,
+    alphabet TEXT NOT NULL,
+    length SMALLINT NOT NULL,
+    next_number BIGINT NOT NULL DEFAULT 0,
+    PRIMARY KEY (domain_id, alphabet, length)
+);
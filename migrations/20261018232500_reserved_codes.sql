@@ -0,0 +1,13 @@
+-- Коды, недоступные для ссылок: на всех доменах (domain_id IS NULL) или на одном.
+-- Пути маршрутов приложения и RESERVED_CODES резервируются без записи в таблице.
+CREATE TABLE IF NOT EXISTS reserved_codes (
+    id BIGSERIAL PRIMARY KEY,
+    code TEXT NOT NULL CHECK (code = lower(code)),
+    domain_id BIGINT REFERENCES domains(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS reserved_codes_global_key
+    ON reserved_codes (code) WHERE domain_id IS NULL;
+CREATE UNIQUE INDEX IF NOT EXISTS reserved_codes_domain_key
+    ON reserved_codes (domain_id, code) WHERE domain_id IS NOT NULL;
@@ -0,0 +1,14 @@
+-- Проверка владения алиасами: тот же challenge, что и у доменов.
+-- Для wildcard (*.go.example.com) проверяется суффикс (go.example.com).
+ALTER TABLE domain_aliases
+    ADD COLUMN IF NOT EXISTS verification_token TEXT NULL,
+    ADD COLUMN IF NOT EXISTS verified_at TIMESTAMPTZ NULL,
+    ADD COLUMN IF NOT EXISTS verification_method TEXT NULL
+        CHECK (verification_method IN ('dns', 'http', 'manual')),
+    ADD COLUMN IF NOT EXISTS verification_checked_at TIMESTAMPTZ NULL,
+    ADD COLUMN IF NOT EXISTS verification_error TEXT NULL;
+
+-- Алиасы, добавленные до появления проверки, считаются подтверждёнными
+UPDATE domain_aliases
+SET verified_at = created_at, verification_method = 'manual'
+WHERE verified_at IS NULL;
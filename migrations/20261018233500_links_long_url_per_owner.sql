@@ -0,0 +1,5 @@
+-- Один URL может быть сокращён разными владельцами и на разных доменах:
+-- повторное использование ссылки ограничено токеном и пользователем (LinkService)
+ALTER TABLE links DROP CONSTRAINT IF EXISTS links_long_url_key;
+
+CREATE INDEX IF NOT EXISTS links_long_url_domain_id_idx ON links (long_url, domain_id);
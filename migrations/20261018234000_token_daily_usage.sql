@@ -0,0 +1,16 @@
+-- Счётчик суточной квоты токена: резервируется атомарно до создания ссылок
+CREATE TABLE IF NOT EXISTS token_daily_usage (
+    token_id BIGINT NOT NULL REFERENCES api_tokens(id) ON DELETE CASCADE,
+    day DATE NOT NULL,
+    links_created BIGINT NOT NULL DEFAULT 0 CHECK (links_created >= 0),
+    PRIMARY KEY (token_id, day)
+);
+
+-- Уже созданные сегодня (UTC) ссылки продолжают расходовать квоту
+INSERT INTO token_daily_usage (token_id, day, links_created)
+SELECT created_by, (NOW() AT TIME ZONE 'UTC')::date, COUNT(*)
+FROM links
+WHERE created_by IS NOT NULL
+  AND created_at >= date_trunc('day', NOW() AT TIME ZONE 'UTC') AT TIME ZONE 'UTC'
+GROUP BY created_by
+ON CONFLICT (token_id, day) DO NOTHING;
@@ -1,5 +1,13 @@
 use chrono::{DateTime, Utc};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use validator::{Validate, ValidationError};
+
+use crate::application::services::DomainOverview;
+use crate::domain::entities::{
+    CodePolicy, Domain, DomainAlias, DomainVerification, UpdateDomain, VerificationChallenge,
+};
+use crate::error::AppError;
 
 #[derive(Debug, Serialize)]
 pub struct DomainItem {
@@ -7,11 +15,292 @@ pub struct DomainItem {
     pub is_default: bool,
     pub is_active: bool,
     pub description: Option<String>,
+    /// Количество коротких ссылок на домене
+    pub links_count: i64,
+    /// Алиасы и wildcard шаблоны (`*.go.example.com`), ведущие на домен
+    pub aliases: Vec<AliasItem>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logo_url: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub not_found_message: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fallback_url: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub root_redirect_url: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub not_found_redirect_url: Option<String>,
+
+    /// Проверка владения доменом (только для права `domains:admin`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verification: Option<DomainVerificationItem>,
+
+    /// Правила кодов ссылок
+    pub code_policy: CodePolicyItem,
+
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+/// Алиас домена; обслуживается только в статусе verified
+#[derive(Debug, Serialize)]
+pub struct AliasItem {
+    pub host: String,
+    /// Проверка владения алиасом (только для права `domains:admin`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verification: Option<DomainVerificationItem>,
+}
+
+impl From<DomainAlias> for AliasItem {
+    fn from(alias: DomainAlias) -> Self {
+        let verification =
+            DomainVerificationItem::new(&alias.verification, alias.verification_challenge());
+
+        Self {
+            host: alias.host,
+            verification: Some(verification),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct CodePolicyItem {
+    /// Стратегия генерации (null - глобальная CODE_STRATEGY)
+    pub strategy: Option<&'static str>,
+    pub length: usize,
+    pub alphabet: &'static str,
+    pub custom_code_min_length: usize,
+    pub custom_code_max_length: usize,
+}
+
+impl From<&CodePolicy> for CodePolicyItem {
+    fn from(policy: &CodePolicy) -> Self {
+        Self {
+            strategy: policy.strategy.map(|strategy| strategy.as_str()),
+            length: policy.length,
+            alphabet: policy.alphabet.as_str(),
+            custom_code_min_length: policy.custom_min_length,
+            custom_code_max_length: policy.custom_max_length,
+        }
+    }
+}
+
+/// Статус проверки и инструкции для неподтверждённого домена или алиаса
+#[derive(Debug, Serialize)]
+pub struct DomainVerificationItem {
+    /// verified / pending
+    pub status: &'static str,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub method: Option<&'static str>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verified_at: Option<DateTime<Utc>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checked_at: Option<DateTime<Utc>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dns_record: Option<DnsRecordItem>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub http_url: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DnsRecordItem {
+    #[serde(rename = "type")]
+    pub record_type: &'static str,
+    pub name: String,
+    pub value: String,
+}
+
+impl DomainVerificationItem {
+    fn new(verification: &DomainVerification, challenge: Option<VerificationChallenge>) -> Self {
+        Self {
+            status: if verification.is_verified() {
+                "verified"
+            } else {
+                "pending"
+            },
+            method: verification.method.map(|method| method.as_str()),
+            verified_at: verification.verified_at,
+            checked_at: verification.checked_at,
+            last_error: verification.last_error.clone(),
+            http_url: challenge.as_ref().map(|c| c.http_url.clone()),
+            dns_record: challenge.map(|c| DnsRecordItem {
+                record_type: "TXT",
+                name: c.dns_name,
+                value: c.dns_value,
+            }),
+        }
+    }
+}
+
+impl DomainItem {
+    pub fn new(domain: Domain, links_count: i64, aliases: Vec<DomainAlias>) -> Self {
+        let verification =
+            DomainVerificationItem::new(&domain.verification, domain.verification_challenge());
+        let code_policy = CodePolicyItem::from(&domain.code_policy);
+
+        Self {
+            domain: domain.domain,
+            is_default: domain.is_default,
+            is_active: domain.is_active,
+            description: domain.description,
+            links_count,
+            aliases: aliases.into_iter().map(AliasItem::from).collect(),
+            logo_url: domain.branding.logo_url,
+            not_found_message: domain.branding.not_found_message,
+            fallback_url: domain.branding.fallback_url,
+            root_redirect_url: domain.redirects.root_url,
+            not_found_redirect_url: domain.redirects.not_found_url,
+            verification: Some(verification),
+            code_policy,
+            created_at: domain.created_at,
+            updated_at: domain.updated_at,
+        }
+    }
+}
+
+impl From<DomainOverview> for DomainItem {
+    fn from(overview: DomainOverview) -> Self {
+        Self::new(overview.domain, overview.links_count, overview.aliases)
+    }
+}
+
+impl DomainItem {
+    /// Без статуса проверки и токенов challenge домена и алиасов
+    pub fn without_verification(mut self) -> Self {
+        self.verification = None;
+        for alias in &mut self.aliases {
+            alias.verification = None;
+        }
+        self
+    }
+}
+
 #[derive(Debug, Serialize)]
 pub struct DomainListResponse {
     pub items: Vec<DomainItem>,
 }
+
+/// Новый домен создаётся неподтверждённым, поэтому сделать его доменом по умолчанию
+/// можно только после проверки владения (`POST /domains/{domain}/default`)
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateDomainRequest {
+    #[validate(length(min = 1, max = 255))]
+    pub domain: String,
+
+    #[validate(length(max = 500))]
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateAliasRequest {
+    /// Алиас (`www.s.example.com`) или wildcard (`*.go.example.com`)
+    #[validate(length(min = 1, max = 255))]
+    pub host: String,
+}
+
+/// Частичное обновление: отсутствующие поля не меняются,
+/// пустая строка сбрасывает поле оформления или редиректа
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateDomainRequest {
+    pub is_active: Option<bool>,
+
+    #[validate(length(max = 500))]
+    pub description: Option<String>,
+
+    /// Логотип на странице 404/410
+    #[validate(length(max = 2048), custom(function = "validate_page_url"))]
+    pub logo_url: Option<String>,
+
+    /// Текст страницы 404/410 вместо стандартного
+    #[validate(length(max = 500))]
+    pub not_found_message: Option<String>,
+
+    /// Ссылка «Перейти на сайт» на странице 404/410
+    #[validate(length(max = 2048), custom(function = "validate_page_url"))]
+    pub fallback_url: Option<String>,
+
+    /// Редирект с корня домена (`GET /`)
+    #[validate(length(max = 2048), custom(function = "validate_page_url"))]
+    pub root_redirect_url: Option<String>,
+
+    /// Редирект с несуществующих кодов вместо страницы 404
+    #[validate(length(max = 2048), custom(function = "validate_page_url"))]
+    pub not_found_redirect_url: Option<String>,
+
+    /// Длина генерируемых кодов
+    pub code_length: Option<usize>,
+
+    /// Алфавит генерируемых кодов: base62, lowercase, no_lookalike
+    pub code_alphabet: Option<String>,
+
+    /// Границы длины кастомных кодов
+    pub custom_code_min_length: Option<usize>,
+    pub custom_code_max_length: Option<usize>,
+
+    /// Стратегия генерации кодов: random, sequential, words
+    /// (пустая строка - глобальная CODE_STRATEGY)
+    pub code_strategy: Option<String>,
+}
+
+impl UpdateDomainRequest {
+    /// Изменения домена; алфавит и стратегия кодов разбираются здесь
+    pub fn into_update(self) -> Result<UpdateDomain, AppError> {
+        let code_alphabet = self
+            .code_alphabet
+            .as_deref()
+            .map(str::parse)
+            .transpose()
+            .map_err(|e: String| {
+                AppError::bad_request("Invalid code alphabet", json!({"reason": e}))
+            })?;
+
+        // Пустая строка сбрасывает стратегию домена на глобальную
+        let code_strategy = match self.code_strategy.as_deref() {
+            None => None,
+            Some("") => Some(None),
+            Some(strategy) => Some(Some(strategy.parse().map_err(|e: String| {
+                AppError::bad_request("Invalid code strategy", json!({"reason": e}))
+            })?)),
+        };
+
+        Ok(UpdateDomain {
+            is_active: self.is_active,
+            description: self.description,
+            logo_url: self.logo_url,
+            not_found_message: self.not_found_message,
+            fallback_url: self.fallback_url,
+            root_redirect_url: self.root_redirect_url,
+            not_found_redirect_url: self.not_found_redirect_url,
+            code_length: self.code_length,
+            code_alphabet,
+            custom_code_min_length: self.custom_code_min_length,
+            custom_code_max_length: self.custom_code_max_length,
+            code_strategy,
+        })
+    }
+}
+
+/// Пустая строка или абсолютный http(s) URL
+fn validate_page_url(value: &str) -> Result<(), ValidationError> {
+    if value.is_empty() {
+        return Ok(());
+    }
+
+    match url::Url::parse(value) {
+        Ok(url) if matches!(url.scheme(), "http" | "https") => Ok(()),
+        _ => Err(ValidationError::new("url")
+            .with_message("Must be an absolute http(s) URL or empty to reset".into())),
+    }
+}
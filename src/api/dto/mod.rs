@@ -2,6 +2,8 @@ pub mod clicks;
 pub mod domain;
 pub mod health;
 pub mod pagination;
+pub mod qr;
 pub mod shorten;
 pub mod stats;
 pub mod stats_list;
+pub mod tag;
@@ -2,6 +2,8 @@ use chrono::{DateTime, Utc};
 use serde::Deserialize;
 use serde_with::{DisplayFromStr, serde_as};
 
+use crate::domain::repositories::{SortDirection, StatsSortField};
+
 #[serde_as]
 #[derive(Debug, Deserialize)]
 pub struct PaginationParams {
@@ -70,3 +72,86 @@ pub struct StatsQueryParams {
 
     pub domain: Option<String>,
 }
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortFieldParam {
+    Clicks,
+    CreatedAt,
+    Code,
+}
+
+impl From<SortFieldParam> for StatsSortField {
+    fn from(value: SortFieldParam) -> Self {
+        match value {
+            SortFieldParam::Clicks => StatsSortField::Clicks,
+            SortFieldParam::CreatedAt => StatsSortField::CreatedAt,
+            SortFieldParam::Code => StatsSortField::Code,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrderParam {
+    Asc,
+    Desc,
+}
+
+impl From<SortOrderParam> for SortDirection {
+    fn from(value: SortOrderParam) -> Self {
+        match value {
+            SortOrderParam::Asc => SortDirection::Asc,
+            SortOrderParam::Desc => SortDirection::Desc,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SortParams {
+    /// Поле сортировки: clicks | created_at | code
+    pub sort: Option<SortFieldParam>,
+
+    /// Направление: asc | desc (по умолчанию desc)
+    pub order: Option<SortOrderParam>,
+}
+
+impl SortParams {
+    pub fn field(&self) -> StatsSortField {
+        self.sort.map(Into::into).unwrap_or_default()
+    }
+
+    pub fn direction(&self) -> SortDirection {
+        self.order.map(Into::into).unwrap_or_default()
+    }
+}
+
+/// Параметры списка ссылок: фильтры + сортировка и поиск
+#[derive(Debug, Deserialize)]
+pub struct StatsListQueryParams {
+    #[serde(flatten)]
+    pub pagination: PaginationParams,
+
+    #[serde(flatten)]
+    pub date_filter: DateFilterParams,
+
+    #[serde(flatten)]
+    pub sort: SortParams,
+
+    pub domain: Option<String>,
+
+    /// Поиск подстроки в long_url и code
+    pub q: Option<String>,
+
+    /// Фильтр по тегу
+    pub tag: Option<String>,
+}
+
+/// Параметры статистики по тегам
+#[derive(Debug, Deserialize)]
+pub struct TagStatsQueryParams {
+    #[serde(flatten)]
+    pub date_filter: DateFilterParams,
+
+    pub domain: Option<String>,
+}
@@ -0,0 +1,182 @@
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::error::AppError;
+use crate::utils::qr::{QrColor, QrErrorCorrection, QrImageFormat, QrOptions};
+
+const DEFAULT_SIZE: u32 = 512;
+const MIN_SIZE: u32 = 64;
+const MAX_SIZE: u32 = 2048;
+const DEFAULT_MARGIN: u32 = 4;
+const MAX_MARGIN: u32 = 16;
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QrFormatParam {
+    Png,
+    Svg,
+}
+
+impl From<QrFormatParam> for QrImageFormat {
+    fn from(value: QrFormatParam) -> Self {
+        match value {
+            QrFormatParam::Png => QrImageFormat::Png,
+            QrFormatParam::Svg => QrImageFormat::Svg,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub enum QrEccParam {
+    #[serde(alias = "l")]
+    L,
+    #[serde(alias = "m")]
+    M,
+    #[serde(alias = "q")]
+    Q,
+    #[serde(alias = "h")]
+    H,
+}
+
+impl From<QrEccParam> for QrErrorCorrection {
+    fn from(value: QrEccParam) -> Self {
+        match value {
+            QrEccParam::L => QrErrorCorrection::Low,
+            QrEccParam::M => QrErrorCorrection::Medium,
+            QrEccParam::Q => QrErrorCorrection::Quartile,
+            QrEccParam::H => QrErrorCorrection::High,
+        }
+    }
+}
+
+/// Параметры генерации QR кода
+#[derive(Debug, Deserialize)]
+pub struct QrQueryParams {
+    /// png | svg (по умолчанию png)
+    pub format: Option<QrFormatParam>,
+
+    /// Размер стороны в пикселях (64-2048, по умолчанию 512)
+    pub size: Option<u32>,
+
+    /// Отступ в модулях (0-16, по умолчанию 4)
+    pub margin: Option<u32>,
+
+    /// Уровень коррекции ошибок: L | M | Q | H (по умолчанию M)
+    pub ecc: Option<QrEccParam>,
+
+    /// Цвет модулей, hex (по умолчанию #000000)
+    pub fg: Option<String>,
+
+    /// Цвет фона, hex (по умолчанию #ffffff)
+    pub bg: Option<String>,
+}
+
+impl QrQueryParams {
+    pub fn to_options(&self) -> Result<QrOptions, AppError> {
+        let size = self.size.unwrap_or(DEFAULT_SIZE);
+        if !(MIN_SIZE..=MAX_SIZE).contains(&size) {
+            return Err(AppError::bad_request(
+                format!("Size must be between {} and {}", MIN_SIZE, MAX_SIZE),
+                json!({ "size": size }),
+            ));
+        }
+
+        let margin = self.margin.unwrap_or(DEFAULT_MARGIN);
+        if margin > MAX_MARGIN {
+            return Err(AppError::bad_request(
+                format!("Margin must be between 0 and {}", MAX_MARGIN),
+                json!({ "margin": margin }),
+            ));
+        }
+
+        Ok(QrOptions {
+            format: self.format.map(Into::into).unwrap_or_default(),
+            size,
+            margin,
+            ecc: self.ecc.map(Into::into).unwrap_or_default(),
+            foreground: parse_color("fg", self.fg.as_deref(), QrColor::BLACK)?,
+            background: parse_color("bg", self.bg.as_deref(), QrColor::WHITE)?,
+        })
+    }
+}
+
+fn parse_color(field: &str, value: Option<&str>, default: QrColor) -> Result<QrColor, AppError> {
+    match value {
+        None => Ok(default),
+        Some(value) => value
+            .parse()
+            .map_err(|e: String| AppError::bad_request(e, json!({ "field": field }))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::extract::Query;
+    use axum::http::Uri;
+
+    fn params(query: &str) -> Result<QrQueryParams, String> {
+        let uri: Uri = format!("/qr?{}", query).parse().unwrap();
+        Query::<QrQueryParams>::try_from_uri(&uri)
+            .map(|Query(params)| params)
+            .map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn applies_defaults() {
+        let options = params("").unwrap().to_options().unwrap();
+
+        assert_eq!(options.format, QrImageFormat::Png);
+        assert_eq!(options.size, DEFAULT_SIZE);
+        assert_eq!(options.margin, DEFAULT_MARGIN);
+        assert_eq!(options.ecc, QrErrorCorrection::Medium);
+        assert_eq!(options.foreground, QrColor::BLACK);
+        assert_eq!(options.background, QrColor::WHITE);
+    }
+
+    #[test]
+    fn parses_all_parameters() {
+        let options = params("format=svg&size=1024&margin=0&ecc=h&fg=%23ff0000&bg=00ff00")
+            .unwrap()
+            .to_options()
+            .unwrap();
+
+        assert_eq!(options.format, QrImageFormat::Svg);
+        assert_eq!(options.size, 1024);
+        assert_eq!(options.margin, 0);
+        assert_eq!(options.ecc, QrErrorCorrection::High);
+        assert_eq!(options.foreground, QrColor { r: 255, g: 0, b: 0 });
+        assert_eq!(options.background, QrColor { r: 0, g: 255, b: 0 });
+    }
+
+    #[test]
+    fn rejects_size_out_of_range() {
+        for size in [0, MIN_SIZE - 1, MAX_SIZE + 1] {
+            let params = params(&format!("size={}", size)).unwrap();
+            assert!(
+                params.to_options().is_err(),
+                "size {} must be rejected",
+                size
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_margin_out_of_range() {
+        let params = params(&format!("margin={}", MAX_MARGIN + 1)).unwrap();
+
+        assert!(params.to_options().is_err());
+    }
+
+    #[test]
+    fn rejects_bad_color() {
+        assert!(params("fg=blue").unwrap().to_options().is_err());
+        assert!(params("bg=%23abcd").unwrap().to_options().is_err());
+    }
+
+    #[test]
+    fn rejects_unknown_ecc_and_format() {
+        assert!(params("ecc=x").is_err());
+        assert!(params("format=gif").is_err());
+    }
+}
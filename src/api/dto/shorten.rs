@@ -1,11 +1,29 @@
 use crate::error::ErrorInfo;
+use crate::utils::code_generator::check_custom_code_format;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
-use validator::Validate;
+use validator::{Validate, ValidationError};
 
 lazy_static::lazy_static! {
-    static ref CUSTOM_CODE_REGEX: Regex =
-        Regex::new(r"^[a-z0-9-]+$").unwrap();
+    static ref TAG_REGEX: Regex =
+        Regex::new(r"^[A-Za-z0-9][A-Za-z0-9_-]{0,49}$").unwrap();
+}
+
+fn validate_tags(tags: &[String]) -> Result<(), ValidationError> {
+    if tags.iter().all(|t| TAG_REGEX.is_match(t.trim())) {
+        Ok(())
+    } else {
+        Err(ValidationError::new("tag_format").with_message(
+            "Tags must be 1-50 characters: letters, digits, hyphens and underscores".into(),
+        ))
+    }
+}
+
+/// Формат кастомного кода. Длину задаёт политика домена - её проверяет хендлер,
+/// когда домен уже известен.
+fn validate_custom_code(code: &str) -> Result<(), ValidationError> {
+    check_custom_code_format(code)
+        .map_err(|message| ValidationError::new("custom_code").with_message(message.into()))
 }
 
 #[derive(Debug, Deserialize, Validate)]
@@ -21,9 +39,18 @@ pub struct UrlItem {
 
     pub domain: Option<String>,
 
-    #[validate(length(min = 3, max = 20))]
-    #[validate(regex(path = "*CUSTOM_CODE_REGEX"))]
+    #[validate(custom(function = "validate_custom_code"))]
     pub custom_code: Option<String>,
+
+    #[serde(default)]
+    #[validate(length(max = 10), custom(function = "validate_tags"))]
+    pub tags: Vec<String>,
+
+    #[validate(length(min = 1, max = 255))]
+    pub title: Option<String>,
+
+    #[validate(length(max = 2000))]
+    pub notes: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -39,6 +66,10 @@ pub enum ShortenResultItem {
         long_url: String,
         code: String,
         short_url: String,
+        /// false - URL уже был сокращён с теми же параметрами, ссылка не изменилась
+        created: bool,
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        tags: Vec<String>,
     },
     Error {
         long_url: String,
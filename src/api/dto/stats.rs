@@ -2,7 +2,7 @@ use chrono::{DateTime, Utc};
 use serde::Serialize;
 
 use super::clicks::ClickInfo;
-use super::stats_list::PaginationMeta;
+use super::stats_list::{CreatedByInfo, PaginationMeta};
 
 #[derive(Debug, Serialize)]
 pub struct StatsResponse {
@@ -11,6 +11,37 @@ pub struct StatsResponse {
     pub domain: Option<String>,
     pub long_url: String,
     pub created_at: DateTime<Utc>,
+    pub tags: Vec<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_by: Option<CreatedByInfo>,
+
+    /// Пользователь-владелец ссылки
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+
+    /// OpenGraph-превью целевой страницы (заполняется фоново)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preview: Option<PagePreview>,
+
     pub total: i64,
     pub items: Vec<ClickInfo>,
 }
+
+#[derive(Debug, Serialize)]
+pub struct PagePreview {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub og_title: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub og_description: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub og_image: Option<String>,
+}
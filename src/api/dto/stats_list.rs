@@ -15,6 +15,27 @@ pub struct LinkStatsItem {
     pub long_url: String,
     pub total: i64,
     pub created_at: DateTime<Utc>,
+    pub tags: Vec<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_by: Option<CreatedByInfo>,
+
+    /// Пользователь-владелец ссылки
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+}
+
+/// Токен, которым создана ссылка
+#[derive(Debug, Serialize)]
+pub struct CreatedByInfo {
+    pub token_id: i64,
+    pub token_name: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -0,0 +1,13 @@
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct TagStatsItem {
+    pub name: String,
+    pub links_count: i64,
+    pub total_clicks: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TagListResponse {
+    pub items: Vec<TagStatsItem>,
+}
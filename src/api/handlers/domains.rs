@@ -1,26 +1,224 @@
-use crate::api::dto::domain::{DomainItem, DomainListResponse};
+use crate::api::dto::domain::{
+    CreateAliasRequest, CreateDomainRequest, DomainItem, DomainListResponse, UpdateDomainRequest,
+};
+use crate::domain::entities::{Domain, Principal, Scope};
 use crate::error::AppError;
+use crate::infrastructure::cache::domain_prefix;
 use crate::state::AppState;
-use axum::{Json, extract::State};
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use tracing::{error, info};
+use validator::Validate;
 
-/// GET /domains - Получение списка всех ссылок со статистикой
+/// GET /domains - Список всех доменов. Проверка владения (токены challenge)
+/// видна только с правом `domains:admin`
 pub async fn domain_list_handler(
     State(state): State<AppState>,
+    Extension(principal): Extension<Principal>,
 ) -> Result<Json<DomainListResponse>, AppError> {
-    let all_domains = state.domain_service.list_domains(false).await?;
+    let show_verification = principal.has_scope(Scope::DomainsAdmin);
 
-    // Формируем ответ
-    let items = all_domains
+    let items = state
+        .domain_service
+        .list_domain_overviews(false)
+        .await?
         .into_iter()
-        .map(|domain| DomainItem {
-            domain: domain.domain,
-            is_default: domain.is_default,
-            is_active: domain.is_active,
-            description: domain.description,
-            created_at: domain.created_at,
-            updated_at: domain.updated_at,
+        .map(DomainItem::from)
+        .map(|item| match show_verification {
+            true => item,
+            false => item.without_verification(),
         })
         .collect();
 
     Ok(Json(DomainListResponse { items }))
 }
+
+/// POST /domains - Добавление домена
+pub async fn domain_create_handler(
+    State(state): State<AppState>,
+    Json(payload): Json<CreateDomainRequest>,
+) -> Result<(StatusCode, Json<DomainItem>), AppError> {
+    payload.validate()?;
+
+    let created = state
+        .domain_service
+        .create_domain(payload.domain, false, payload.description, false)
+        .await?;
+
+    info!("Domain added (pending verification): {}", created.domain);
+    state.domains_changed().await;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(DomainItem::new(created, 0, Vec::new())),
+    ))
+}
+
+/// PATCH /domains/{domain} - Включение/выключение домена, описание, оформление, редиректы
+/// и политика кодов
+pub async fn domain_update_handler(
+    State(state): State<AppState>,
+    Path(domain): Path<String>,
+    Json(payload): Json<UpdateDomainRequest>,
+) -> Result<Json<DomainItem>, AppError> {
+    payload.validate()?;
+
+    let update = payload.into_update()?;
+
+    let current = state.domain_service.get_domain(&domain).await?;
+    let updated = state
+        .domain_service
+        .update_domain(current.id, update)
+        .await?;
+
+    state.domains_changed().await;
+
+    // Редиректы выключенного домена не должны отдаваться из кэша
+    if current.is_active && !updated.is_active {
+        info!("Domain deactivated: {}", updated.domain);
+        if let Err(e) = state
+            .cache
+            .invalidate_prefix(&domain_prefix(&updated.domain))
+            .await
+        {
+            error!("Failed to invalidate cache for {}: {}", updated.domain, e);
+        }
+    }
+
+    Ok(Json(domain_item(&state, updated).await?))
+}
+
+/// DELETE /domains/{domain} - Удаление домена без ссылок
+pub async fn domain_delete_handler(
+    State(state): State<AppState>,
+    Path(domain): Path<String>,
+) -> Result<StatusCode, AppError> {
+    let domain = state.domain_service.get_domain(&domain).await?;
+    state.domain_service.delete_domain(domain.id).await?;
+    state.domains_changed().await;
+
+    info!("Domain removed: {}", domain.domain);
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// POST /domains/{domain}/verify - Проверка владения доменом (TXT запись или HTTP challenge)
+pub async fn domain_verify_handler(
+    State(state): State<AppState>,
+    Path(domain): Path<String>,
+) -> Result<Json<DomainItem>, AppError> {
+    let domain = state.domain_service.get_domain(&domain).await?;
+
+    // Уже подтверждённый домен повторно не проверяем
+    let Some(challenge) = domain.verification_challenge() else {
+        return Ok(Json(domain_item(&state, domain).await?));
+    };
+
+    let outcome = state.domain_verifier.check(&challenge).await;
+    let verified = state
+        .domain_service
+        .complete_verification(&domain, outcome)
+        .await?;
+    state.domains_changed().await;
+
+    info!(
+        "Domain verified: {} ({})",
+        verified.domain,
+        verified.verification.method.map_or("-", |m| m.as_str())
+    );
+
+    Ok(Json(domain_item(&state, verified).await?))
+}
+
+/// POST /domains/{domain}/default - Сделать домен доменом по умолчанию
+pub async fn domain_set_default_handler(
+    State(state): State<AppState>,
+    Path(domain): Path<String>,
+) -> Result<Json<DomainItem>, AppError> {
+    let domain = state.domain_service.get_domain(&domain).await?;
+    state.domain_service.set_default(domain.id).await?;
+    state.domains_changed().await;
+
+    info!("Default domain changed: {}", domain.domain);
+
+    let domain = state.domain_service.get_domain(&domain.domain).await?;
+
+    Ok(Json(domain_item(&state, domain).await?))
+}
+
+/// POST /domains/{domain}/aliases - Добавить алиас или wildcard шаблон
+pub async fn domain_alias_create_handler(
+    State(state): State<AppState>,
+    Path(domain): Path<String>,
+    Json(payload): Json<CreateAliasRequest>,
+) -> Result<(StatusCode, Json<DomainItem>), AppError> {
+    payload.validate()?;
+
+    let domain = state.domain_service.get_domain(&domain).await?;
+    let alias = state
+        .domain_service
+        .add_alias(&domain, payload.host, false)
+        .await?;
+
+    info!(
+        "Alias added (pending verification): {} -> {}",
+        alias.host, domain.domain
+    );
+
+    Ok((
+        StatusCode::CREATED,
+        Json(domain_item(&state, domain).await?),
+    ))
+}
+
+/// POST /domains/{domain}/aliases/{host}/verify - Проверка владения алиасом
+/// (для wildcard - его суффиксом)
+pub async fn domain_alias_verify_handler(
+    State(state): State<AppState>,
+    Path((domain, host)): Path<(String, String)>,
+) -> Result<Json<DomainItem>, AppError> {
+    let domain = state.domain_service.get_domain(&domain).await?;
+    let alias = state.domain_service.get_alias(&domain, &host).await?;
+
+    // Уже подтверждённый алиас повторно не проверяем
+    if let Some(challenge) = alias.verification_challenge() {
+        let outcome = state.domain_verifier.check(&challenge).await;
+        let verified = state
+            .domain_service
+            .complete_alias_verification(&alias, outcome)
+            .await?;
+        state.domains_changed().await;
+
+        info!(
+            "Alias verified: {} -> {} ({})",
+            verified.host,
+            domain.domain,
+            verified.verification.method.map_or("-", |m| m.as_str())
+        );
+    }
+
+    Ok(Json(domain_item(&state, domain).await?))
+}
+
+/// DELETE /domains/{domain}/aliases/{host} - Удалить алиас
+pub async fn domain_alias_delete_handler(
+    State(state): State<AppState>,
+    Path((domain, host)): Path<(String, String)>,
+) -> Result<StatusCode, AppError> {
+    let domain = state.domain_service.get_domain(&domain).await?;
+    state.domain_service.remove_alias(&domain, &host).await?;
+    state.domains_changed().await;
+
+    info!("Alias removed: {} -> {}", host, domain.domain);
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn domain_item(state: &AppState, domain: Domain) -> Result<DomainItem, AppError> {
+    let links_count = state.domain_service.count_links(domain.id).await?;
+    let aliases = state.domain_service.list_aliases(domain.id).await?;
+    Ok(DomainItem::new(domain, links_count, aliases))
+}
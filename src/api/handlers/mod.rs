@@ -1,13 +1,23 @@
 pub mod domains;
 pub mod health;
+pub mod qr;
 pub mod redirect;
 pub mod shorten;
 pub mod stats;
 pub mod stats_list;
+pub mod tags;
+pub mod well_known;
 
-pub use domains::domain_list_handler;
+pub use domains::{
+    domain_alias_create_handler, domain_alias_delete_handler, domain_alias_verify_handler,
+    domain_create_handler, domain_delete_handler, domain_list_handler, domain_set_default_handler,
+    domain_update_handler, domain_verify_handler,
+};
 pub use health::health_handler;
-pub use redirect::redirect_handler;
+pub use qr::qr_handler;
+pub use redirect::{redirect_handler, root_handler};
 pub use shorten::shorten_handler;
 pub use stats::stats_handler;
 pub use stats_list::stats_list_handler;
+pub use tags::tag_list_handler;
+pub use well_known::verification_challenge_handler;
@@ -0,0 +1,59 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::header,
+    response::{IntoResponse, Response},
+};
+
+use serde_json::json;
+
+use crate::api::dto::qr::QrQueryParams;
+use crate::error::AppError;
+use crate::state::AppState;
+use crate::utils::qr::render_qr;
+
+/// GET /links/{domain}/{code}/qr - QR код короткой ссылки (PNG или SVG)
+pub async fn qr_handler(
+    State(state): State<AppState>,
+    Path((domain_name, code)): Path<(String, String)>,
+    Query(params): Query<QrQueryParams>,
+) -> Result<Response, AppError> {
+    let options = params.to_options()?;
+
+    let domain = state.domain_service.get_domain(&domain_name).await?;
+    let link = state
+        .link_service
+        .get_link_by_code(&code, domain.id)
+        .await?;
+
+    let short_url = state.link_service.get_short_url(&domain.domain, &link.code);
+
+    // Растеризация PNG до 2048x2048 занимает CPU - не на рабочем потоке tokio
+    let render_options = options.clone();
+    let image = tokio::task::spawn_blocking(move || render_qr(&short_url, &render_options))
+        .await
+        .map_err(|e| {
+            AppError::internal(
+                "QR rendering task failed",
+                json!({ "error": e.to_string() }),
+            )
+        })??;
+
+    let disposition = format!(
+        "inline; filename=\"{}.{}\"",
+        link.code,
+        options.format.extension()
+    );
+
+    Ok((
+        [
+            (
+                header::CONTENT_TYPE,
+                options.format.content_type().to_string(),
+            ),
+            (header::CONTENT_DISPOSITION, disposition),
+            (header::CACHE_CONTROL, "private, max-age=3600".to_string()),
+        ],
+        image,
+    )
+        .into_response())
+}
@@ -1,15 +1,19 @@
 use axum::{
     extract::{Path, State},
     http::{HeaderMap, header},
-    response::{IntoResponse, Redirect},
+    response::{IntoResponse, Redirect, Response},
 };
+use serde_json::json;
 use std::net::SocketAddr;
 use tracing::{debug, error};
 
 use crate::domain::click_event::ClickEvent;
+use crate::domain::entities::Domain;
 use crate::error::AppError;
+use crate::infrastructure::cache::redirect_key;
 use crate::state::AppState;
 use crate::utils::extract_domain::extract_domain_from_headers;
+use crate::web::pages::{link_error_page, wants_html};
 
 /// GET /:code - Редирект на оригинальный URL
 pub async fn redirect_handler(
@@ -17,74 +21,150 @@ pub async fn redirect_handler(
     State(state): State<AppState>,
     headers: HeaderMap,
     axum::extract::ConnectInfo(addr): axum::extract::ConnectInfo<SocketAddr>,
-) -> Result<impl IntoResponse, AppError> {
-    // 1. Получаем домен из Host header
-    let domain = extract_domain_from_headers(&headers)?;
+) -> Result<Response, AppError> {
+    // 1. Получаем хост из Host header (или X-Forwarded-Host от доверенного прокси)
+    let trusted_proxies = &state.config.trusted_proxies;
+    let host = extract_domain_from_headers(&headers, addr.ip(), trusted_proxies)?;
+    let client_ip = trusted_proxies.client_ip(addr.ip(), &headers);
+
+    // 2. Домен хоста: точное имя, алиас или wildcard (кэшируется в памяти)
+    let domain = match state.domain_for_host(&host).await {
+        Ok(domain) => domain,
+        Err(e) => return Ok(link_not_found(&headers, &host, None, e)),
+    };
+
+    let long_url = match resolve_long_url(&state, &domain, &code).await {
+        Ok(long_url) => long_url,
+        Err(e) => return Ok(link_not_found(&headers, &host, Some(&domain), e)),
+    };
+
+    // 7. Отправляем событие клика в очередь (воркер сам найдёт link_id)
+    let click_event = ClickEvent::new(
+        domain.domain,
+        code,
+        Some(client_ip.to_string()),
+        headers
+            .get(header::USER_AGENT)
+            .and_then(|v| v.to_str().ok()),
+        headers.get(header::REFERER).and_then(|v| v.to_str().ok()),
+    );
+
+    // Игнорируем ошибку, если очередь переполнена
+    let _ = state.click_sender.try_send(click_event);
+
+    // 8. Редирект
+    Ok(Redirect::temporary(&long_url).into_response())
+}
+
+/// GET / - Редирект с корня домена на заданный для него адрес
+pub async fn root_handler(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    axum::extract::ConnectInfo(addr): axum::extract::ConnectInfo<SocketAddr>,
+) -> Result<Response, AppError> {
+    let host = extract_domain_from_headers(&headers, addr.ip(), &state.config.trusted_proxies)?;
+
+    let domain = match state.domain_for_host(&host).await {
+        Ok(domain) => domain,
+        Err(e) => return Ok(link_not_found(&headers, &host, None, e)),
+    };
+
+    let root_url = ensure_active(&domain).map(|_| domain.redirects.root_url.as_deref());
+
+    match root_url {
+        Ok(Some(root_url)) => Ok(Redirect::temporary(root_url).into_response()),
+        Ok(None) => {
+            let e = AppError::not_found("No root page for domain", json!({"domain": host}));
+            Ok(link_not_found(&headers, &host, Some(&domain), e))
+        }
+        Err(e) => Ok(link_not_found(&headers, &host, Some(&domain), e)),
+    }
+}
+
+/// Ответ на несуществующую ссылку: fallback редирект домена,
+/// страница 404/410 в оформлении домена для браузера или JSON
+fn link_not_found(
+    headers: &HeaderMap,
+    host: &str,
+    domain: Option<&Domain>,
+    error: AppError,
+) -> Response {
+    // Выключенный домен (410) никуда не перенаправляет
+    if matches!(error, AppError::NotFound { .. })
+        && let Some(url) = domain
+            .filter(|d| d.is_active)
+            .and_then(|d| d.redirects.not_found_url.as_deref())
+    {
+        return Redirect::temporary(url).into_response();
+    }
+
+    if wants_html(headers) {
+        let branding = domain.map(|d| &d.branding);
+        return link_error_page(error, host, branding);
+    }
+
+    error.into_response()
+}
+
+/// Целевой URL ссылки: из кэша или из БД. Ключ кэша строится по основному
+/// имени домена, поэтому алиасы делят записи с ним.
+async fn resolve_long_url(
+    state: &AppState,
+    domain: &Domain,
+    code: &str,
+) -> Result<String, AppError> {
+    // Выключенный домен больше не обслуживает редиректы
+    ensure_active(domain)?;
 
     // 2. Строим cache key: domain:code
-    let cache_key = format!("{}:{}", domain, code);
+    let cache_key = redirect_key(&domain.domain, code);
 
-    // 3. Пытаемся получить из кэша
-    let long_url = match state.cache.get_url(&cache_key).await {
+    // 3. Пытаемся получить из кэша (выключение домена очищает его записи)
+    match state.cache.get_url(&cache_key).await {
         Ok(Some(cached_url)) => {
             debug!("Cache HIT for {}", cache_key);
-            cached_url
+            Ok(cached_url)
         }
         Ok(None) => {
             debug!("Cache MISS for {}", cache_key);
 
-            // 4. Находим домен в БД
-            let domain_entity = state.domain_service.get_domain(&domain).await?;
-
-            // 5. Ищем ссылку по коду и domain_id
-            let link = state
-                .link_service
-                .get_link_by_code(&code, domain_entity.id)
-                .await?;
+            // 4-5. Находим ссылку в БД
+            let long_url = find_long_url(state, domain, code).await?;
 
             // 6. Сохраняем в кэш (асинхронно)
             let cache_clone = state.cache.clone();
-            let cache_key_clone = cache_key.clone();
-            let url_clone = link.long_url.clone();
+            let url_clone = long_url.clone();
             tokio::spawn(async move {
-                if let Err(e) = cache_clone
-                    .set_url(&cache_key_clone, &url_clone, None)
-                    .await
-                {
+                if let Err(e) = cache_clone.set_url(&cache_key, &url_clone, None).await {
                     error!("Failed to cache URL: {}", e);
                 }
             });
 
-            link.long_url
+            Ok(long_url)
         }
         Err(e) => {
             error!("Cache error: {}", e);
 
             // Fallback на БД
-            let domain_entity = state.domain_service.get_domain(&domain).await?;
-            let link = state
-                .link_service
-                .get_link_by_code(&code, domain_entity.id)
-                .await?;
-
-            link.long_url
+            find_long_url(state, domain, code).await
         }
-    };
+    }
+}
 
-    // 7. Отправляем событие клика в очередь (воркер сам найдёт link_id)
-    let click_event = ClickEvent::new(
-        domain,
-        code,
-        Some(addr.ip().to_string()),
-        headers
-            .get(header::USER_AGENT)
-            .and_then(|v| v.to_str().ok()),
-        headers.get(header::REFERER).and_then(|v| v.to_str().ok()),
-    );
+async fn find_long_url(state: &AppState, domain: &Domain, code: &str) -> Result<String, AppError> {
+    let link = state.link_service.get_link_by_code(code, domain.id).await?;
 
-    // Игнорируем ошибку, если очередь переполнена
-    let _ = state.click_sender.try_send(click_event);
+    Ok(link.long_url)
+}
 
-    // 8. Редирект
-    Ok(Redirect::temporary(&long_url))
+/// Выключенный домен больше не обслуживает редиректы
+fn ensure_active(domain: &Domain) -> Result<(), AppError> {
+    if domain.is_active {
+        return Ok(());
+    }
+
+    Err(AppError::gone(
+        "Domain is disabled",
+        json!({"domain": domain.domain}),
+    ))
 }
@@ -1,20 +1,78 @@
-use axum::{Json, extract::State};
+use axum::{
+    Extension, Json,
+    extract::State,
+    http::{HeaderMap, HeaderName},
+    response::{IntoResponse, Response},
+};
+use chrono::{Duration, NaiveTime, Utc};
+use serde_json::json;
 use validator::Validate;
 
 use crate::api::dto::shorten::{
     BatchSummary, ShortenRequest, ShortenResponse, ShortenResultItem, UrlItem,
 };
+use crate::application::services::ShortenedLink;
+use crate::domain::entities::{LinkMetadata, Principal, Scope};
+use crate::domain::metadata_job::MetadataJob;
 use crate::error::AppError;
 use crate::state::AppState;
 
 /// POST /shorten - Создание коротких ссылок
 pub async fn shorten_handler(
     State(state): State<AppState>,
+    principal: Option<Extension<Principal>>,
     Json(payload): Json<ShortenRequest>,
-) -> Result<Json<ShortenResponse>, AppError> {
+) -> Result<Response, AppError> {
+    let principal = principal.map(|Extension(principal)| principal);
+
+    // Без токена создавать ссылки можно только на доменах из ANONYMOUS_SHORTEN_DOMAINS
+    match &principal {
+        Some(principal) => principal.require_scope(Scope::LinksWrite)?,
+        None if state.config.anonymous_shorten_domains.is_empty() => {
+            return Err(AppError::unauthorized(
+                "Unauthorized",
+                json!({"reason": "Authorization header is missing or invalid"}),
+            ));
+        }
+        None => {}
+    }
+
     payload.validate()?;
 
     let total = payload.urls.len();
+
+    // Суточная квота токена: резервируется сразу на весь запрос, чтобы
+    // параллельные запросы не превысили её вместе
+    let quota = match principal
+        .as_ref()
+        .and_then(|p| p.token_id.zip(p.limits.daily_link_quota))
+    {
+        Some((token_id, limit)) => {
+            let usage = state
+                .link_service
+                .reserve_daily_quota(token_id, total as i64, limit as i64)
+                .await?;
+
+            if !usage.reserved {
+                let mut response = AppError::too_many_requests(
+                    "Daily link quota exceeded",
+                    json!({
+                        "quota": limit,
+                        "used": usage.used,
+                        "requested": total
+                    }),
+                    seconds_until_next_day(),
+                )
+                .into_response();
+                set_quota_headers(response.headers_mut(), limit, usage.used);
+                return Ok(response);
+            }
+
+            Some((limit, usage))
+        }
+        None => None,
+    };
+
     let mut results = Vec::with_capacity(total);
     let mut successful = 0;
     let mut failed = 0;
@@ -23,13 +81,15 @@ pub async fn shorten_handler(
         let long_url = item.url.clone();
 
         // Обрабатываем каждую ссылку независимо
-        match process_single_url(&state, item).await {
-            Ok((code, short_url)) => {
+        match process_single_url(&state, item, principal.as_ref()).await {
+            Ok((code, short_url, created, tags)) => {
                 successful += 1;
                 results.push(ShortenResultItem::Success {
                     long_url,
                     code,
                     short_url,
+                    created,
+                    tags,
                 });
             }
             Err(err) => {
@@ -42,29 +102,168 @@ pub async fn shorten_handler(
         }
     }
 
-    Ok(Json(ShortenResponse {
-        summary: BatchSummary {
-            total,
-            successful,
-            failed,
-        },
-        items: results,
-    }))
+    // Квоту расходуют только новые ссылки
+    let mut headers = HeaderMap::new();
+    if let Some((limit, mut usage)) = quota {
+        let unused = unused_quota(&results);
+        if unused > 0 {
+            usage = state
+                .link_service
+                .release_daily_quota(usage, unused as i64)
+                .await?;
+        }
+        set_quota_headers(&mut headers, limit, usage.used);
+    }
+
+    Ok((
+        headers,
+        Json(ShortenResponse {
+            summary: BatchSummary {
+                total,
+                successful,
+                failed,
+            },
+            items: results,
+        }),
+    )
+        .into_response())
+}
+
+/// Сколько зарезервированной квоты вернуть: ошибки и уже существующие ссылки
+/// новых ссылок не создали
+fn unused_quota(items: &[ShortenResultItem]) -> usize {
+    items
+        .iter()
+        .filter(|item| !matches!(item, ShortenResultItem::Success { created: true, .. }))
+        .count()
+}
+
+fn set_quota_headers(headers: &mut HeaderMap, limit: u32, used: i64) {
+    headers.insert(X_QUOTA_LIMIT, limit.into());
+    headers.insert(X_QUOTA_REMAINING, (limit as i64 - used).max(0).into());
 }
 
-async fn process_single_url(state: &AppState, item: UrlItem) -> Result<(String, String), AppError> {
+const X_QUOTA_LIMIT: HeaderName = HeaderName::from_static("x-quota-limit");
+const X_QUOTA_REMAINING: HeaderName = HeaderName::from_static("x-quota-remaining");
+
+/// Секунд до сброса суточной квоты (полночь UTC)
+fn seconds_until_next_day() -> u64 {
+    let now = Utc::now();
+    let next_day = (now.date_naive() + Duration::days(1))
+        .and_time(NaiveTime::MIN)
+        .and_utc();
+
+    (next_day - now).num_seconds().max(1) as u64
+}
+
+async fn process_single_url(
+    state: &AppState,
+    item: UrlItem,
+    principal: Option<&Principal>,
+) -> Result<(String, String, bool, Vec<String>), AppError> {
     let domain = if let Some(domain_name) = item.domain {
         state.domain_service.get_domain(&domain_name).await?
     } else {
         state.domain_service.get_default_domain().await?
     };
 
-    let link = state
+    // Ссылки на выключенном домене всё равно не открывались бы
+    if !domain.is_active {
+        return Err(AppError::bad_request(
+            "Domain is inactive",
+            json!({ "domain": domain.domain }),
+        ));
+    }
+
+    match principal {
+        Some(principal) => principal.require_domain(&domain)?,
+        None if !state.config.allows_anonymous_shorten(&domain.domain) => {
+            return Err(AppError::unauthorized(
+                "Anonymous link creation is not allowed on this domain",
+                json!({ "domain": domain.domain }),
+            ));
+        }
+        None => {}
+    }
+
+    let metadata = LinkMetadata {
+        title: item
+            .title
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty()),
+        notes: item.notes.filter(|n| !n.trim().is_empty()),
+        created_by: principal.and_then(|p| p.token_id),
+        owner_id: principal.and_then(Principal::user_id),
+        ..Default::default()
+    };
+
+    let ShortenedLink {
+        link,
+        created,
+        tags,
+    } = state
         .link_service
-        .create_short_link_for_domain(item.url, domain.id, item.custom_code)
+        .create_short_link_for_domain(item.url, &domain, item.custom_code, metadata, item.tags)
         .await?;
 
+    // Заголовок не задан - загружаем title/OpenGraph новой страницы в фоне
+    if let Some(sender) = &state.metadata_sender
+        && created
+        && link.metadata.title.is_none()
+        && link.metadata.og_title.is_none()
+        && let Err(e) = sender.try_send(MetadataJob::new(link.id, link.long_url.clone()))
+    {
+        metrics::counter!("metadata_queue_dropped_total").increment(1);
+        tracing::warn!(link_id = link.id, error = %e, "Failed to enqueue metadata job");
+    }
+
+    // Новая ссылка создана вместе с тегами; существующей - дописываем
+    if !created {
+        state.tag_service.attach_tags(link.id, tags.clone()).await?;
+    }
+
     let short_url = state.link_service.get_short_url(&domain.domain, &link.code);
 
-    Ok((link.code, short_url))
+    Ok((link.code, short_url, created, tags))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn success(created: bool) -> ShortenResultItem {
+        ShortenResultItem::Success {
+            long_url: "https://example.com/".to_string(),
+            code: "abc".to_string(),
+            short_url: "https://s.example/abc".to_string(),
+            created,
+            tags: Vec::new(),
+        }
+    }
+
+    fn failure() -> ShortenResultItem {
+        ShortenResultItem::Error {
+            long_url: "https://example.com/".to_string(),
+            error: AppError::bad_request("Invalid URL", json!({})).to_error_info(),
+        }
+    }
+
+    #[test]
+    fn repeated_shorten_leaves_remaining_quota_unchanged() {
+        // Повтор уже сокращённой ссылки: резерв на 1 возвращается целиком
+        let limit = 10;
+        let used_before = 4;
+        let items = [success(false)];
+
+        let used_after = used_before + items.len() - unused_quota(&items);
+
+        assert_eq!(limit - used_after, limit - used_before);
+    }
+
+    #[test]
+    fn only_created_links_consume_quota() {
+        let items = [success(true), success(false), failure(), success(true)];
+
+        assert_eq!(unused_quota(&items), 2);
+    }
 }
@@ -4,8 +4,8 @@ use axum::{
 };
 
 use crate::api::dto::pagination::StatsQueryParams;
-use crate::api::dto::stats::StatsResponse;
-use crate::api::dto::stats_list::PaginationMeta;
+use crate::api::dto::stats::{PagePreview, StatsResponse};
+use crate::api::dto::stats_list::{CreatedByInfo, PaginationMeta};
 use crate::domain::repositories::StatsFilter;
 use crate::error::AppError;
 use crate::state::AppState;
@@ -46,6 +46,16 @@ pub async fn stats_handler(
     // Вычисляем количество страниц
     let total_pages = (detailed_stats.total as f64 / page_size as f64).ceil() as u32;
 
+    let metadata = &detailed_stats.link.metadata;
+    let preview = (metadata.og_title.is_some()
+        || metadata.og_description.is_some()
+        || metadata.og_image.is_some())
+    .then(|| PagePreview {
+        og_title: metadata.og_title.clone(),
+        og_description: metadata.og_description.clone(),
+        og_image: metadata.og_image.clone(),
+    });
+
     // Преобразуем в DTO
     let response = StatsResponse {
         pagination: PaginationMeta {
@@ -58,6 +68,19 @@ pub async fn stats_handler(
         domain: detailed_stats.link.domain,
         long_url: detailed_stats.link.long_url,
         created_at: detailed_stats.link.created_at,
+        tags: detailed_stats.tags,
+        title: detailed_stats.link.metadata.title,
+        notes: detailed_stats.link.metadata.notes,
+        created_by: detailed_stats
+            .link
+            .metadata
+            .created_by
+            .map(|token_id| CreatedByInfo {
+                token_id,
+                token_name: detailed_stats.created_by_name,
+            }),
+        owner: detailed_stats.owner_name,
+        preview,
         total: detailed_stats.total,
         items: detailed_stats
             .items
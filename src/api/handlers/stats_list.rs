@@ -3,8 +3,10 @@ use axum::{
     extract::{Query, State},
 };
 
-use crate::api::dto::pagination::StatsQueryParams;
-use crate::api::dto::stats_list::{LinkStatsItem, PaginationMeta, StatsListResponse};
+use crate::api::dto::pagination::StatsListQueryParams;
+use crate::api::dto::stats_list::{
+    CreatedByInfo, LinkStatsItem, PaginationMeta, StatsListResponse,
+};
 use crate::domain::repositories::StatsFilter;
 use crate::error::AppError;
 use crate::state::AppState;
@@ -13,7 +15,7 @@ use serde_json::json;
 /// GET /stats - Получение списка всех ссылок со статистикой
 pub async fn stats_list_handler(
     State(state): State<AppState>,
-    Query(params): Query<StatsQueryParams>,
+    Query(params): Query<StatsListQueryParams>,
 ) -> Result<Json<StatsListResponse>, AppError> {
     // Валидация параметров пагинации
     let (offset, limit) = params
@@ -31,14 +33,24 @@ pub async fn stats_list_handler(
         None
     };
 
+    if params.q.as_ref().is_some_and(|q| q.chars().count() > 200) {
+        return Err(AppError::bad_request(
+            "Search query is too long",
+            json!({ "max_length": 200 }),
+        ));
+    }
+
     let filter = StatsFilter::new(offset, limit)
         .with_domain(domain_id)
-        .with_date_range(params.date_filter.from, params.date_filter.to);
+        .with_date_range(params.date_filter.from, params.date_filter.to)
+        .with_sort(params.sort.field(), params.sort.direction())
+        .with_search(params.q)
+        .with_tag(params.tag);
 
-    // Получаем данные и общее количество
+    // Получаем данные и общее количество (с теми же фильтрами)
     let (all_stats, total_items) = tokio::try_join!(
-        state.stats_service.get_all_stats(filter),
-        state.stats_service.count_all_links()
+        state.stats_service.get_all_stats(filter.clone()),
+        state.stats_service.count_all_links(&filter)
     )?;
 
     // Формируем ответ
@@ -50,6 +62,14 @@ pub async fn stats_list_handler(
             long_url: stat.long_url,
             total: stat.total,
             created_at: stat.created_at,
+            tags: stat.tags,
+            title: stat.title,
+            notes: stat.notes,
+            created_by: stat.created_by.map(|token_id| CreatedByInfo {
+                token_id,
+                token_name: stat.created_by_name,
+            }),
+            owner: stat.owner_name,
         })
         .collect();
 
@@ -0,0 +1,38 @@
+use axum::{
+    Json,
+    extract::{Query, State},
+};
+
+use crate::api::dto::pagination::TagStatsQueryParams;
+use crate::api::dto::tag::{TagListResponse, TagStatsItem};
+use crate::error::AppError;
+use crate::state::AppState;
+
+/// GET /tags - Статистика по тегам (кампаниям)
+pub async fn tag_list_handler(
+    State(state): State<AppState>,
+    Query(params): Query<TagStatsQueryParams>,
+) -> Result<Json<TagListResponse>, AppError> {
+    let domain_id = if let Some(domain_name) = &params.domain {
+        let domain = state.domain_service.get_domain(domain_name).await?;
+        Some(domain.id)
+    } else {
+        None
+    };
+
+    let stats = state
+        .tag_service
+        .list_tag_stats(domain_id, params.date_filter.from, params.date_filter.to)
+        .await?;
+
+    let items = stats
+        .into_iter()
+        .map(|tag| TagStatsItem {
+            name: tag.name,
+            links_count: tag.links_count,
+            total_clicks: tag.total_clicks,
+        })
+        .collect();
+
+    Ok(Json(TagListResponse { items }))
+}
@@ -0,0 +1,31 @@
+use axum::{
+    extract::{ConnectInfo, Path, State},
+    http::HeaderMap,
+};
+use serde_json::json;
+use std::net::SocketAddr;
+
+use crate::error::AppError;
+use crate::state::AppState;
+use crate::utils::extract_domain::extract_domain_from_headers;
+
+/// GET /.well-known/url-shortener/{token} - HTTP challenge для домена или алиаса,
+/// который уже направлен на сервис (DNS указывает сюда, а TXT записи нет)
+pub async fn verification_challenge_handler(
+    State(state): State<AppState>,
+    Path(token): Path<String>,
+    headers: HeaderMap,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+) -> Result<String, AppError> {
+    let host = extract_domain_from_headers(&headers, addr.ip(), &state.config.trusted_proxies)?;
+    let challenges = state.domain_service.pending_challenges(&host).await?;
+
+    // Отдаём токен только ожидающему проверки имени и только свой
+    challenges
+        .into_iter()
+        .find(|challenge| challenge.token == token)
+        .map(|challenge| challenge.token)
+        .ok_or_else(|| {
+            AppError::not_found("Verification challenge not found", json!({"host": host}))
+        })
+}
@@ -1,12 +1,18 @@
 use axum::{
     extract::{FromRequestParts, Request, State},
+    http::header::AUTHORIZATION,
     middleware::Next,
     response::Response,
 };
 use axum_auth::AuthBearer;
 
+use crate::application::services::session_service::SESSION_COOKIE;
+use crate::domain::entities::{Principal, Scope};
+use crate::utils::cookies::get_cookie;
 use crate::{error::AppError, state::AppState};
 
+/// Аутентификация по Bearer токену или, без заголовка Authorization,
+/// по cookie сессии дашборда
 pub async fn layer(
     State(st): State<AppState>,
     req: Request,
@@ -14,21 +20,78 @@ pub async fn layer(
 ) -> Result<Response, AppError> {
     let (mut parts, body) = req.into_parts();
 
-    // Извлекаем Bearer токен из заголовка Authorization
-    let AuthBearer(token) = AuthBearer::from_request_parts(&mut parts, &())
-        .await
-        .map_err(|_| {
-            AppError::unauthorized(
-                "Unauthorized",
-                serde_json::json!({"reason": "Authorization header is missing or invalid"}),
-            )
-        })?;
+    let principal = if parts.headers.contains_key(AUTHORIZATION) {
+        // Извлекаем Bearer токен из заголовка Authorization
+        let AuthBearer(token) = AuthBearer::from_request_parts(&mut parts, &())
+            .await
+            .map_err(|_| missing_credentials())?;
+
+        let api_token = st.auth_service.authenticate(&token).await?;
+        st.auth_service.principal(api_token).await?
+    } else {
+        let session = get_cookie(&parts.headers, SESSION_COOKIE).ok_or_else(missing_credentials)?;
+        authenticate_session(&st, session).await?
+    };
+
+    // Прикрепляем субъект к запросу
+    parts.extensions.insert(principal);
 
     // Собираем Request обратно
     let req = Request::from_parts(parts, body);
 
-    // Аутентифицируем через сервис
-    st.auth_service.authenticate(&token).await?;
+    Ok(next.run(req).await)
+}
+
+/// Необязательная аутентификация: без заголовка Authorization и cookie сессии
+/// запрос проходит анонимно, но переданные учётные данные обязаны быть валидными
+pub async fn optional_layer(
+    State(st): State<AppState>,
+    req: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    if !req.headers().contains_key(AUTHORIZATION)
+        && get_cookie(req.headers(), SESSION_COOKIE).is_none()
+    {
+        return Ok(next.run(req).await);
+    }
+
+    layer(State(st), req, next).await
+}
+
+/// Субъект сессии дашборда (сессия, токен и пользователь должны быть активны)
+pub async fn authenticate_session(
+    st: &AppState,
+    cookie_value: &str,
+) -> Result<Principal, AppError> {
+    let session = st.session_service.verify(cookie_value).await?;
+
+    match (session.token_id, session.user_id) {
+        (Some(token_id), _) => {
+            let api_token = st.auth_service.authenticate_by_id(token_id).await?;
+            st.auth_service.principal(api_token).await
+        }
+        (None, Some(user_id)) => st.auth_service.user_principal(user_id).await,
+        (None, None) => Err(missing_credentials()),
+    }
+}
+
+/// Проверка права доступа для маршрута (после `layer`)
+pub async fn require_scope(scope: Scope, req: Request, next: Next) -> Result<Response, AppError> {
+    let principal = req.extensions().get::<Principal>().ok_or_else(|| {
+        AppError::unauthorized(
+            "Unauthorized",
+            serde_json::json!({"reason": "Authentication required"}),
+        )
+    })?;
+
+    principal.require_scope(scope)?;
 
     Ok(next.run(req).await)
 }
+
+fn missing_credentials() -> AppError {
+    AppError::unauthorized(
+        "Unauthorized",
+        serde_json::json!({"reason": "Authorization header is missing or invalid"}),
+    )
+}
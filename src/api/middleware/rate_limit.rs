@@ -1,34 +1,207 @@
-use governor::clock::QuantaInstant;
-use governor::middleware::NoOpMiddleware;
-use std::sync::Arc;
-use tower_governor::{
-    GovernorLayer, governor::GovernorConfigBuilder, key_extractor::PeerIpKeyExtractor,
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::{HeaderMap, HeaderName, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
 };
+use serde_json::json;
+use std::net::{IpAddr, SocketAddr};
 
-/// Базовый rate limiter для API (100 req/min)
-pub fn layer() -> GovernorLayer<PeerIpKeyExtractor, NoOpMiddleware<QuantaInstant>, axum::body::Body>
-{
-    let governor_conf = Arc::new(
-        GovernorConfigBuilder::default()
-            .per_second(2)
-            .burst_size(100)
-            .finish()
-            .unwrap(),
-    );
+use crate::config::Config;
+use crate::domain::entities::Principal;
+use crate::error::AppError;
+use crate::infrastructure::rate_limit::{RateLimit, RateLimitDecision};
+use crate::state::AppState;
+use crate::utils::client_ip::TrustedProxies;
+
+/// Ключ rate limiting: аутентифицированный токен, пользователь или IP клиента
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RateLimitKey {
+    Token(i64),
+    User(i64),
+    Ip(IpAddr),
+}
+
+impl RateLimitKey {
+    /// Извлечь ключ из запроса (после auth middleware)
+    pub fn extract(req: &Request, trusted_proxies: &TrustedProxies) -> Option<Self> {
+        if let Some(principal) = req.extensions().get::<Principal>() {
+            if let Some(token_id) = principal.token_id {
+                return Some(Self::Token(token_id));
+            }
+            if let Some(user_id) = principal.user_id() {
+                return Some(Self::User(user_id));
+            }
+        }
+
+        Self::client_ip(req, trusted_proxies)
+    }
+
+    /// Ключ по IP клиента (без учёта токена). За доверенным прокси -
+    /// адрес из `Forwarded` / `X-Forwarded-For` / `X-Real-IP`.
+    pub fn client_ip(req: &Request, trusted_proxies: &TrustedProxies) -> Option<Self> {
+        req.extensions()
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| Self::Ip(trusted_proxies.client_ip(addr.ip(), req.headers())))
+    }
+
+    pub fn storage_key(&self, scope: &str) -> String {
+        match self {
+            Self::Token(id) => format!("{}:token:{}", scope, id),
+            Self::User(id) => format!("{}:user:{}", scope, id),
+            Self::Ip(ip) => format!("{}:ip:{}", scope, ip),
+        }
+    }
+}
+
+/// Лимит для API запроса: индивидуальный у токена или из конфигурации
+fn api_limit(config: &Config, req: &Request) -> RateLimit {
+    match req.extensions().get::<Principal>() {
+        Some(principal) => match principal.limits.requests_per_minute {
+            // Burst - запросы примерно за 10 секунд
+            Some(per_minute) => RateLimit::new(per_minute, per_minute.div_ceil(6)),
+            None => RateLimit::new(
+                config.rate_limit_token_per_minute,
+                config.rate_limit_token_burst,
+            ),
+        },
+        None => anonymous_limit(config),
+    }
+}
 
-    GovernorLayer::new(governor_conf)
+fn anonymous_limit(config: &Config) -> RateLimit {
+    RateLimit::new(
+        config.rate_limit_anonymous_per_minute,
+        config.rate_limit_anonymous_burst,
+    )
 }
 
-/// Строгий лимит для auth endpoints (10 req/min)
-pub fn secure_layer()
--> GovernorLayer<PeerIpKeyExtractor, NoOpMiddleware<QuantaInstant>, axum::body::Body> {
-    let governor_conf = Arc::new(
-        GovernorConfigBuilder::default()
-            .per_second(1)
-            .burst_size(10)
-            .finish()
-            .unwrap(),
+/// Rate limiting API по токену (анонимные запросы - по IP).
+/// Должен выполняться после `auth::layer` / `auth::optional_layer`.
+pub async fn api_layer(
+    State(st): State<AppState>,
+    req: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    let Some(key) = RateLimitKey::extract(&req, &st.config.trusted_proxies) else {
+        return Ok(next.run(req).await);
+    };
+
+    let limit = api_limit(&st.config, &req);
+    enforce(&st, key.storage_key("api"), limit, req, next).await
+}
+
+/// Rate limiting публичных страниц дашборда (по IP)
+pub async fn public_layer(
+    State(st): State<AppState>,
+    req: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    let Some(key) = RateLimitKey::client_ip(&req, &st.config.trusted_proxies) else {
+        return Ok(next.run(req).await);
+    };
+
+    let limit = anonymous_limit(&st.config);
+    enforce(&st, key.storage_key("web"), limit, req, next).await
+}
+
+/// Rate limiting страниц дашборда, требующих входа (по IP)
+pub async fn dashboard_layer(
+    State(st): State<AppState>,
+    req: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    let Some(key) = RateLimitKey::client_ip(&req, &st.config.trusted_proxies) else {
+        return Ok(next.run(req).await);
+    };
+
+    let limit = RateLimit::new(
+        st.config.rate_limit_dashboard_per_minute,
+        st.config.rate_limit_dashboard_burst,
     );
+    enforce(&st, key.storage_key("dashboard"), limit, req, next).await
+}
 
-    GovernorLayer::new(governor_conf)
+/// Лимит неудачных попыток аутентификации
+pub fn auth_failure_limit(config: &Config) -> RateLimit {
+    RateLimit::new(
+        config.rate_limit_auth_failures_per_minute,
+        config.rate_limit_auth_failures_burst,
+    )
 }
+
+/// Защита от перебора токенов: учитываются только ответы 401 с IP клиента,
+/// после исчерпания лимита запросы с этого IP отклоняются до аутентификации.
+/// Должен выполняться до `auth::layer` / `auth::optional_layer`.
+pub async fn auth_failure_layer(
+    State(st): State<AppState>,
+    req: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    let Some(key) = RateLimitKey::client_ip(&req, &st.config.trusted_proxies) else {
+        return Ok(next.run(req).await);
+    };
+
+    let key = key.storage_key("auth_failures");
+    let limit = auth_failure_limit(&st.config);
+
+    let decision = st.rate_limiter.peek(&key, limit).await;
+    if !decision.allowed {
+        return Err(auth_failures_exceeded(limit, decision));
+    }
+
+    let response = next.run(req).await;
+    if response.status() == StatusCode::UNAUTHORIZED {
+        metrics::counter!("auth_failures_total").increment(1);
+        st.rate_limiter.check(&key, limit).await;
+    }
+
+    Ok(response)
+}
+
+/// Ошибка 429 при исчерпании лимита неудачных попыток
+pub fn auth_failures_exceeded(limit: RateLimit, decision: RateLimitDecision) -> AppError {
+    metrics::counter!("rate_limit_rejected_total").increment(1);
+
+    AppError::too_many_requests(
+        "Too many failed authentication attempts",
+        json!({ "limit_per_minute": limit.per_minute }),
+        decision.retry_after.as_secs_f64().ceil() as u64,
+    )
+}
+
+async fn enforce(
+    st: &AppState,
+    key: String,
+    limit: RateLimit,
+    req: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    let decision = st.rate_limiter.check(&key, limit).await;
+
+    if !decision.allowed {
+        metrics::counter!("rate_limit_rejected_total").increment(1);
+
+        let mut response = AppError::too_many_requests(
+            "Too many requests",
+            json!({ "limit_per_minute": limit.per_minute }),
+            decision.retry_after.as_secs_f64().ceil() as u64,
+        )
+        .into_response();
+        set_limit_headers(response.headers_mut(), limit, 0);
+        return Ok(response);
+    }
+
+    let mut response = next.run(req).await;
+    set_limit_headers(response.headers_mut(), limit, decision.remaining);
+
+    Ok(response)
+}
+
+fn set_limit_headers(headers: &mut HeaderMap, limit: RateLimit, remaining: u32) {
+    headers.insert(X_RATELIMIT_LIMIT, limit.per_minute.into());
+    headers.insert(X_RATELIMIT_REMAINING, remaining.into());
+}
+
+const X_RATELIMIT_LIMIT: HeaderName = HeaderName::from_static("x-ratelimit-limit");
+const X_RATELIMIT_REMAINING: HeaderName = HeaderName::from_static("x-ratelimit-remaining");
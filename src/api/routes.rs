@@ -1,22 +1,75 @@
 use crate::api::handlers::{
-    domain_list_handler, health_handler, shorten_handler, stats_handler, stats_list_handler,
+    domain_alias_create_handler, domain_alias_delete_handler, domain_alias_verify_handler,
+    domain_create_handler, domain_delete_handler, domain_list_handler, domain_set_default_handler,
+    domain_update_handler, domain_verify_handler, health_handler, qr_handler, shorten_handler,
+    stats_handler, stats_list_handler, tag_list_handler,
 };
+use crate::api::middleware::auth;
+use crate::domain::entities::Scope;
 use crate::state::AppState;
 use axum::{
     Router,
-    routing::{get, post},
+    extract::Request,
+    middleware::{self, Next},
+    routing::{MethodRouter, delete, get, patch, post},
 };
 
 /// Защищённые маршруты (требуют аутентификацию)
 pub fn protected_routes() -> Router<AppState> {
     Router::new()
         .route("/health", get(health_handler))
-        .route("/domains", get(domain_list_handler))
-        .route("/stats", get(stats_list_handler))
-        .route("/stats/{code}", get(stats_handler))
+        .route(
+            "/domains",
+            get(domain_list_handler)
+                .merge(scoped(post(domain_create_handler), Scope::DomainsAdmin)),
+        )
+        .route(
+            "/domains/{domain}",
+            scoped(
+                patch(domain_update_handler).delete(domain_delete_handler),
+                Scope::DomainsAdmin,
+            ),
+        )
+        .route(
+            "/domains/{domain}/aliases",
+            scoped(post(domain_alias_create_handler), Scope::DomainsAdmin),
+        )
+        .route(
+            "/domains/{domain}/aliases/{host}",
+            scoped(delete(domain_alias_delete_handler), Scope::DomainsAdmin),
+        )
+        .route(
+            "/domains/{domain}/aliases/{host}/verify",
+            scoped(post(domain_alias_verify_handler), Scope::DomainsAdmin),
+        )
+        .route(
+            "/domains/{domain}/verify",
+            scoped(post(domain_verify_handler), Scope::DomainsAdmin),
+        )
+        .route(
+            "/domains/{domain}/default",
+            scoped(post(domain_set_default_handler), Scope::DomainsAdmin),
+        )
+        .route("/stats", scoped(get(stats_list_handler), Scope::StatsRead))
+        .route(
+            "/stats/{code}",
+            scoped(get(stats_handler), Scope::StatsRead),
+        )
+        .route("/tags", scoped(get(tag_list_handler), Scope::StatsRead))
+        .route(
+            "/links/{domain}/{code}/qr",
+            scoped(get(qr_handler), Scope::LinksRead),
+        )
 }
 
 /// Публичные маршруты API
 pub fn public_routes() -> Router<AppState> {
     Router::new().route("/shorten", post(shorten_handler))
 }
+
+/// Требовать право `scope` для маршрута
+fn scoped(route: MethodRouter<AppState>, scope: Scope) -> MethodRouter<AppState> {
+    route.route_layer(middleware::from_fn(move |req: Request, next: Next| {
+        auth::require_scope(scope, req, next)
+    }))
+}
@@ -1,42 +1,127 @@
-use sha2::{Digest, Sha256};
 use std::sync::Arc;
 
-use crate::domain::repositories::TokenRepository;
+use crate::domain::entities::{Principal, User};
+use crate::domain::repositories::{ApiToken, TokenRepository, UserRepository};
 use crate::error::AppError;
+use crate::utils::token_hash::{TokenHasher, lookup_id};
 use serde_json::json;
 
 /// Сервис для аутентификации
-pub struct AuthService<R: TokenRepository> {
+pub struct AuthService<R: TokenRepository, U: UserRepository> {
     repository: Arc<R>,
+    users: Arc<U>,
+    hasher: TokenHasher,
 }
 
-impl<R: TokenRepository> AuthService<R> {
-    pub fn new(repository: Arc<R>) -> Self {
-        Self { repository }
-    }
-
-    fn hash_token(&self, token: &str) -> String {
-        let mut h = Sha256::new();
-        h.update(token.as_bytes());
-        hex::encode(h.finalize())
+impl<R: TokenRepository, U: UserRepository> AuthService<R, U> {
+    pub fn new(repository: Arc<R>, users: Arc<U>, hasher: TokenHasher) -> Self {
+        Self {
+            repository,
+            users,
+            hasher,
+        }
     }
 
-    pub async fn authenticate(&self, token: &str) -> Result<(), AppError> {
-        let token_hash = self.hash_token(token);
+    /// Аутентификация по токену, возвращает найденный токен
+    pub async fn authenticate(&self, token: &str) -> Result<ApiToken, AppError> {
+        let token_hash = self.hasher.hash(token);
 
         // Проверяем валидность токена
-        let is_valid = self.repository.validate_token(&token_hash).await?;
-
-        if !is_valid {
-            return Err(AppError::unauthorized(
-                "Unauthorized",
-                json!({"reason": "Invalid or revoked token"}),
-            ));
-        }
+        let api_token = match self.repository.find_active_by_hash(&token_hash).await? {
+            Some(api_token) => api_token,
+            None => self
+                .authenticate_legacy(token, &token_hash)
+                .await?
+                .ok_or_else(|| {
+                    // В лог попадает только открытая часть токена
+                    tracing::debug!(lookup_id = ?lookup_id(token), "Token authentication failed");
+                    AppError::unauthorized(
+                        "Unauthorized",
+                        json!({"reason": "Invalid, revoked or expired token"}),
+                    )
+                })?,
+        };
 
         // Обновляем время последнего использования
         let _ = self.repository.update_last_used(&token_hash).await;
 
-        Ok(())
+        Ok(api_token)
+    }
+
+    /// Токен с хэшем старого формата: при успехе хэш заменяется на HMAC
+    async fn authenticate_legacy(
+        &self,
+        token: &str,
+        token_hash: &str,
+    ) -> Result<Option<ApiToken>, AppError> {
+        let legacy_hash = TokenHasher::legacy_hash(token);
+        let Some(mut api_token) = self
+            .repository
+            .find_active_by_legacy_hash(&legacy_hash)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        self.repository
+            .upgrade_hash(api_token.id, token_hash)
+            .await?;
+        tracing::info!(
+            token_id = api_token.id,
+            "Migrated token hash to HMAC-SHA256"
+        );
+
+        api_token.token_hash = token_hash.to_string();
+        api_token.legacy_hash = false;
+
+        Ok(Some(api_token))
+    }
+
+    /// Аутентификация по ID токена (для сессий дашборда): токен должен быть активен
+    pub async fn authenticate_by_id(&self, token_id: i64) -> Result<ApiToken, AppError> {
+        let api_token = self
+            .repository
+            .find_by_id(token_id)
+            .await?
+            .filter(ApiToken::is_active)
+            .ok_or_else(|| {
+                AppError::unauthorized(
+                    "Unauthorized",
+                    json!({"reason": "Invalid, revoked or expired token"}),
+                )
+            })?;
+
+        let _ = self
+            .repository
+            .update_last_used(&api_token.token_hash)
+            .await;
+
+        Ok(api_token)
+    }
+
+    /// Субъект запроса по токену: токен заблокированного пользователя не действует
+    pub async fn principal(&self, token: ApiToken) -> Result<Principal, AppError> {
+        let user = match token.user_id {
+            Some(user_id) => Some(self.active_user(user_id).await?),
+            None => None,
+        };
+
+        Ok(Principal::from_token(token, user.as_ref()))
+    }
+
+    /// Субъект сессии, открытой по логину и паролю
+    pub async fn user_principal(&self, user_id: i64) -> Result<Principal, AppError> {
+        let user = self.active_user(user_id).await?;
+        Ok(Principal::from_user(&user))
+    }
+
+    async fn active_user(&self, user_id: i64) -> Result<User, AppError> {
+        self.users
+            .find_by_id(user_id)
+            .await?
+            .filter(User::is_active)
+            .ok_or_else(|| {
+                AppError::unauthorized("Unauthorized", json!({"reason": "User is disabled"}))
+            })
     }
 }
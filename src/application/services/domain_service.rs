@@ -1,9 +1,46 @@
-use crate::domain::entities::{Domain, NewDomain, UpdateDomain};
+use crate::domain::entities::{
+    Domain, DomainAlias, NewDomain, UpdateDomain, VerificationChallenge, VerificationMethod,
+    WILDCARD_PREFIX, wildcard_candidates,
+};
 use crate::domain::repositories::DomainRepository;
 use crate::error::AppError;
+use crate::utils::hostname::{HostnameError, normalize_hostname, parse_domain_name};
+use rand_core::{OsRng, TryRngCore};
 use serde_json::json;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
+/// Итог приведения имени домена или алиаса к канонической форме
+#[derive(Debug, Clone)]
+pub enum NameOutcome {
+    /// Имя переименовано (при `apply = false` - будет переименовано)
+    Renamed(String),
+    /// Каноническое имя уже занято другим доменом или алиасом
+    Conflict(String),
+    /// Имя не разбирается как IDNA
+    Invalid(String),
+}
+
+/// Имя домена или алиаса, хранящееся не в канонической форме
+#[derive(Debug, Clone)]
+pub struct NameNormalization {
+    /// Алиас или wildcard (иначе домен)
+    pub is_alias: bool,
+    pub current: String,
+    pub outcome: NameOutcome,
+}
+
+/// Домен с количеством ссылок и алиасами (для списков)
+#[derive(Debug, Clone)]
+pub struct DomainOverview {
+    pub domain: Domain,
+    pub links_count: i64,
+    pub aliases: Vec<DomainAlias>,
+}
+
+/// Длина токена проверки владения доменом (байт до hex)
+const VERIFICATION_TOKEN_BYTES: usize = 16;
+
 pub struct DomainService<R: DomainRepository> {
     repository: Arc<R>,
 }
@@ -13,15 +50,24 @@ impl<R: DomainRepository> DomainService<R> {
         Self { repository }
     }
 
-    /// Создать новый домен
+    /// Создать новый домен. Неподтверждённый (`verified = false`) домен создаётся
+    /// выключенным и получает токен для проверки владения.
     pub async fn create_domain(
         &self,
         domain: String,
         is_default: bool,
         description: Option<String>,
+        verified: bool,
     ) -> Result<Domain, AppError> {
         // Валидация домена
-        self.validate_domain_name(&domain)?;
+        let domain = self.parse_domain_name(&domain)?;
+
+        if is_default && !verified {
+            return Err(AppError::bad_request(
+                "Cannot make unverified domain default",
+                json!({"domain": domain, "hint": "Verify the domain first"}),
+            ));
+        }
 
         // Проверка существования
         if self.repository.find_by_name(&domain).await?.is_some() {
@@ -31,10 +77,18 @@ impl<R: DomainRepository> DomainService<R> {
             ));
         }
 
+        if self.repository.find_alias(&domain).await?.is_some() {
+            return Err(AppError::conflict(
+                "Domain is already used as an alias",
+                json!({"domain": domain}),
+            ));
+        }
+
         let new_domain = NewDomain {
             domain,
             is_default,
             description,
+            verification_token: (!verified).then(generate_verification_token),
         };
 
         let created = self.repository.create(new_domain).await?;
@@ -52,14 +106,167 @@ impl<R: DomainRepository> DomainService<R> {
         self.repository.list(only_active).await
     }
 
-    /// Получить домен по имени
+    /// Список доменов с количеством ссылок и алиасами: три запроса на весь список
+    pub async fn list_domain_overviews(
+        &self,
+        only_active: bool,
+    ) -> Result<Vec<DomainOverview>, AppError> {
+        let domains = self.repository.list(only_active).await?;
+        let links_counts = self.repository.count_links_by_domain().await?;
+
+        let mut aliases: HashMap<i64, Vec<DomainAlias>> = HashMap::new();
+        for alias in self.repository.list_all_aliases().await? {
+            aliases.entry(alias.domain_id).or_default().push(alias);
+        }
+
+        Ok(domains
+            .into_iter()
+            .map(|domain| DomainOverview {
+                links_count: links_counts.get(&domain.id).copied().unwrap_or(0),
+                aliases: aliases.remove(&domain.id).unwrap_or_default(),
+                domain,
+            })
+            .collect())
+    }
+
+    /// Получить домен по имени (в любом регистре, Unicode или punycode)
     pub async fn get_domain(&self, domain: &str) -> Result<Domain, AppError> {
+        let name = normalize_hostname(domain).unwrap_or_else(|_| domain.to_string());
         self.repository
-            .find_by_name(domain)
+            .find_by_name(&name)
             .await?
             .ok_or_else(|| AppError::not_found("Domain not found", json!({"domain": domain})))
     }
 
+    /// Домен для хоста запроса: точное имя, алиас или wildcard
+    pub async fn resolve_host(&self, host: &str) -> Result<Option<Domain>, AppError> {
+        let Ok(host) = normalize_hostname(host) else {
+            return Ok(None);
+        };
+        self.repository
+            .find_by_host(&host, &wildcard_candidates(&host))
+            .await
+    }
+
+    /// Алиасы и wildcard шаблоны домена
+    pub async fn list_aliases(&self, domain_id: i64) -> Result<Vec<DomainAlias>, AppError> {
+        self.repository.list_aliases(domain_id).await
+    }
+
+    /// Добавить алиас (`www.s.example.com`) или wildcard шаблон (`*.go.example.com`).
+    /// Неподтверждённый (`verified = false`) алиас не обслуживается, пока не пройдена
+    /// проверка владения им (для wildcard - суффиксом `go.example.com`).
+    pub async fn add_alias(
+        &self,
+        domain: &Domain,
+        host: String,
+        verified: bool,
+    ) -> Result<DomainAlias, AppError> {
+        let host = host.trim();
+        let host = match host.strip_prefix(WILDCARD_PREFIX) {
+            Some(suffix) => format!("{}{}", WILDCARD_PREFIX, self.parse_domain_name(suffix)?),
+            None => self.parse_domain_name(host)?,
+        };
+
+        // Точное имя всегда важнее алиаса - такой алиас никогда бы не сработал
+        if self.repository.find_by_name(&host).await?.is_some() {
+            return Err(AppError::conflict(
+                "A domain with this name already exists",
+                json!({"host": host}),
+            ));
+        }
+
+        if let Some(existing) = self.repository.find_alias(&host).await? {
+            return Err(AppError::conflict(
+                "Alias already exists",
+                json!({"host": host, "domain_id": existing.domain_id}),
+            ));
+        }
+
+        let token = (!verified).then(generate_verification_token);
+        self.repository
+            .add_alias(domain.id, &host, token.as_deref())
+            .await
+    }
+
+    /// Алиас домена по имени
+    pub async fn get_alias(&self, domain: &Domain, host: &str) -> Result<DomainAlias, AppError> {
+        let host = normalize_alias(host);
+        self.repository
+            .find_alias(&host)
+            .await?
+            .filter(|alias| alias.domain_id == domain.id)
+            .ok_or_else(|| {
+                AppError::not_found(
+                    "Alias not found",
+                    json!({"domain": domain.domain, "host": host}),
+                )
+            })
+    }
+
+    /// Удалить алиас домена
+    pub async fn remove_alias(&self, domain: &Domain, host: &str) -> Result<(), AppError> {
+        let alias = self.get_alias(domain, host).await?;
+        self.repository.remove_alias(alias.id).await
+    }
+
+    /// Сохранить результат проверки владения алиасом
+    pub async fn complete_alias_verification(
+        &self,
+        alias: &DomainAlias,
+        outcome: Result<VerificationMethod, String>,
+    ) -> Result<DomainAlias, AppError> {
+        match outcome {
+            Ok(method) => self.repository.mark_alias_verified(alias.id, method).await,
+            Err(reason) => {
+                reject_verification(
+                    self.repository
+                        .record_alias_verification_failure(alias.id, &reason),
+                    "Alias verification failed",
+                    ("host", &alias.host),
+                    &reason,
+                    alias.verification_challenge(),
+                )
+                .await
+            }
+        }
+    }
+
+    /// Подтвердить алиас вручную (администратор через CLI)
+    pub async fn mark_alias_verified_manually(
+        &self,
+        alias: &DomainAlias,
+    ) -> Result<DomainAlias, AppError> {
+        self.repository
+            .mark_alias_verified(alias.id, VerificationMethod::Manual)
+            .await
+    }
+
+    /// Ожидающие проверки challenge для хоста запроса: домена, алиаса
+    /// и wildcard шаблона, суффиксом которого является хост
+    pub async fn pending_challenges(
+        &self,
+        host: &str,
+    ) -> Result<Vec<VerificationChallenge>, AppError> {
+        let Ok(host) = normalize_hostname(host) else {
+            return Ok(Vec::new());
+        };
+
+        let mut challenges = Vec::new();
+        if let Some(domain) = self.repository.find_by_name(&host).await? {
+            challenges.extend(domain.verification_challenge());
+        }
+
+        let wildcard = format!("{}{}", WILDCARD_PREFIX, host);
+        for alias_host in [host, wildcard] {
+            if let Some(alias) = self.repository.find_alias(&alias_host).await? {
+                challenges.extend(alias.verification_challenge());
+            }
+        }
+
+        Ok(challenges)
+    }
+
     /// Получить default домен
     pub async fn get_default_domain(&self) -> Result<Domain, AppError> {
         self.repository.get_default().await
@@ -67,6 +274,16 @@ impl<R: DomainRepository> DomainService<R> {
 
     /// Установить default домен
     pub async fn set_default(&self, domain_id: i64) -> Result<(), AppError> {
+        let domain = self.get_domain_by_id(domain_id).await?;
+
+        // Ссылки без явного домена не должны создаваться на выключенном домене
+        if !domain.is_active {
+            return Err(AppError::bad_request(
+                "Cannot make inactive domain default",
+                json!({"domain": domain.domain, "hint": "Activate the domain first"}),
+            ));
+        }
+
         self.repository.set_default(domain_id).await
     }
 
@@ -76,16 +293,71 @@ impl<R: DomainRepository> DomainService<R> {
         domain_id: i64,
         update: UpdateDomain,
     ) -> Result<Domain, AppError> {
+        let domain = self.get_domain_by_id(domain_id).await?;
+
+        if domain.is_default && update.is_active == Some(false) {
+            return Err(AppError::bad_request(
+                "Cannot deactivate default domain",
+                json!({"domain": domain.domain, "hint": "Set another domain as default first"}),
+            ));
+        }
+
+        let code_policy = update.apply_code_policy(&domain.code_policy);
+        if let Err(reason) = code_policy.validate() {
+            return Err(AppError::bad_request(
+                "Invalid code policy",
+                json!({"domain": domain.domain, "reason": reason}),
+            ));
+        }
+
+        if update.is_active == Some(true) && !domain.verification.is_verified() {
+            return Err(AppError::bad_request(
+                "Domain is not verified",
+                json!({"domain": domain.domain, "hint": "Verify domain ownership first"}),
+            ));
+        }
+
         self.repository.update(domain_id, update).await
     }
 
+    /// Сохранить результат проверки владения доменом: при успехе домен
+    /// подтверждается и включается, иначе запоминается причина неудачи
+    pub async fn complete_verification(
+        &self,
+        domain: &Domain,
+        outcome: Result<VerificationMethod, String>,
+    ) -> Result<Domain, AppError> {
+        match outcome {
+            Ok(method) => self.repository.mark_verified(domain.id, method).await,
+            Err(reason) => {
+                reject_verification(
+                    self.repository
+                        .record_verification_failure(domain.id, &reason),
+                    "Domain verification failed",
+                    ("domain", &domain.domain),
+                    &reason,
+                    domain.verification_challenge(),
+                )
+                .await
+            }
+        }
+    }
+
+    /// Подтвердить домен вручную (администратор через CLI)
+    pub async fn mark_verified_manually(&self, domain_id: i64) -> Result<Domain, AppError> {
+        self.repository
+            .mark_verified(domain_id, VerificationMethod::Manual)
+            .await
+    }
+
+    /// Количество ссылок на домене
+    pub async fn count_links(&self, domain_id: i64) -> Result<i64, AppError> {
+        self.repository.count_links(domain_id).await
+    }
+
     /// Удалить домен
     pub async fn delete_domain(&self, domain_id: i64) -> Result<(), AppError> {
-        let domain = self
-            .repository
-            .find_by_id(domain_id)
-            .await?
-            .ok_or_else(|| AppError::not_found("Domain not found", json!({"id": domain_id})))?;
+        let domain = self.get_domain_by_id(domain_id).await?;
 
         // Нельзя удалить default домен
         if domain.is_default {
@@ -107,34 +379,138 @@ impl<R: DomainRepository> DomainService<R> {
         self.repository.delete(domain_id).await
     }
 
-    /// Валидация имени домена
-    fn validate_domain_name(&self, domain: &str) -> Result<(), AppError> {
-        if domain.is_empty() || domain.len() > 255 {
-            return Err(AppError::bad_request(
-                "Invalid domain name length",
-                json!({"min": 1, "max": 255}),
-            ));
+    /// Привести имена доменов и алиасов, добавленных в обход сервиса, к
+    /// канонической форме (`normalize_hostname`). Имя не меняется, если каноническое
+    /// уже занято; при `apply = false` только возвращает, что было бы сделано.
+    pub async fn normalize_names(&self, apply: bool) -> Result<Vec<NameNormalization>, AppError> {
+        let domains = self.repository.list(false).await?;
+        let mut aliases = Vec::new();
+        for domain in &domains {
+            aliases.extend(self.repository.list_aliases(domain.id).await?);
         }
 
-        // Простая проверка формата
-        if !domain.contains('.') {
-            return Err(AppError::bad_request(
-                "Invalid domain format",
-                json!({"hint": "Domain must contain at least one dot"}),
-            ));
-        }
+        let mut taken: HashSet<String> = domains
+            .iter()
+            .map(|domain| domain.domain.clone())
+            .chain(aliases.iter().map(|alias| alias.host.clone()))
+            .collect();
 
-        // Проверка на недопустимые символы
-        if !domain
-            .chars()
-            .all(|c| c.is_alphanumeric() || c == '.' || c == '-')
-        {
-            return Err(AppError::bad_request(
-                "Invalid characters in domain name",
-                json!({"allowed": "a-z, 0-9, dots, hyphens"}),
-            ));
+        // Домены раньше алиасов: при совпадении имя остаётся за доменом
+        let names = domains
+            .iter()
+            .map(|domain| (false, domain.id, domain.domain.as_str()))
+            .chain(
+                aliases
+                    .iter()
+                    .map(|alias| (true, alias.id, alias.host.as_str())),
+            );
+
+        let mut report = Vec::new();
+        for (is_alias, id, current) in names {
+            let canonical = if is_alias {
+                canonical_alias(current)
+            } else {
+                normalize_hostname(current)
+            };
+
+            let outcome = match canonical {
+                Ok(canonical) if canonical == current => continue,
+                Ok(canonical) if taken.contains(&canonical) => NameOutcome::Conflict(canonical),
+                Ok(canonical) => {
+                    if apply {
+                        match is_alias {
+                            true => self.repository.rename_alias(id, &canonical).await?,
+                            false => self.repository.rename(id, &canonical).await?,
+                        }
+                    }
+                    taken.remove(current);
+                    taken.insert(canonical.clone());
+                    NameOutcome::Renamed(canonical)
+                }
+                Err(e) => NameOutcome::Invalid(e.to_string()),
+            };
+
+            report.push(NameNormalization {
+                is_alias,
+                current: current.to_string(),
+                outcome,
+            });
         }
 
-        Ok(())
+        Ok(report)
     }
+
+    async fn get_domain_by_id(&self, domain_id: i64) -> Result<Domain, AppError> {
+        self.repository
+            .find_by_id(domain_id)
+            .await?
+            .ok_or_else(|| AppError::not_found("Domain not found", json!({"id": domain_id})))
+    }
+
+    /// Каноническое имя домена (IDNA/punycode) с проверкой правил DNS
+    fn parse_domain_name(&self, domain: &str) -> Result<String, AppError> {
+        parse_domain_name(domain).map_err(|e| {
+            AppError::bad_request(
+                "Invalid domain name",
+                json!({"domain": domain, "reason": e.to_string()}),
+            )
+        })
+    }
+}
+
+/// Запомнить причину неудачной проверки владения и вернуть ошибку
+/// с challenge, который нужно разместить для повторной попытки
+async fn reject_verification<T>(
+    record_failure: impl Future<Output = Result<(), AppError>>,
+    message: &str,
+    (field, host): (&str, &str),
+    reason: &str,
+    challenge: Option<VerificationChallenge>,
+) -> Result<T, AppError> {
+    record_failure.await?;
+
+    let mut details = json!({
+        "reason": reason,
+        "dns_record": challenge.as_ref().map(|c| json!({
+            "type": "TXT",
+            "name": c.dns_name,
+            "value": c.dns_value,
+        })),
+        "http_url": challenge.as_ref().map(|c| &c.http_url),
+    });
+    details[field] = json!(host);
+
+    Err(AppError::bad_request(message, details))
+}
+
+fn generate_verification_token() -> String {
+    let mut buffer = [0u8; VERIFICATION_TOKEN_BYTES];
+    OsRng
+        .try_fill_bytes(&mut buffer)
+        .expect("Failed to generate random bytes");
+    hex::encode(buffer)
+}
+
+/// Каноническое имя алиаса: wildcard префикс сохраняется, суффикс нормализуется
+fn canonical_alias(host: &str) -> Result<String, HostnameError> {
+    match host.strip_prefix(WILDCARD_PREFIX) {
+        Some(suffix) => Ok(format!(
+            "{}{}",
+            WILDCARD_PREFIX,
+            normalize_hostname(suffix)?
+        )),
+        None => normalize_hostname(host),
+    }
+}
+
+/// Имя алиаса в том виде, в котором оно хранится (wildcard префикс сохраняется)
+fn normalize_alias(host: &str) -> String {
+    let host = host.trim();
+    let (prefix, name) = match host.strip_prefix(WILDCARD_PREFIX) {
+        Some(suffix) => (WILDCARD_PREFIX, suffix),
+        None => ("", host),
+    };
+
+    let name = normalize_hostname(name).unwrap_or_else(|_| name.to_lowercase());
+    format!("{}{}", prefix, name)
 }
@@ -1,23 +1,58 @@
+use chrono::{NaiveDate, Utc};
 use std::sync::Arc;
 
-use crate::domain::entities::{Link, NewLink};
-use crate::domain::repositories::{DomainRepository, LinkRepository};
+use crate::application::services::ReservedCodeService;
+use crate::application::services::tag_service::normalize_tags;
+use crate::domain::code_generator::CodeGenerators;
+use crate::domain::entities::{Domain, Link, LinkMetadata, NewLink};
+use crate::domain::repositories::{DomainRepository, LinkRepository, ReservedCodeRepository};
 use crate::error::AppError;
-use crate::utils::code_generator::{generate_code, validate_custom_code};
+use crate::utils::code_generator::validate_custom_code;
 use crate::utils::url_normalizer::normalize_url;
 use serde_json::json;
 
+/// Результат создания ссылки
+#[derive(Debug, Clone)]
+pub struct ShortenedLink {
+    pub link: Link,
+    /// false - возвращена уже существующая ссылка без изменений
+    pub created: bool,
+    /// Нормализованные теги запроса
+    pub tags: Vec<String>,
+}
+
+/// Суточная квота токена после резервирования
+#[derive(Debug, Clone, Copy)]
+pub struct DailyQuotaUsage {
+    pub token_id: i64,
+    /// Сутки (UTC), на которые сделан резерв
+    pub day: NaiveDate,
+    /// Израсходовано с учётом резерва
+    pub used: i64,
+    /// false - квоты не хватило, резерв не сделан
+    pub reserved: bool,
+}
+
 /// Сервис для работы со ссылками
-pub struct LinkService<L: LinkRepository, D: DomainRepository> {
+pub struct LinkService<L: LinkRepository, D: DomainRepository, R: ReservedCodeRepository> {
     link_repository: Arc<L>,
     domain_repository: Arc<D>,
+    reserved_codes: Arc<ReservedCodeService<R>>,
+    code_generators: CodeGenerators,
 }
 
-impl<L: LinkRepository, D: DomainRepository> LinkService<L, D> {
-    pub fn new(link_repository: Arc<L>, domain_repository: Arc<D>) -> Self {
+impl<L: LinkRepository, D: DomainRepository, R: ReservedCodeRepository> LinkService<L, D, R> {
+    pub fn new(
+        link_repository: Arc<L>,
+        domain_repository: Arc<D>,
+        reserved_codes: Arc<ReservedCodeService<R>>,
+        code_generators: CodeGenerators,
+    ) -> Self {
         Self {
             link_repository,
             domain_repository,
+            reserved_codes,
+            code_generators,
         }
     }
 
@@ -26,66 +61,111 @@ impl<L: LinkRepository, D: DomainRepository> LinkService<L, D> {
         &self,
         long_url: String,
         custom_code: Option<String>,
-    ) -> Result<Link, AppError> {
+        metadata: LinkMetadata,
+        tags: Vec<String>,
+    ) -> Result<ShortenedLink, AppError> {
         // Получаем default домен
         let default_domain = self.domain_repository.get_default().await?;
-        self.create_short_link_for_domain(long_url, default_domain.id, custom_code)
+        self.create_short_link_for_domain(long_url, &default_domain, custom_code, metadata, tags)
             .await
     }
 
-    /// Создать короткую ссылку для конкретного домена
+    /// Создать короткую ссылку для конкретного домена (коды - по политике домена).
+    /// Теги новой ссылки сохраняются в той же транзакции; к существующей ссылке
+    /// их привязывает вызывающий
     pub async fn create_short_link_for_domain(
         &self,
         long_url: String,
-        domain_id: i64,
+        domain: &Domain,
         custom_code: Option<String>,
-    ) -> Result<Link, AppError> {
+        metadata: LinkMetadata,
+        tags: Vec<String>,
+    ) -> Result<ShortenedLink, AppError> {
+        let domain_id = domain.id;
+        let tags = normalize_tags(tags);
+
         // 1. Валидация и нормализация URL
         let normalized_url = normalize_url(&long_url).map_err(|e| {
             AppError::bad_request("Invalid URL format", json!({ "reason": e.to_string() }))
         })?;
 
-        // 2. Проверяем, существует ли уже ссылка для этого домена
-        if let Some(existing_link) = self
-            .link_repository
-            .find_by_long_url(&normalized_url, domain_id)
-            .await?
+        // 2. Проверяем, создавал ли уже этот владелец ссылку на URL в этом домене
+        // с теми же title/notes. Чужие ссылки не возвращаем: к ним приписались бы
+        // теги вызывающего
+        if custom_code.is_none()
+            && let Some(existing_link) = self
+                .link_repository
+                .find_by_long_url(&normalized_url, domain_id, &metadata)
+                .await?
         {
-            return Ok(existing_link);
+            return Ok(ShortenedLink {
+                link: existing_link,
+                created: false,
+                tags,
+            });
         }
 
-        // 3. Генерируем или валидируем кастомный код
-        let code = if let Some(custom) = custom_code {
-            // Валидация кастомного кода
-            validate_custom_code(&custom)?;
+        // 3. Кастомный код: проверяем и создаём ссылку с ним
+        let Some(custom) = custom_code else {
+            // Сгенерированный код: занятость проверит уникальный индекс при вставке
+            let link = self
+                .create_with_generated_code(normalized_url, domain, metadata, tags.clone())
+                .await?;
 
-            // Проверка уникальности
-            if self
-                .link_repository
-                .find_by_code(&custom, domain_id)
-                .await?
-                .is_some()
-            {
-                return Err(AppError::conflict(
-                    "Custom code already exists for this domain",
-                    json!({ "code": custom, "domain_id": domain_id }),
-                ));
+            return Ok(ShortenedLink {
+                link,
+                created: true,
+                tags,
+            });
+        };
+
+        validate_custom_code(&custom, &domain.code_policy)?;
+
+        if self.reserved_codes.is_reserved(&custom, domain_id).await? {
+            return Err(AppError::bad_request(
+                "This code is reserved",
+                json!({ "code": custom }),
+            ));
+        }
+
+        // Проверка уникальности; повтор того же запроса возвращает ту же ссылку
+        if let Some(existing_link) = self
+            .link_repository
+            .find_by_code(&custom, domain_id)
+            .await?
+        {
+            if is_same_link(&existing_link, &normalized_url, &metadata) {
+                return Ok(ShortenedLink {
+                    link: existing_link,
+                    created: false,
+                    tags,
+                });
             }
 
-            custom
-        } else {
-            // Генерируем случайный код
-            self.generate_unique_code(domain_id).await?
-        };
+            return Err(custom_code_conflict(&custom, domain_id));
+        }
 
         // 4. Создаём новую ссылку
         let new_link = NewLink {
-            code,
+            code: custom.clone(),
             long_url: normalized_url,
             domain_id,
+            metadata,
+            tags: tags.clone(),
         };
 
-        self.link_repository.create(new_link).await
+        // Код могли занять между проверкой и вставкой
+        let link = self
+            .link_repository
+            .create(new_link)
+            .await?
+            .ok_or_else(|| custom_code_conflict(&custom, domain_id))?;
+
+        Ok(ShortenedLink {
+            link,
+            created: true,
+            tags,
+        })
     }
 
     /// Получить полную информацию о ссылке по коду и домену
@@ -101,32 +181,111 @@ impl<L: LinkRepository, D: DomainRepository> LinkService<L, D> {
             })
     }
 
+    /// Зарезервировать `count` ссылок в суточной (UTC) квоте токена.
+    /// При превышении квоты ничего не резервируется (`reserved = false`)
+    pub async fn reserve_daily_quota(
+        &self,
+        token_id: i64,
+        count: i64,
+        limit: i64,
+    ) -> Result<DailyQuotaUsage, AppError> {
+        let day = Utc::now().date_naive();
+
+        match self
+            .link_repository
+            .reserve_daily_quota(token_id, day, count, limit)
+            .await?
+        {
+            Some(used) => Ok(DailyQuotaUsage {
+                token_id,
+                day,
+                used,
+                reserved: true,
+            }),
+            None => Ok(DailyQuotaUsage {
+                token_id,
+                day,
+                used: self.link_repository.daily_quota_used(token_id, day).await?,
+                reserved: false,
+            }),
+        }
+    }
+
+    /// Вернуть в квоту ссылки, которые не удалось создать
+    pub async fn release_daily_quota(
+        &self,
+        usage: DailyQuotaUsage,
+        count: i64,
+    ) -> Result<DailyQuotaUsage, AppError> {
+        let used = self
+            .link_repository
+            .release_daily_quota(usage.token_id, usage.day, count)
+            .await?;
+
+        Ok(DailyQuotaUsage { used, ..usage })
+    }
+
     /// Получить короткий URL (domain + code)
     pub fn get_short_url(&self, domain: &str, code: &str) -> String {
         format!("https://{}/{}", domain.trim_end_matches('/'), code)
     }
 
-    /// Генерация уникального кода в рамках домена
-    async fn generate_unique_code(&self, domain_id: i64) -> Result<String, AppError> {
+    /// Создание ссылки со сгенерированным кодом стратегии домена. Занятый код
+    /// не проверяется заранее: вставка с ним ничего не создаёт, и берётся следующий
+    async fn create_with_generated_code(
+        &self,
+        long_url: String,
+        domain: &Domain,
+        metadata: LinkMetadata,
+        tags: Vec<String>,
+    ) -> Result<Link, AppError> {
         const MAX_ATTEMPTS: usize = 10;
 
+        let policy = &domain.code_policy;
+        let generator = self.code_generators.for_policy(policy);
+
         for _ in 0..MAX_ATTEMPTS {
-            let code = generate_code();
+            let code = generator.generate(domain.id, policy).await?;
 
-            // Проверяем, не занят ли код в рамках этого домена
-            if self
-                .link_repository
-                .find_by_code(&code, domain_id)
-                .await?
-                .is_none()
-            {
-                return Ok(code);
+            if self.reserved_codes.is_reserved(&code, domain.id).await? {
+                continue;
+            }
+
+            let new_link = NewLink {
+                code,
+                long_url: long_url.clone(),
+                domain_id: domain.id,
+                metadata: metadata.clone(),
+                tags: tags.clone(),
+            };
+
+            if let Some(link) = self.link_repository.create(new_link).await? {
+                return Ok(link);
             }
         }
 
         Err(AppError::internal(
             "Failed to generate unique code",
-            json!({ "reason": "Too many collisions" }),
+            json!({
+                "reason": "Too many collisions",
+                "strategy": self.code_generators.strategy(policy).as_str()
+            }),
         ))
     }
 }
+
+fn custom_code_conflict(code: &str, domain_id: i64) -> AppError {
+    AppError::conflict(
+        "Custom code already exists for this domain",
+        json!({ "code": code, "domain_id": domain_id }),
+    )
+}
+
+/// Ссылка создана тем же владельцем на тот же URL с теми же title/notes
+fn is_same_link(link: &Link, long_url: &str, metadata: &LinkMetadata) -> bool {
+    link.long_url == long_url
+        && link.metadata.created_by == metadata.created_by
+        && link.metadata.owner_id == metadata.owner_id
+        && link.metadata.title == metadata.title
+        && link.metadata.notes == metadata.notes
+}
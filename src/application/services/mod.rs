@@ -1,9 +1,17 @@
 pub mod auth_service;
 pub mod domain_service;
 pub mod link_service;
+pub mod reserved_code_service;
+pub mod session_service;
 pub mod stats_service;
+pub mod tag_service;
+pub mod user_service;
 
 pub use auth_service::AuthService;
-pub use domain_service::DomainService;
-pub use link_service::LinkService;
+pub use domain_service::{DomainOverview, DomainService, NameNormalization, NameOutcome};
+pub use link_service::{DailyQuotaUsage, LinkService, ShortenedLink};
+pub use reserved_code_service::ReservedCodeService;
+pub use session_service::SessionService;
 pub use stats_service::StatsService;
+pub use tag_service::TagService;
+pub use user_service::UserService;
@@ -0,0 +1,169 @@
+use serde_json::json;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::domain::entities::{Domain, ReservedCode};
+use crate::domain::repositories::ReservedCodeRepository;
+use crate::error::AppError;
+use crate::utils::code_generator::ReservedCodes;
+
+/// Максимальная длина зарезервированного кода
+const MAX_RESERVED_CODE_LENGTH: usize = 64;
+
+/// Коды из БД: код -> домены, на которых он зарезервирован (None - на всех)
+type AddedCodes = HashMap<String, HashSet<Option<i64>>>;
+
+/// Сервис зарезервированных кодов: встроенные (маршруты и RESERVED_CODES)
+/// и добавленные через admin CLI. Добавленные коды кэшируются в памяти на
+/// `cache_ttl`, чтобы генерация кода не ходила в БД на каждую попытку.
+pub struct ReservedCodeService<R: ReservedCodeRepository> {
+    repository: Arc<R>,
+    builtin: ReservedCodes,
+    cache_ttl: Duration,
+    cached: Mutex<Option<(Arc<AddedCodes>, Instant)>>,
+}
+
+impl<R: ReservedCodeRepository> ReservedCodeService<R> {
+    /// `cache_ttl` = 0 - проверять каждый код запросом в БД
+    pub fn new(repository: Arc<R>, builtin: ReservedCodes, cache_ttl: Duration) -> Self {
+        Self {
+            repository,
+            builtin,
+            cache_ttl,
+            cached: Mutex::new(None),
+        }
+    }
+
+    /// Недоступен ли код для ссылок на домене
+    pub async fn is_reserved(&self, code: &str, domain_id: i64) -> Result<bool, AppError> {
+        if self.builtin.contains(code) {
+            return Ok(true);
+        }
+
+        if self.cache_ttl.is_zero() {
+            return self.repository.is_reserved(code, domain_id).await;
+        }
+
+        let added = self.added_codes().await?;
+        Ok(added
+            .get(&code.to_lowercase())
+            .is_some_and(|domains| domains.contains(&None) || domains.contains(&Some(domain_id))))
+    }
+
+    /// Сбросить кэш (после изменений здесь, в admin CLI или на другом инстансе)
+    pub fn invalidate(&self) {
+        *self.cached.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+
+    async fn added_codes(&self) -> Result<Arc<AddedCodes>, AppError> {
+        if let Some((codes, expires_at)) = self
+            .cached
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .as_ref()
+            && *expires_at > Instant::now()
+        {
+            return Ok(codes.clone());
+        }
+
+        let mut codes = AddedCodes::new();
+        for (code, domain_id) in self.repository.list_codes().await? {
+            codes.entry(code).or_default().insert(domain_id);
+        }
+
+        let codes = Arc::new(codes);
+        *self.cached.lock().unwrap_or_else(|e| e.into_inner()) =
+            Some((codes.clone(), Instant::now() + self.cache_ttl));
+
+        Ok(codes)
+    }
+
+    pub fn builtin(&self) -> &ReservedCodes {
+        &self.builtin
+    }
+
+    /// Коды, зарезервированные через admin CLI
+    pub async fn list(&self) -> Result<Vec<ReservedCode>, AppError> {
+        self.repository.list().await
+    }
+
+    /// Зарезервировать код на всех доменах или на одном. Существующие ссылки
+    /// с этим кодом продолжают работать.
+    pub async fn reserve(
+        &self,
+        code: &str,
+        domain: Option<&Domain>,
+    ) -> Result<ReservedCode, AppError> {
+        let code = Self::normalize_code(code)?;
+
+        if self.builtin.contains(&code) {
+            return Err(AppError::conflict(
+                "Code is already reserved",
+                json!({ "code": code, "reason": "Built-in reserved code" }),
+            ));
+        }
+
+        let reserved = self
+            .repository
+            .add(&code, domain.map(|domain| domain.id))
+            .await?;
+        self.invalidate();
+
+        Ok(reserved)
+    }
+
+    /// Снять резерв с кода
+    pub async fn release(&self, code: &str, domain: Option<&Domain>) -> Result<(), AppError> {
+        let code = Self::normalize_code(code)?;
+
+        if !self
+            .repository
+            .remove(&code, domain.map(|domain| domain.id))
+            .await?
+        {
+            let reason = if self.builtin.contains(&code) {
+                "Built-in reserved codes cannot be released"
+            } else {
+                "Code is not reserved"
+            };
+            return Err(AppError::not_found(
+                "Reserved code not found",
+                json!({
+                    "code": code,
+                    "domain": domain.map(|domain| domain.domain.as_str()),
+                    "reason": reason
+                }),
+            ));
+        }
+
+        self.invalidate();
+        Ok(())
+    }
+
+    /// Нижний регистр, один сегмент пути без пробелов
+    fn normalize_code(code: &str) -> Result<String, AppError> {
+        let code = code.trim().to_lowercase();
+
+        let valid = !code.is_empty()
+            && code.len() <= MAX_RESERVED_CODE_LENGTH
+            && code
+                .chars()
+                .all(|c| c.is_ascii_graphic() && !matches!(c, '/' | '?' | '#' | '%'));
+
+        if !valid {
+            return Err(AppError::bad_request(
+                "Invalid reserved code",
+                json!({
+                    "code": code,
+                    "reason": format!(
+                        "Must be 1-{} printable ASCII characters without '/', '?', '#', '%'",
+                        MAX_RESERVED_CODE_LENGTH
+                    )
+                }),
+            ));
+        }
+
+        Ok(code)
+    }
+}
@@ -0,0 +1,189 @@
+use base64::Engine as _;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use chrono::{DateTime, Duration, Utc};
+use hmac::{Hmac, Mac};
+use rand_core::{OsRng, TryRngCore};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+
+use crate::domain::entities::User;
+use crate::domain::repositories::{
+    ApiToken, DashboardSession, NewDashboardSession, SessionRepository,
+};
+use crate::error::AppError;
+use serde_json::json;
+
+/// Имя cookie сессии дашборда
+pub const SESSION_COOKIE: &str = "session";
+
+const SESSION_ID_BYTES: usize = 32;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Выданная сессия: значение cookie показывается клиенту один раз
+#[derive(Debug, Clone)]
+pub struct IssuedSession {
+    pub cookie_value: String,
+    pub session: DashboardSession,
+}
+
+/// Сервис сессий дашборда.
+///
+/// Cookie имеет вид `<id>.<подпись>`: подпись HMAC-SHA256 отсекает подделанные
+/// значения без запроса к БД, в БД хранится только SHA-256 от идентификатора.
+pub struct SessionService<R: SessionRepository> {
+    repository: Arc<R>,
+    secret: Vec<u8>,
+    ttl: Duration,
+}
+
+impl<R: SessionRepository> SessionService<R> {
+    /// Ключ подписи - `secret` (SESSION_SECRET), а без него выводится из `pepper`
+    /// (TOKEN_PEPPER) с отдельной меткой: сессии переживают перезапуск и работают
+    /// на всех инстансах, а подпись не совпадает с хешами токенов
+    pub fn new(repository: Arc<R>, secret: Option<&str>, pepper: &str, ttl: Duration) -> Self {
+        let secret = match secret {
+            Some(secret) => secret.as_bytes().to_vec(),
+            None => {
+                let mut mac = HmacSha256::new_from_slice(pepper.as_bytes())
+                    .expect("HMAC accepts keys of any length");
+                mac.update(b"dashboard-session");
+                mac.finalize().into_bytes().to_vec()
+            }
+        };
+
+        Self {
+            repository,
+            secret,
+            ttl,
+        }
+    }
+
+    /// Создать сессию для токена (не дольше срока действия самого токена)
+    pub async fn create_for_token(
+        &self,
+        token: &ApiToken,
+        ip: Option<String>,
+        user_agent: Option<String>,
+    ) -> Result<IssuedSession, AppError> {
+        let expires_at = match token.expires_at {
+            Some(token_expires_at) => token_expires_at.min(Utc::now() + self.ttl),
+            None => Utc::now() + self.ttl,
+        };
+
+        self.create(Some(token.id), None, expires_at, ip, user_agent)
+            .await
+    }
+
+    /// Создать сессию после входа по логину и паролю
+    pub async fn create_for_user(
+        &self,
+        user: &User,
+        ip: Option<String>,
+        user_agent: Option<String>,
+    ) -> Result<IssuedSession, AppError> {
+        let expires_at = Utc::now() + self.ttl;
+        self.create(None, Some(user.id), expires_at, ip, user_agent)
+            .await
+    }
+
+    async fn create(
+        &self,
+        token_id: Option<i64>,
+        user_id: Option<i64>,
+        expires_at: DateTime<Utc>,
+        ip: Option<String>,
+        user_agent: Option<String>,
+    ) -> Result<IssuedSession, AppError> {
+        // Попутно чистим старые записи
+        let _ = self.repository.delete_expired().await;
+
+        let session_id = URL_SAFE_NO_PAD.encode(random_bytes());
+
+        let session = self
+            .repository
+            .create(NewDashboardSession {
+                session_hash: hash_session_id(&session_id),
+                token_id,
+                user_id,
+                expires_at,
+                ip,
+                user_agent,
+            })
+            .await?;
+
+        Ok(IssuedSession {
+            cookie_value: format!("{}.{}", session_id, self.sign(&session_id)),
+            session,
+        })
+    }
+
+    /// Проверить cookie и найти действующую сессию
+    pub async fn verify(&self, cookie_value: &str) -> Result<DashboardSession, AppError> {
+        let session_id = self.verified_id(cookie_value).ok_or_else(invalid_session)?;
+
+        let session = self
+            .repository
+            .find_active_by_hash(&hash_session_id(session_id))
+            .await?
+            .ok_or_else(invalid_session)?;
+
+        let _ = self.repository.touch(session.id).await;
+
+        Ok(session)
+    }
+
+    /// Отозвать сессию (выход). Неизвестные и поддельные cookie игнорируются.
+    pub async fn revoke(&self, cookie_value: &str) -> Result<(), AppError> {
+        match self.verified_id(cookie_value) {
+            Some(session_id) => self.repository.revoke(&hash_session_id(session_id)).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Завершить все сессии пользователя
+    pub async fn revoke_for_user(&self, user_id: i64) -> Result<(), AppError> {
+        self.repository.revoke_for_user(user_id).await
+    }
+
+    fn mac(&self, session_id: &str) -> HmacSha256 {
+        let mut mac =
+            HmacSha256::new_from_slice(&self.secret).expect("HMAC accepts keys of any length");
+        mac.update(session_id.as_bytes());
+        mac
+    }
+
+    fn sign(&self, session_id: &str) -> String {
+        URL_SAFE_NO_PAD.encode(self.mac(session_id).finalize().into_bytes())
+    }
+
+    /// Идентификатор сессии из cookie, если подпись верна
+    fn verified_id<'a>(&self, cookie_value: &'a str) -> Option<&'a str> {
+        let (session_id, signature) = cookie_value.split_once('.')?;
+        let signature = URL_SAFE_NO_PAD.decode(signature).ok()?;
+
+        self.mac(session_id)
+            .verify_slice(&signature)
+            .ok()
+            .map(|_| session_id)
+    }
+}
+
+fn random_bytes() -> [u8; SESSION_ID_BYTES] {
+    let mut buffer = [0u8; SESSION_ID_BYTES];
+    OsRng
+        .try_fill_bytes(&mut buffer)
+        .expect("Failed to generate random bytes");
+    buffer
+}
+
+fn hash_session_id(session_id: &str) -> String {
+    hex::encode(Sha256::digest(session_id.as_bytes()))
+}
+
+fn invalid_session() -> AppError {
+    AppError::unauthorized(
+        "Unauthorized",
+        json!({"reason": "Invalid, revoked or expired session"}),
+    )
+}
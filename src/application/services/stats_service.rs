@@ -47,7 +47,7 @@ impl<R: StatsRepository> StatsService<R> {
         self.repository.get_all_stats(filter).await
     }
 
-    pub async fn count_all_links(&self) -> Result<i64, AppError> {
-        self.repository.count_all_links().await
+    pub async fn count_all_links(&self, filter: &StatsFilter) -> Result<i64, AppError> {
+        self.repository.count_all_links(filter).await
     }
 }
@@ -0,0 +1,52 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+
+use crate::domain::repositories::{TagRepository, TagStats};
+use crate::error::AppError;
+
+/// Сервис для работы с тегами (кампаниями) ссылок
+pub struct TagService<R: TagRepository> {
+    repository: Arc<R>,
+}
+
+impl<R: TagRepository> TagService<R> {
+    pub fn new(repository: Arc<R>) -> Self {
+        Self { repository }
+    }
+
+    /// Привязать теги к ссылке, возвращает нормализованный список
+    pub async fn attach_tags(
+        &self,
+        link_id: i64,
+        tags: Vec<String>,
+    ) -> Result<Vec<String>, AppError> {
+        let tags = normalize_tags(tags);
+        self.repository.attach_tags(link_id, &tags).await?;
+        Ok(tags)
+    }
+
+    /// Статистика по тегам
+    pub async fn list_tag_stats(
+        &self,
+        domain_id: Option<i64>,
+        from_date: Option<DateTime<Utc>>,
+        to_date: Option<DateTime<Utc>>,
+    ) -> Result<Vec<TagStats>, AppError> {
+        self.repository
+            .list_stats(domain_id, from_date, to_date)
+            .await
+    }
+}
+
+/// Приведение тегов к нижнему регистру и удаление дублей
+pub fn normalize_tags(tags: Vec<String>) -> Vec<String> {
+    let mut normalized: Vec<String> = tags
+        .into_iter()
+        .map(|t| t.trim().to_lowercase())
+        .filter(|t| !t.is_empty())
+        .collect();
+    normalized.sort();
+    normalized.dedup();
+    normalized
+}
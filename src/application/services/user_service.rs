@@ -0,0 +1,181 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde_json::json;
+use std::sync::Arc;
+
+use crate::domain::entities::{NewUser, Role, User};
+use crate::domain::repositories::UserRepository;
+use crate::error::AppError;
+use crate::utils::password::{hash_password, verify_password};
+
+const MIN_PASSWORD_LENGTH: usize = 12;
+
+lazy_static! {
+    static ref USERNAME_REGEX: Regex = Regex::new(r"^[a-z0-9][a-z0-9._-]{2,63}$").unwrap();
+
+    /// Хэш пароля, которого ни у кого нет (для выравнивания времени ответа)
+    static ref DUMMY_HASH: String = hash_password("timing-equalization-only")
+        .expect("failed to hash the timing-equalization password");
+}
+
+/// Сервис пользователей
+pub struct UserService<R: UserRepository> {
+    repository: Arc<R>,
+}
+
+impl<R: UserRepository> UserService<R> {
+    pub fn new(repository: Arc<R>) -> Self {
+        Self { repository }
+    }
+
+    /// Создать пользователя (домены имеют смысл только для редактора)
+    pub async fn create_user(
+        &self,
+        username: &str,
+        password: &str,
+        role: Role,
+        domain_ids: Vec<i64>,
+    ) -> Result<User, AppError> {
+        let username = username.trim().to_lowercase();
+        validate_username(&username)?;
+        validate_password(password)?;
+
+        if self.repository.find_by_username(&username).await?.is_some() {
+            return Err(AppError::conflict(
+                "User already exists",
+                json!({"username": username}),
+            ));
+        }
+
+        self.repository
+            .create(NewUser {
+                username,
+                password_hash: hash_password_blocking(password).await?,
+                role,
+                domain_ids: domain_ids_for(role, domain_ids),
+            })
+            .await
+    }
+
+    pub async fn get_user(&self, username: &str) -> Result<User, AppError> {
+        self.repository
+            .find_by_username(&username.trim().to_lowercase())
+            .await?
+            .ok_or_else(|| AppError::not_found("User not found", json!({"username": username})))
+    }
+
+    pub async fn list_users(&self) -> Result<Vec<User>, AppError> {
+        self.repository.list().await
+    }
+
+    /// Действующий (не заблокированный) пользователь
+    pub async fn find_active(&self, id: i64) -> Result<Option<User>, AppError> {
+        Ok(self
+            .repository
+            .find_by_id(id)
+            .await?
+            .filter(User::is_active))
+    }
+
+    /// Проверка логина и пароля
+    pub async fn authenticate(&self, username: &str, password: &str) -> Result<User, AppError> {
+        let user = self
+            .repository
+            .find_by_username(&username.trim().to_lowercase())
+            .await?;
+
+        // Для несуществующего пользователя тоже считаем хэш, чтобы не выдавать его временем ответа
+        let password_hash = user.as_ref().map(|u| u.password_hash.clone());
+        let password = password.to_owned();
+        let password_ok = run_blocking(move || {
+            verify_password(
+                &password,
+                password_hash.as_deref().unwrap_or(DUMMY_HASH.as_str()),
+            )
+        })
+        .await?;
+
+        match user {
+            Some(user) if password_ok && user.is_active() => {
+                let _ = self.repository.update_last_login(user.id).await;
+                Ok(user)
+            }
+            _ => Err(AppError::unauthorized(
+                "Unauthorized",
+                json!({"reason": "Invalid username or password"}),
+            )),
+        }
+    }
+
+    pub async fn reset_password(&self, user: &User, password: &str) -> Result<(), AppError> {
+        validate_password(password)?;
+        self.repository
+            .update_password(user.id, &hash_password_blocking(password).await?)
+            .await
+    }
+
+    pub async fn set_role(
+        &self,
+        user: &User,
+        role: Role,
+        domain_ids: Vec<i64>,
+    ) -> Result<(), AppError> {
+        self.repository
+            .update_role(user.id, role, &domain_ids_for(role, domain_ids))
+            .await
+    }
+
+    pub async fn set_disabled(&self, user: &User, disabled: bool) -> Result<(), AppError> {
+        self.repository.set_disabled(user.id, disabled).await
+    }
+}
+
+/// Argon2 занимает CPU на десятки миллисекунд — не держим им рабочие потоки tokio
+async fn run_blocking<T: Send + 'static>(
+    f: impl FnOnce() -> T + Send + 'static,
+) -> Result<T, AppError> {
+    tokio::task::spawn_blocking(f).await.map_err(|e| {
+        AppError::internal(
+            "Password hashing task failed",
+            json!({"error": e.to_string()}),
+        )
+    })
+}
+
+async fn hash_password_blocking(password: &str) -> Result<String, AppError> {
+    let password = password.to_owned();
+    run_blocking(move || hash_password(&password)).await?
+}
+
+/// Домены хранятся только у редактора: админу они не нужны, зрителю не дают прав
+fn domain_ids_for(role: Role, domain_ids: Vec<i64>) -> Vec<i64> {
+    match role {
+        Role::Editor => domain_ids,
+        Role::Admin | Role::Viewer => Vec::new(),
+    }
+}
+
+fn validate_username(username: &str) -> Result<(), AppError> {
+    if !USERNAME_REGEX.is_match(username) {
+        return Err(AppError::bad_request(
+            "Username must be 3-64 characters: lowercase letters, digits, '.', '_' or '-'",
+            json!({"username": username}),
+        ));
+    }
+
+    Ok(())
+}
+
+fn validate_password(password: &str) -> Result<(), AppError> {
+    if password.chars().count() < MIN_PASSWORD_LENGTH {
+        return Err(AppError::bad_request(
+            format!(
+                "Password must be at least {} characters",
+                MIN_PASSWORD_LENGTH
+            ),
+            json!({}),
+        ));
+    }
+
+    Ok(())
+}
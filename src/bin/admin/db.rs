@@ -0,0 +1,170 @@
+//! Команды `admin db` и `admin stats`
+
+use url_shortener::application::services::{DomainService, NameOutcome};
+use url_shortener::infrastructure::persistence::PgDomainRepository;
+
+use anyhow::Result;
+use clap::Subcommand;
+use colored::*;
+use sqlx::PgPool;
+use std::sync::Arc;
+
+use crate::domains::notify_domains_changed;
+
+#[derive(Subcommand)]
+pub(crate) enum DbAction {
+    /// Check database connection
+    Check,
+
+    /// Show database info
+    Info,
+
+    /// Bring domain and alias names to canonical form (lowercase, punycode)
+    /// and report names that conflict or cannot be parsed
+    NormalizeDomains {
+        /// Only show what would change
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+pub(crate) async fn handle_db_action(action: DbAction, pool: &PgPool) -> Result<()> {
+    match action {
+        DbAction::Check => {
+            println!("{}", "🔍 Checking database connection...".bright_blue());
+
+            sqlx::query("SELECT 1").fetch_one(pool).await?;
+
+            println!("{}", "✅ Database connection OK".green().bold());
+        }
+        DbAction::Info => {
+            println!("{}", "ℹ️  Database Information".bright_blue().bold());
+            println!();
+
+            let version: String = sqlx::query_scalar("SELECT version()")
+                .fetch_one(pool)
+                .await?;
+
+            println!("  PostgreSQL: {}", version.bright_white());
+            println!();
+        }
+        DbAction::NormalizeDomains { dry_run } => {
+            normalize_domain_names(pool, dry_run).await?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn normalize_domain_names(pool: &PgPool, dry_run: bool) -> Result<()> {
+    println!("{}", "🔧 Normalize Domain Names".bright_blue().bold());
+    println!();
+
+    let service = DomainService::new(Arc::new(PgDomainRepository::new(Arc::new(pool.clone()))));
+    let report = service
+        .normalize_names(!dry_run)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to normalize names: {}", e))?;
+
+    if report.is_empty() {
+        println!("{}", "✅ All names are canonical".green().bold());
+        println!();
+        return Ok(());
+    }
+
+    let mut renamed = 0;
+    let mut problems = 0;
+    for item in &report {
+        let kind = if item.is_alias { "alias" } else { "domain" };
+        let outcome = match &item.outcome {
+            NameOutcome::Renamed(name) => {
+                renamed += 1;
+                format!("-> {}", name).green()
+            }
+            NameOutcome::Conflict(name) => {
+                problems += 1;
+                format!("conflicts with existing {}", name).red()
+            }
+            NameOutcome::Invalid(reason) => {
+                problems += 1;
+                format!("invalid: {}", reason).red()
+            }
+        };
+
+        println!(
+            "  {:<7} {:<40} {}",
+            kind.bright_black(),
+            item.current.cyan(),
+            outcome
+        );
+    }
+
+    println!();
+    if dry_run {
+        println!(
+            "  Would rename: {}",
+            renamed.to_string().bright_white().bold()
+        );
+    } else {
+        println!("  Renamed: {}", renamed.to_string().bright_white().bold());
+        if renamed > 0 {
+            notify_domains_changed().await;
+        }
+    }
+
+    if problems > 0 {
+        println!(
+            "  {} {}",
+            problems.to_string().red().bold(),
+            "name(s) need manual attention: rename or remove them".yellow()
+        );
+        println!();
+        std::process::exit(1);
+    }
+
+    println!();
+    Ok(())
+}
+
+pub(crate) async fn handle_stats(pool: &PgPool) -> Result<()> {
+    println!("{}", "📊 Statistics".bright_blue().bold());
+    println!();
+
+    let links_count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM links")
+        .fetch_one(pool)
+        .await?;
+
+    let clicks_count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM link_clicks")
+        .fetch_one(pool)
+        .await?;
+
+    let tokens_count: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM api_tokens WHERE revoked_at IS NULL")
+            .fetch_one(pool)
+            .await?;
+
+    let users_count: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM users WHERE disabled_at IS NULL")
+            .fetch_one(pool)
+            .await?;
+
+    println!(
+        "  Links:         {}",
+        links_count.to_string().bright_green().bold()
+    );
+    println!(
+        "  Clicks:        {}",
+        clicks_count.to_string().bright_green().bold()
+    );
+    println!(
+        "  Active tokens: {}",
+        tokens_count.to_string().bright_green().bold()
+    );
+    println!(
+        "  Active users:  {}",
+        users_count.to_string().bright_green().bold()
+    );
+    println!();
+
+    Ok(())
+}
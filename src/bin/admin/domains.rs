@@ -0,0 +1,615 @@
+//! Команды `admin domain`: домены, алиасы и проверка владения
+
+use url_shortener::application::services::DomainService;
+use url_shortener::domain::entities::{
+    CodeAlphabet, CodeStrategy, Domain, DomainAlias, UpdateDomain,
+};
+use url_shortener::infrastructure::cache::{CacheService, RedisCache, domain_prefix};
+use url_shortener::infrastructure::persistence::PgDomainRepository;
+use url_shortener::infrastructure::verification::DomainVerifier;
+
+use anyhow::{Context, Result};
+use clap::Subcommand;
+use colored::*;
+use dialoguer::Confirm;
+use sqlx::PgPool;
+use std::sync::Arc;
+
+use crate::{format_time, report_notify_error, servers_cache};
+
+#[derive(Subcommand)]
+pub(crate) enum DomainAction {
+    /// Add a new domain
+    Add {
+        /// Domain name (e.g., go.example.com)
+        domain: String,
+
+        /// Make it the default domain for links created without one
+        #[arg(long)]
+        default: bool,
+
+        /// Free-form description
+        #[arg(short, long)]
+        description: Option<String>,
+
+        /// Skip the ownership check and activate right away
+        #[arg(long)]
+        verified: bool,
+    },
+
+    /// List all domains
+    List,
+
+    /// Add an alias (www.s.example.com) or a wildcard (*.go.example.com) for a domain
+    AliasAdd {
+        domain: String,
+        host: String,
+
+        /// Skip the ownership check and serve the alias right away
+        #[arg(long)]
+        verified: bool,
+    },
+
+    /// Check ownership of an alias (for a wildcard - of its suffix)
+    AliasVerify {
+        domain: String,
+        host: String,
+
+        /// Mark verified without checking DNS/HTTP
+        #[arg(long)]
+        manual: bool,
+    },
+
+    /// Remove an alias or a wildcard of a domain
+    AliasRemove { domain: String, host: String },
+
+    /// Show or change how link codes are generated and validated on a domain
+    CodePolicy {
+        domain: String,
+
+        /// How codes are generated (default - use CODE_STRATEGY)
+        #[arg(long, value_parser = ["random", "sequential", "words", "default"])]
+        strategy: Option<String>,
+
+        /// Length of generated codes
+        #[arg(long)]
+        length: Option<usize>,
+
+        /// Alphabet of generated codes: base62, lowercase, no_lookalike
+        #[arg(long, value_parser = parse_code_alphabet)]
+        alphabet: Option<CodeAlphabet>,
+
+        /// Minimum length of custom codes
+        #[arg(long)]
+        custom_min: Option<usize>,
+
+        /// Maximum length of custom codes
+        #[arg(long)]
+        custom_max: Option<usize>,
+    },
+
+    /// Check domain ownership (DNS TXT record or HTTP challenge) and activate it
+    Verify {
+        domain: String,
+
+        /// Mark as verified without checking
+        #[arg(long)]
+        manual: bool,
+    },
+
+    /// Start serving redirects on a domain
+    Activate { domain: String },
+
+    /// Stop serving redirects on a domain (links are kept)
+    Deactivate { domain: String },
+
+    /// Make a domain the default one
+    SetDefault { domain: String },
+
+    /// Remove a domain without links
+    Remove {
+        domain: String,
+
+        /// Skip confirmation prompt
+        #[arg(short = 'y', long)]
+        yes: bool,
+    },
+}
+
+pub(crate) async fn handle_domain_action(action: DomainAction, pool: &PgPool) -> Result<()> {
+    let service = DomainService::new(Arc::new(PgDomainRepository::new(Arc::new(pool.clone()))));
+
+    match action {
+        DomainAction::Add {
+            domain,
+            default,
+            description,
+            verified,
+        } => {
+            println!("{}", "🌐 Add Domain".bright_blue().bold());
+            println!();
+
+            let created = service
+                .create_domain(domain, default, description, verified)
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to add domain: {}", e))?;
+
+            println!("  Domain:  {}", created.domain.cyan());
+            println!("  Default: {}", format_bool(default));
+            println!();
+            println!("{}", "✅ Domain added".green().bold());
+            println!();
+            print_verification_instructions(&created);
+        }
+        DomainAction::List => {
+            list_domains(&service).await?;
+        }
+        DomainAction::AliasAdd {
+            domain,
+            host,
+            verified,
+        } => {
+            let domain = get_domain(&service, &domain).await?;
+            let alias = service
+                .add_alias(&domain, host, verified)
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to add alias: {}", e))?;
+
+            println!("  {} -> {}", alias.host.cyan(), domain.domain.cyan());
+            println!("{}", "✅ Alias added".green().bold());
+            println!();
+            print_alias_verification_instructions(&domain, &alias);
+        }
+        DomainAction::AliasVerify {
+            domain,
+            host,
+            manual,
+        } => {
+            let domain = get_domain(&service, &domain).await?;
+            let alias = service
+                .get_alias(&domain, &host)
+                .await
+                .map_err(|e| anyhow::anyhow!("{}", e))?;
+            if alias.verification.is_verified() {
+                println!("{}", "⚠️  This alias is already verified".yellow());
+                return Ok(());
+            }
+
+            let result = if manual {
+                service.mark_alias_verified_manually(&alias).await
+            } else {
+                let challenge = alias
+                    .verification_challenge()
+                    .context("Alias has no verification token")?;
+                let outcome = domain_verifier()?.check(&challenge).await;
+                service.complete_alias_verification(&alias, outcome).await
+            };
+
+            match result {
+                Ok(verified) => {
+                    println!("  Alias:  {}", verified.host.cyan());
+                    println!(
+                        "  Method: {}",
+                        verified.verification.method.map_or("-", |m| m.as_str())
+                    );
+                    println!("{}", "✅ Alias verified".green().bold());
+                }
+                Err(e) => {
+                    println!("{}", format!("❌ {}", e).red());
+
+                    let alias = service
+                        .get_alias(&domain, &alias.host)
+                        .await
+                        .map_err(|e| anyhow::anyhow!("{}", e))?;
+                    if let Some(reason) = &alias.verification.last_error {
+                        println!("  {}", reason.bright_black());
+                    }
+                    println!();
+                    print_alias_verification_instructions(&domain, &alias);
+                    std::process::exit(1);
+                }
+            }
+        }
+        DomainAction::AliasRemove { domain, host } => {
+            let domain = get_domain(&service, &domain).await?;
+            service
+                .remove_alias(&domain, &host)
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to remove alias: {}", e))?;
+
+            println!("{}", "✅ Alias removed".green().bold());
+        }
+        DomainAction::CodePolicy {
+            domain,
+            strategy,
+            length,
+            alphabet,
+            custom_min,
+            custom_max,
+        } => {
+            let mut domain = get_domain(&service, &domain).await?;
+
+            let strategy = strategy
+                .map(|strategy| match strategy.as_str() {
+                    "default" => Ok(None),
+                    other => other.parse::<CodeStrategy>().map(Some),
+                })
+                .transpose()
+                .map_err(anyhow::Error::msg)?;
+
+            let update = UpdateDomain {
+                code_strategy: strategy,
+                code_length: length,
+                code_alphabet: alphabet,
+                custom_code_min_length: custom_min,
+                custom_code_max_length: custom_max,
+                ..Default::default()
+            };
+            let changed = strategy.is_some()
+                || length.is_some()
+                || alphabet.is_some()
+                || custom_min.is_some()
+                || custom_max.is_some();
+
+            if changed {
+                domain = service
+                    .update_domain(domain.id, update)
+                    .await
+                    .map_err(|e| anyhow::anyhow!("Failed to update code policy: {}", e))?;
+            }
+
+            let policy = &domain.code_policy;
+            println!("  Domain:       {}", domain.domain.cyan());
+            match policy.strategy {
+                Some(strategy) => println!("  Strategy:     {}", strategy.as_str().yellow()),
+                None => println!("  Strategy:     {}", "default (CODE_STRATEGY)".dimmed()),
+            }
+            println!("  Length:       {}", policy.length);
+            println!("  Alphabet:     {}", policy.alphabet.as_str().yellow());
+            println!(
+                "  Custom codes: {}-{} characters",
+                policy.custom_min_length, policy.custom_max_length
+            );
+
+            if changed {
+                println!("{}", "✅ Code policy updated".green().bold());
+            }
+        }
+        DomainAction::Verify { domain, manual } => {
+            let domain = get_domain(&service, &domain).await?;
+            if domain.verification.is_verified() {
+                println!("{}", "⚠️  This domain is already verified".yellow());
+                return Ok(());
+            }
+
+            let result = if manual {
+                service.mark_verified_manually(domain.id).await
+            } else {
+                let challenge = domain
+                    .verification_challenge()
+                    .context("Domain has no verification token")?;
+                let outcome = domain_verifier()?.check(&challenge).await;
+                service.complete_verification(&domain, outcome).await
+            };
+
+            match result {
+                Ok(verified) => {
+                    println!("  Domain: {}", verified.domain.cyan());
+                    println!(
+                        "  Method: {}",
+                        verified.verification.method.map_or("-", |m| m.as_str())
+                    );
+                    println!("{}", "✅ Domain verified and activated".green().bold());
+                }
+                Err(e) => {
+                    println!("{}", format!("❌ {}", e).red());
+
+                    // Причина сохраняется вместе с доменом
+                    let domain = get_domain(&service, &domain.domain).await?;
+                    if let Some(reason) = &domain.verification.last_error {
+                        println!("  {}", reason.bright_black());
+                    }
+                    println!();
+                    print_verification_instructions(&domain);
+                    std::process::exit(1);
+                }
+            }
+        }
+        DomainAction::Activate { domain } => {
+            let domain = get_domain(&service, &domain).await?;
+            if domain.is_active {
+                println!("{}", "⚠️  This domain is already active".yellow());
+                return Ok(());
+            }
+
+            set_domain_active(&service, &domain, true).await?;
+            println!("{}", "✅ Domain activated".green().bold());
+        }
+        DomainAction::Deactivate { domain } => {
+            let domain = get_domain(&service, &domain).await?;
+            if !domain.is_active {
+                println!("{}", "⚠️  This domain is already inactive".yellow());
+                return Ok(());
+            }
+
+            set_domain_active(&service, &domain, false).await?;
+            invalidate_domain_cache(&domain).await;
+            println!("{}", "✅ Domain deactivated".green().bold());
+        }
+        DomainAction::SetDefault { domain } => {
+            let domain = get_domain(&service, &domain).await?;
+            if domain.is_default {
+                println!("{}", "⚠️  This domain is already the default".yellow());
+                return Ok(());
+            }
+
+            service
+                .set_default(domain.id)
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to set default domain: {}", e))?;
+
+            println!("  Default: {}", domain.domain.cyan());
+            println!("{}", "✅ Default domain changed".green().bold());
+        }
+        DomainAction::Remove { domain, yes } => {
+            let domain = get_domain(&service, &domain).await?;
+
+            if !yes {
+                let confirmed = Confirm::new()
+                    .with_prompt(format!("Remove {}?", domain.domain))
+                    .default(false)
+                    .interact()?;
+
+                if !confirmed {
+                    println!("{}", "❌ Cancelled".red());
+                    return Ok(());
+                }
+            }
+
+            service
+                .delete_domain(domain.id)
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to remove domain: {}", e))?;
+
+            println!("{}", "✅ Domain removed".green().bold());
+        }
+    }
+
+    Ok(())
+}
+
+async fn list_domains(service: &DomainService<PgDomainRepository>) -> Result<()> {
+    println!("{}", "🌐 Domains".bright_blue().bold());
+    println!();
+
+    let domains = service
+        .list_domain_overviews(false)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to list domains: {}", e))?;
+
+    if domains.is_empty() {
+        println!("{}", "  No domains found".yellow());
+        println!();
+        println!(
+            "  Create one with: {} admin domain add <domain> --default",
+            "cargo run --bin".bright_cyan()
+        );
+        return Ok(());
+    }
+
+    println!(
+        "  {:<4} {:<32} {:<8} {:<9} {:<9} {:<8} {:<17} {}",
+        "ID".bright_white().bold(),
+        "Domain".bright_white().bold(),
+        "Default".bright_white().bold(),
+        "Status".bright_white().bold(),
+        "Verified".bright_white().bold(),
+        "Links".bright_white().bold(),
+        "Created".bright_white().bold(),
+        "Description".bright_white().bold()
+    );
+    println!("  {}", "─".repeat(110).bright_black());
+
+    for overview in &domains {
+        let domain = &overview.domain;
+
+        let status = if domain.is_active {
+            "ACTIVE".green()
+        } else {
+            "INACTIVE".red()
+        };
+
+        let default = if domain.is_default {
+            "yes".bright_yellow()
+        } else {
+            "".normal()
+        };
+
+        let verified = match domain.verification.method {
+            Some(method) => method.as_str().green(),
+            None => "pending".yellow(),
+        };
+
+        println!(
+            "  {:<4} {:<32} {:<8} {:<9} {:<9} {:<8} {:<17} {}",
+            domain.id.to_string().bright_black(),
+            domain.domain.cyan(),
+            default,
+            status,
+            verified,
+            overview.links_count,
+            format_time(domain.created_at).bright_black(),
+            domain.description.as_deref().unwrap_or("").bright_black()
+        );
+
+        if !overview.aliases.is_empty() {
+            let hosts: Vec<String> = overview
+                .aliases
+                .iter()
+                .map(|alias| match alias.verification.is_verified() {
+                    true => alias.host.clone(),
+                    false => format!("{} (pending)", alias.host),
+                })
+                .collect();
+            println!(
+                "       {} {}",
+                "↳ aliases:".bright_black(),
+                hosts.join(", ").bright_black()
+            );
+        }
+    }
+
+    println!();
+    println!(
+        "  Total: {}",
+        domains.len().to_string().bright_white().bold()
+    );
+    println!();
+
+    Ok(())
+}
+
+/// Что опубликовать для проверки неподтверждённого домена
+fn print_verification_instructions(domain: &Domain) {
+    let Some(challenge) = domain.verification_challenge() else {
+        return;
+    };
+
+    println!(
+        "{}",
+        "⏳ Domain is inactive until ownership is verified".yellow()
+    );
+    println!("  Publish one of:");
+    println!(
+        "    DNS TXT  {} = {}",
+        challenge.dns_name.cyan(),
+        challenge.dns_value.bright_white()
+    );
+    println!(
+        "    HTTP     {} responding with {}",
+        challenge.http_url.cyan(),
+        challenge.token.bright_white()
+    );
+    println!();
+    println!(
+        "  Then run: {} admin domain verify {}",
+        "cargo run --bin".bright_cyan(),
+        domain.domain
+    );
+    println!();
+}
+
+/// Что опубликовать для проверки неподтверждённого алиаса
+fn print_alias_verification_instructions(domain: &Domain, alias: &DomainAlias) {
+    let Some(challenge) = alias.verification_challenge() else {
+        return;
+    };
+
+    println!(
+        "{}",
+        "⏳ Alias is not served until ownership is verified".yellow()
+    );
+    println!("  Publish one of:");
+    println!(
+        "    DNS TXT  {} = {}",
+        challenge.dns_name.cyan(),
+        challenge.dns_value.bright_white()
+    );
+    println!(
+        "    HTTP     {} responding with {}",
+        challenge.http_url.cyan(),
+        challenge.token.bright_white()
+    );
+    println!();
+    println!(
+        "  Then run: {} admin domain alias-verify {} {}",
+        "cargo run --bin".bright_cyan(),
+        domain.domain,
+        alias.host
+    );
+    println!();
+}
+
+/// Проверка с теми же настройками, что и у сервера
+fn domain_verifier() -> Result<DomainVerifier> {
+    let timeout_ms = std::env::var("DOMAIN_VERIFICATION_TIMEOUT_MS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(5_000);
+    let allow_private = std::env::var("DOMAIN_VERIFICATION_ALLOW_PRIVATE")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(false);
+
+    DomainVerifier::from_network(std::time::Duration::from_millis(timeout_ms), allow_private)
+        .context("Failed to initialize domain verification")
+}
+
+pub(crate) async fn get_domain(
+    service: &DomainService<PgDomainRepository>,
+    domain: &str,
+) -> Result<Domain> {
+    service
+        .get_domain(domain)
+        .await
+        .map_err(|e| anyhow::anyhow!("{}: {}", domain, e))
+}
+
+async fn set_domain_active(
+    service: &DomainService<PgDomainRepository>,
+    domain: &Domain,
+    is_active: bool,
+) -> Result<()> {
+    service
+        .update_domain(
+            domain.id,
+            UpdateDomain {
+                is_active: Some(is_active),
+                ..Default::default()
+            },
+        )
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to update domain: {}", e))?;
+
+    println!("  Domain: {}", domain.domain.cyan());
+    Ok(())
+}
+
+/// Сбросить закэшированные редиректы домена (если задан REDIS_URL)
+async fn invalidate_domain_cache(domain: &Domain) {
+    let Some(redis_url) = std::env::var("REDIS_URL")
+        .ok()
+        .filter(|url| !url.is_empty())
+    else {
+        return;
+    };
+
+    let result = match RedisCache::connect(&redis_url).await {
+        Ok(cache) => {
+            cache
+                .invalidate_prefix(&domain_prefix(&domain.domain))
+                .await
+        }
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(()) => println!("  {}", "Cached redirects cleared".bright_black()),
+        Err(e) => println!("{} {}", "⚠️  Failed to clear cached redirects:".yellow(), e),
+    }
+}
+
+/// Сбросить кэш хостов запущенных серверов (если задан REDIS_URL)
+pub(crate) async fn notify_domains_changed() {
+    if let Some(cache) = servers_cache().await {
+        report_notify_error(cache.publish_domains_changed().await);
+    }
+}
+
+fn format_bool(value: bool) -> ColoredString {
+    if value { "yes".green() } else { "no".normal() }
+}
+
+fn parse_code_alphabet(value: &str) -> Result<CodeAlphabet, String> {
+    value.parse()
+}
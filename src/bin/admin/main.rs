@@ -0,0 +1,124 @@
+mod db;
+mod domains;
+mod reserved;
+mod tokens;
+mod users;
+
+use url_shortener::infrastructure::cache::{CacheResult, RedisCache};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use clap::{Parser, Subcommand};
+use colored::*;
+use sqlx::PgPool;
+
+use db::{DbAction, handle_db_action, handle_stats};
+use domains::{DomainAction, handle_domain_action, notify_domains_changed};
+use reserved::{ReservedAction, handle_reserved_action, notify_reserved_codes_changed};
+use tokens::{TokenAction, handle_token_action};
+use users::{UserAction, handle_user_action};
+
+/// CLI tool for managing url-shortener
+#[derive(Parser)]
+#[command(name = "admin")]
+#[command(author, version, about, long_about = None)]
+struct Cli {
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Manage API tokens
+    Token {
+        #[command(subcommand)]
+        action: TokenAction,
+    },
+
+    /// Manage short link domains
+    Domain {
+        #[command(subcommand)]
+        action: DomainAction,
+    },
+
+    /// Manage dashboard users
+    User {
+        #[command(subcommand)]
+        action: UserAction,
+    },
+
+    /// Manage codes that cannot be used for links
+    Reserved {
+        #[command(subcommand)]
+        action: ReservedAction,
+    },
+
+    /// Show statistics
+    Stats,
+
+    /// Database operations
+    Db {
+        #[command(subcommand)]
+        action: DbAction,
+    },
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    // Load configuration
+    dotenvy::dotenv().ok();
+
+    let cli = Cli::parse();
+
+    // Connect to database
+    let database_url = std::env::var("DATABASE_URL").context("DATABASE_URL must be set")?;
+
+    let pool = PgPool::connect(&database_url)
+        .await
+        .context("Failed to connect to database")?;
+
+    match cli.command {
+        Commands::Token { action } => handle_token_action(action, &pool).await?,
+        Commands::Domain { action } => {
+            let changes_domains = !matches!(action, DomainAction::List);
+            handle_domain_action(action, &pool).await?;
+            if changes_domains {
+                notify_domains_changed().await;
+            }
+        }
+        Commands::User { action } => handle_user_action(action, &pool).await?,
+        Commands::Reserved { action } => {
+            let changes_codes = !matches!(action, ReservedAction::List);
+            handle_reserved_action(action, &pool).await?;
+            if changes_codes {
+                notify_reserved_codes_changed().await;
+            }
+        }
+        Commands::Stats => handle_stats(&pool).await?,
+        Commands::Db { action } => handle_db_action(action, &pool).await?,
+    }
+
+    Ok(())
+}
+
+pub(crate) fn format_time(at: DateTime<Utc>) -> String {
+    at.format("%Y-%m-%d %H:%M").to_string()
+}
+
+/// Redis запущенных серверов (None, если REDIS_URL не задан или недоступен)
+pub(crate) async fn servers_cache() -> Option<RedisCache> {
+    let redis_url = std::env::var("REDIS_URL")
+        .ok()
+        .filter(|url| !url.is_empty())?;
+
+    RedisCache::connect(&redis_url)
+        .await
+        .map_err(|e| report_notify_error(Err(e)))
+        .ok()
+}
+
+pub(crate) fn report_notify_error(result: CacheResult<()>) {
+    if let Err(e) = result {
+        println!("{} {}", "⚠️  Failed to notify running servers:".yellow(), e);
+    }
+}
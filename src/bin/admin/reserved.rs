@@ -0,0 +1,144 @@
+//! Команды `admin reserved`: зарезервированные коды
+
+use url_shortener::application::services::{DomainService, ReservedCodeService};
+use url_shortener::config::reserved_codes_from_env;
+use url_shortener::infrastructure::cache::CacheService;
+use url_shortener::infrastructure::persistence::{PgDomainRepository, PgReservedCodeRepository};
+use url_shortener::routes::reserved_route_codes;
+use url_shortener::utils::code_generator::ReservedCodes;
+
+use anyhow::Result;
+use clap::Subcommand;
+use colored::*;
+use sqlx::PgPool;
+use std::sync::Arc;
+
+use crate::domains::get_domain;
+use crate::{format_time, report_notify_error, servers_cache};
+
+#[derive(Subcommand)]
+pub(crate) enum ReservedAction {
+    /// List built-in and added reserved codes
+    List,
+
+    /// Reserve a code on all domains or on one
+    Add {
+        code: String,
+
+        /// Reserve only on this domain
+        #[arg(short, long)]
+        domain: Option<String>,
+    },
+
+    /// Release a code reserved with `reserved add`
+    Remove {
+        code: String,
+
+        /// Domain the code was reserved on
+        #[arg(short, long)]
+        domain: Option<String>,
+    },
+}
+
+pub(crate) async fn handle_reserved_action(action: ReservedAction, pool: &PgPool) -> Result<()> {
+    let pool = Arc::new(pool.clone());
+    let domain_service = DomainService::new(Arc::new(PgDomainRepository::new(pool.clone())));
+    let builtin = ReservedCodes::new(
+        reserved_route_codes()
+            .map(str::to_string)
+            .chain(reserved_codes_from_env()),
+    );
+    let service = ReservedCodeService::new(
+        Arc::new(PgReservedCodeRepository::new(pool)),
+        builtin,
+        std::time::Duration::ZERO,
+    );
+
+    match action {
+        ReservedAction::List => {
+            println!("{}", "🔒 Reserved Codes".bright_blue().bold());
+            println!();
+            println!(
+                "  {} {}",
+                "Built-in:".bright_white().bold(),
+                service.builtin().to_sorted_vec().join(", ").bright_black()
+            );
+            println!();
+
+            let codes = service
+                .list()
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to list reserved codes: {}", e))?;
+
+            if codes.is_empty() {
+                println!("{}", "  No reserved codes added".yellow());
+                return Ok(());
+            }
+
+            println!(
+                "  {:<32} {:<32} {}",
+                "Code".bright_white().bold(),
+                "Domain".bright_white().bold(),
+                "Created".bright_white().bold()
+            );
+            println!("  {}", "─".repeat(80).bright_black());
+
+            for reserved in &codes {
+                let domain = match &reserved.domain {
+                    Some(domain) => domain.cyan(),
+                    None => "all domains".bright_black(),
+                };
+                println!(
+                    "  {:<32} {:<32} {}",
+                    reserved.code.yellow(),
+                    domain,
+                    format_time(reserved.created_at)
+                );
+            }
+        }
+        ReservedAction::Add { code, domain } => {
+            let domain = match domain {
+                Some(domain) => Some(get_domain(&domain_service, &domain).await?),
+                None => None,
+            };
+
+            let reserved = service
+                .reserve(&code, domain.as_ref())
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to reserve code: {}", e))?;
+
+            println!("{}", "✅ Code reserved".green().bold());
+            println!("  Code:   {}", reserved.code.yellow());
+            println!(
+                "  Domain: {}",
+                reserved.domain.as_deref().unwrap_or("all domains")
+            );
+            println!(
+                "  {}",
+                "Existing links with this code keep working".bright_black()
+            );
+        }
+        ReservedAction::Remove { code, domain } => {
+            let domain = match domain {
+                Some(domain) => Some(get_domain(&domain_service, &domain).await?),
+                None => None,
+            };
+
+            service
+                .release(&code, domain.as_ref())
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to release code: {}", e))?;
+
+            println!("{}", "✅ Code released".green().bold());
+        }
+    }
+
+    Ok(())
+}
+
+/// Сбросить кэш зарезервированных кодов запущенных серверов (если задан REDIS_URL)
+pub(crate) async fn notify_reserved_codes_changed() {
+    if let Some(cache) = servers_cache().await {
+        report_notify_error(cache.publish_reserved_codes_changed().await);
+    }
+}
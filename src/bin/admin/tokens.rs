@@ -0,0 +1,780 @@
+//! Команды `admin token`: API токены
+
+use url_shortener::domain::entities::{Scope, User};
+use url_shortener::domain::repositories::{
+    ApiToken, DomainRepository, NewApiToken, TokenLimits, TokenRepository, UserRepository,
+};
+use url_shortener::infrastructure::persistence::{
+    PgDomainRepository, PgTokenRepository, PgUserRepository,
+};
+use url_shortener::utils::hostname::normalize_hostname;
+use url_shortener::utils::token_hash::{self, TOKEN_PREFIX, TokenHasher};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use clap::Subcommand;
+use colored::*;
+use dialoguer::{Confirm, Input, MultiSelect};
+use sqlx::PgPool;
+use std::sync::Arc;
+
+use crate::format_time;
+use crate::users::find_user;
+
+/// Политика безопасности: ротация токенов каждые 90 дней
+const DEFAULT_TOKEN_TTL_DAYS: i64 = 90;
+
+#[derive(Subcommand)]
+pub(crate) enum TokenAction {
+    /// Create a new API token
+    Create {
+        /// Token name (e.g., "Production API", "Mobile App")
+        #[arg(short, long)]
+        name: Option<String>,
+
+        /// Custom token value (optional, auto-generated if not provided)
+        #[arg(short, long)]
+        token: Option<String>,
+
+        /// Granted scope, repeatable (links:write, links:read, stats:read, domains:admin)
+        #[arg(short, long = "scope", value_parser = parse_scope)]
+        scopes: Vec<Scope>,
+
+        /// Restrict link creation to this domain, repeatable (default: any domain)
+        #[arg(short, long = "domain")]
+        domains: Vec<String>,
+
+        /// Issue the token to a user; scopes and domains are limited by the user's role
+        #[arg(short, long)]
+        user: Option<String>,
+
+        /// Requests per minute for this token (default: server-wide limit)
+        #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+        rate_limit: Option<u32>,
+
+        /// Links the token may create per day, UTC (default: unlimited)
+        #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+        daily_quota: Option<u32>,
+
+        /// Token lifetime in days
+        #[arg(long, default_value_t = DEFAULT_TOKEN_TTL_DAYS, conflicts_with = "no_expiry")]
+        expires_in_days: i64,
+
+        /// Create a token that never expires
+        #[arg(long)]
+        no_expiry: bool,
+
+        /// Skip confirmation prompt
+        #[arg(short = 'y', long)]
+        yes: bool,
+    },
+
+    /// List all tokens
+    List,
+
+    /// Set per-token rate limit and daily link quota
+    Limits {
+        /// Token name, ID or lookup id (ush_...)
+        name_or_id: String,
+
+        /// Requests per minute (omit to use the server-wide limit)
+        #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+        rate_limit: Option<u32>,
+
+        /// Links per day, UTC (omit for unlimited)
+        #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+        daily_quota: Option<u32>,
+    },
+
+    /// Issue a new secret for a token, keeping the old one valid for an overlap window
+    Rotate {
+        /// Token name, ID or lookup id (ush_...) to rotate
+        name_or_id: String,
+
+        /// Hours the old secret stays valid after rotation
+        #[arg(long, default_value_t = 24)]
+        overlap_hours: i64,
+
+        /// Lifetime of the new token in days
+        #[arg(long, default_value_t = DEFAULT_TOKEN_TTL_DAYS)]
+        expires_in_days: i64,
+
+        /// Skip confirmation prompt
+        #[arg(short = 'y', long)]
+        yes: bool,
+    },
+
+    /// Revoke a token
+    Revoke {
+        /// Token name, ID or lookup id (ush_...) to revoke
+        name_or_hash: String,
+    },
+}
+
+pub(crate) async fn handle_token_action(action: TokenAction, pool: &PgPool) -> Result<()> {
+    let repo = Arc::new(PgTokenRepository::new(Arc::new(pool.clone())));
+    let domain_repo = PgDomainRepository::new(Arc::new(pool.clone()));
+    let hasher = token_hasher()?;
+
+    match action {
+        TokenAction::Create {
+            name,
+            token,
+            scopes,
+            domains,
+            user,
+            rate_limit,
+            daily_quota,
+            expires_in_days,
+            no_expiry,
+            yes,
+        } => {
+            let allowed_domains = resolve_domains(&domain_repo, &domains).await?;
+            let user = match user {
+                Some(username) => Some(find_user(pool, &username).await?),
+                None => None,
+            };
+            let expires_at = if no_expiry {
+                None
+            } else {
+                Some(expiry_from_days(expires_in_days)?)
+            };
+            create_token(
+                repo,
+                &hasher,
+                name,
+                token,
+                TokenGrant {
+                    user,
+                    scopes,
+                    allowed_domains,
+                    limits: TokenLimits {
+                        requests_per_minute: rate_limit,
+                        daily_link_quota: daily_quota,
+                    },
+                    expires_at,
+                },
+                yes,
+            )
+            .await?;
+        }
+        TokenAction::List => {
+            let user_repo = PgUserRepository::new(Arc::new(pool.clone()));
+            list_tokens(repo, &domain_repo, &user_repo).await?;
+        }
+        TokenAction::Limits {
+            name_or_id,
+            rate_limit,
+            daily_quota,
+        } => {
+            set_token_limits(
+                repo,
+                name_or_id,
+                TokenLimits {
+                    requests_per_minute: rate_limit,
+                    daily_link_quota: daily_quota,
+                },
+            )
+            .await?;
+        }
+        TokenAction::Rotate {
+            name_or_id,
+            overlap_hours,
+            expires_in_days,
+            yes,
+        } => {
+            rotate_token(
+                repo,
+                &hasher,
+                name_or_id,
+                overlap_hours,
+                expires_in_days,
+                yes,
+            )
+            .await?;
+        }
+        TokenAction::Revoke { name_or_hash } => {
+            revoke_token(repo, name_or_hash).await?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn create_token(
+    repo: Arc<PgTokenRepository>,
+    hasher: &TokenHasher,
+    name: Option<String>,
+    token: Option<String>,
+    grant: TokenGrant,
+    skip_confirm: bool,
+) -> Result<()> {
+    let TokenGrant {
+        user,
+        scopes,
+        allowed_domains,
+        limits,
+        expires_at,
+    } = grant;
+
+    println!("{}", "🔑 Create API Token".bright_blue().bold());
+    println!();
+
+    // Get token name
+    let token_name = match name {
+        Some(n) => n,
+        None => Input::new()
+            .with_prompt("Token name")
+            .with_initial_text("Production API")
+            .interact_text()?,
+    };
+
+    // Get scopes
+    let scopes = if !scopes.is_empty() {
+        scopes
+    } else if skip_confirm {
+        anyhow::bail!("At least one --scope is required");
+    } else {
+        let labels: Vec<_> = Scope::ALL.iter().map(Scope::as_str).collect();
+        let selected = MultiSelect::new()
+            .with_prompt("Token scopes (space to select)")
+            .items(&labels)
+            .interact()?;
+
+        selected.into_iter().map(|i| Scope::ALL[i]).collect()
+    };
+
+    if scopes.is_empty() {
+        anyhow::bail!("Token must have at least one scope");
+    }
+
+    if let Some(user) = &user {
+        check_user_grant(user, &scopes, allowed_domains.as_deref())?;
+    }
+
+    // Generate or use provided token
+    let (token_value, lookup_id) = match token {
+        Some(t) => {
+            println!("{}", "⚠️  Using provided token value".yellow());
+            let lookup_id = token_hash::lookup_id(&t).map(str::to_string);
+            (t, lookup_id)
+        }
+        None => {
+            let generated = token_hash::generate_token();
+            println!("{}", "✨ Generated new token".green());
+            (generated.token, Some(generated.lookup_id))
+        }
+    };
+
+    // Show token details
+    println!();
+    println!("{}", "Token details:".bright_white().bold());
+    println!("  Name:    {}", token_name.cyan());
+    if let Some(user) = &user {
+        println!("  User:    {} ({})", user.username.cyan(), user.role);
+    }
+    println!("  Scopes:  {}", format_scopes(&scopes).bright_white());
+    println!("  Domains: {}", format_domains(allowed_domains.as_deref()));
+    println!("  Limits:  {}", format_limits(&limits));
+    println!("  Expires: {}", format_expiry(expires_at));
+    println!("  Token:   {}", token_value.bright_yellow().bold());
+    println!();
+    println!(
+        "{}",
+        "⚠️  IMPORTANT: Save this token now! You won't be able to see it again."
+            .red()
+            .bold()
+    );
+    println!();
+
+    // Confirm
+    if !skip_confirm {
+        let confirmed = Confirm::new()
+            .with_prompt("Create this token?")
+            .default(true)
+            .interact()?;
+
+        if !confirmed {
+            println!("{}", "❌ Cancelled".red());
+            return Ok(());
+        }
+    }
+
+    // Hash token
+    let token_hash = hasher.hash(&token_value);
+
+    // Save to database
+    repo.create_token(NewApiToken {
+        name: token_name,
+        token_hash,
+        scopes,
+        expires_at,
+        allowed_domain_ids: allowed_domains
+            .map(|domains| domains.into_iter().map(|d| d.0).collect()),
+        limits,
+        lookup_id,
+        user_id: user.map(|u| u.id),
+    })
+    .await
+    .map_err(|e| anyhow::anyhow!("Failed to create token: {}", e))?;
+
+    println!();
+    println!("{}", "✅ Token created successfully!".green().bold());
+    println!();
+    println!("{}", "Add this to your requests:".bright_white());
+    println!(
+        "  {}: Bearer {}",
+        "Authorization".bright_cyan(),
+        token_value.bright_yellow()
+    );
+    println!();
+    println!("{}", "Example:".bright_white());
+    println!(
+        "  curl -H \"Authorization: Bearer {}\" http://localhost:3000/stats",
+        token_value.bright_yellow()
+    );
+    println!();
+
+    Ok(())
+}
+
+async fn list_tokens(
+    repo: Arc<PgTokenRepository>,
+    domain_repo: &PgDomainRepository,
+    user_repo: &PgUserRepository,
+) -> Result<()> {
+    println!("{}", "📋 API Tokens".bright_blue().bold());
+    println!();
+
+    let tokens = repo
+        .list_tokens()
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to list tokens: {}", e))?;
+
+    let domains = domain_repo
+        .list(false)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to list domains: {}", e))?;
+
+    let users = user_repo
+        .list()
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to list users: {}", e))?;
+
+    if tokens.is_empty() {
+        println!("{}", "  No tokens found".yellow());
+        println!();
+        println!(
+            "  Create one with: {} admin token create",
+            "cargo run --bin".bright_cyan()
+        );
+        return Ok(());
+    }
+
+    println!(
+        "  {:<4} {:<24} {:<13} {:<17} {:<17} {:<17} {:<9} {}",
+        "ID".bright_white().bold(),
+        "Name".bright_white().bold(),
+        "Lookup".bright_white().bold(),
+        "Created".bright_white().bold(),
+        "Expires".bright_white().bold(),
+        "Last used".bright_white().bold(),
+        "Status".bright_white().bold(),
+        "Scopes".bright_white().bold()
+    );
+    println!("  {}", "─".repeat(144).bright_black());
+
+    for token in &tokens {
+        let status = if token.revoked_at.is_some() {
+            "REVOKED".red()
+        } else if token.is_expired() {
+            "EXPIRED".red()
+        } else {
+            "ACTIVE".green()
+        };
+
+        let expires = match token.expires_at {
+            Some(at) if token.is_active() && at - Utc::now() < Duration::days(7) => {
+                format_time(at).yellow()
+            }
+            Some(at) => format_time(at).bright_black(),
+            None => "never".yellow(),
+        };
+
+        let last_used = token
+            .last_used_at
+            .map(format_time)
+            .unwrap_or_else(|| "never".to_string());
+
+        let lookup = token
+            .lookup_id
+            .as_ref()
+            .map(|id| format!("{}{}", TOKEN_PREFIX, id))
+            .unwrap_or_else(|| "-".to_string());
+
+        println!(
+            "  {:<4} {:<24} {:<13} {:<17} {:<17} {:<17} {:<9} {}",
+            token.id.to_string().bright_black(),
+            token.name.cyan(),
+            lookup.bright_black(),
+            format_time(token.created_at).bright_black(),
+            expires,
+            last_used.bright_black(),
+            status,
+            format_scopes(&token.scopes)
+        );
+
+        if let Some(user_id) = token.user_id {
+            let user = users
+                .iter()
+                .find(|u| u.id == user_id)
+                .map(|u| format!("{} ({})", u.username, u.role))
+                .unwrap_or_else(|| format!("#{}", user_id));
+
+            println!("       {} {}", "↳ user:".bright_black(), user.cyan());
+        }
+
+        if let Some(domain_ids) = &token.allowed_domain_ids {
+            let names: Vec<_> = domain_ids
+                .iter()
+                .map(|id| {
+                    domains
+                        .iter()
+                        .find(|d| d.id == *id)
+                        .map(|d| d.domain.clone())
+                        .unwrap_or_else(|| format!("#{}", id))
+                })
+                .collect();
+
+            let names = if names.is_empty() {
+                "none".to_string()
+            } else {
+                names.join(", ")
+            };
+
+            println!("       {} {}", "↳ domains:".bright_black(), names.yellow());
+        }
+
+        if token.legacy_hash && token.is_active() {
+            println!(
+                "       {} {}",
+                "↳ hash:".bright_black(),
+                "legacy SHA-256, upgraded on next use".yellow()
+            );
+        }
+
+        if token.limits.requests_per_minute.is_some() || token.limits.daily_link_quota.is_some() {
+            println!(
+                "       {} {}",
+                "↳ limits:".bright_black(),
+                format_limits(&token.limits).yellow()
+            );
+        }
+    }
+
+    println!();
+    println!(
+        "  Total: {}",
+        tokens.len().to_string().bright_white().bold()
+    );
+    println!();
+
+    Ok(())
+}
+
+async fn revoke_token(repo: Arc<PgTokenRepository>, name_or_hash: String) -> Result<()> {
+    println!("{}", "🔒 Revoke API Token".bright_blue().bold());
+    println!();
+
+    // Try to find by name or ID
+    let token = find_token(&repo, &name_or_hash).await?;
+
+    if token.revoked_at.is_some() {
+        println!("{}", "⚠️  This token is already revoked".yellow());
+        return Ok(());
+    }
+
+    println!("  Token: {}", token.name.cyan());
+    println!("  ID:    {}", token.id.to_string().bright_black());
+    println!();
+
+    let confirmed = Confirm::new()
+        .with_prompt("Revoke this token?")
+        .default(false)
+        .interact()?;
+
+    if !confirmed {
+        println!("{}", "❌ Cancelled".red());
+        return Ok(());
+    }
+
+    repo.revoke_token(token.id)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to revoke token: {}", e))?;
+
+    println!();
+    println!("{}", "✅ Token revoked successfully!".green().bold());
+    println!();
+
+    Ok(())
+}
+
+async fn rotate_token(
+    repo: Arc<PgTokenRepository>,
+    hasher: &TokenHasher,
+    name_or_id: String,
+    overlap_hours: i64,
+    expires_in_days: i64,
+    skip_confirm: bool,
+) -> Result<()> {
+    println!("{}", "🔄 Rotate API Token".bright_blue().bold());
+    println!();
+
+    if overlap_hours < 0 {
+        anyhow::bail!("Overlap window can't be negative");
+    }
+
+    let token = find_token(&repo, &name_or_id).await?;
+
+    if !token.is_active() {
+        anyhow::bail!("Token is revoked or expired, create a new one instead");
+    }
+
+    let old_expires_at = Utc::now() + Duration::hours(overlap_hours);
+    let new_expires_at = expiry_from_days(expires_in_days)?;
+    let generated = token_hash::generate_token();
+
+    println!("  Token:          {}", token.name.cyan());
+    println!("  ID:             {}", token.id.to_string().bright_black());
+    println!("  Scopes:         {}", format_scopes(&token.scopes));
+    println!("  Old valid till: {}", format_time(old_expires_at).yellow());
+    println!("  New expires:    {}", format_time(new_expires_at));
+    println!();
+
+    if !skip_confirm {
+        let confirmed = Confirm::new()
+            .with_prompt("Rotate this token?")
+            .default(true)
+            .interact()?;
+
+        if !confirmed {
+            println!("{}", "❌ Cancelled".red());
+            return Ok(());
+        }
+    }
+
+    let new_token = repo
+        .rotate_token(
+            token.id,
+            NewApiToken {
+                name: token.name.clone(),
+                token_hash: hasher.hash(&generated.token),
+                scopes: token.scopes.clone(),
+                expires_at: Some(new_expires_at),
+                allowed_domain_ids: token.allowed_domain_ids.clone(),
+                limits: token.limits,
+                lookup_id: Some(generated.lookup_id.clone()),
+                user_id: token.user_id,
+            },
+            old_expires_at,
+        )
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to rotate token: {}", e))?;
+
+    println!();
+    println!("{}", "✅ Token rotated successfully!".green().bold());
+    println!();
+    println!("  New ID:    {}", new_token.id.to_string().bright_black());
+    println!("  New token: {}", generated.token.bright_yellow().bold());
+    println!();
+    println!(
+        "{}",
+        "⚠️  IMPORTANT: Save this token now! You won't be able to see it again."
+            .red()
+            .bold()
+    );
+    println!(
+        "  The old secret stops working at {}",
+        format_time(old_expires_at).yellow()
+    );
+    println!();
+
+    Ok(())
+}
+
+async fn set_token_limits(
+    repo: Arc<PgTokenRepository>,
+    name_or_id: String,
+    limits: TokenLimits,
+) -> Result<()> {
+    println!("{}", "🚦 Token Limits".bright_blue().bold());
+    println!();
+
+    let token = find_token(&repo, &name_or_id).await?;
+
+    repo.update_limits(token.id, limits)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to update limits: {}", e))?;
+
+    println!("  Token:  {}", token.name.cyan());
+    println!("  Before: {}", format_limits(&token.limits).bright_black());
+    println!("  After:  {}", format_limits(&limits).bright_white());
+    println!();
+    println!("{}", "✅ Limits updated".green().bold());
+    println!();
+
+    Ok(())
+}
+
+async fn find_token(repo: &PgTokenRepository, name_or_id: &str) -> Result<ApiToken> {
+    // `ush_<lookup id>` (или весь токен) - поиск по lookup id
+    if let Some(rest) = name_or_id.strip_prefix(TOKEN_PREFIX) {
+        let lookup_id = rest.split('_').next().unwrap_or(rest);
+        let token = repo
+            .find_by_lookup_id(lookup_id)
+            .await
+            .map_err(|e| anyhow::anyhow!("Database error: {}", e))?;
+
+        return token.context("Token not found");
+    }
+
+    let token = match name_or_id.parse::<i64>() {
+        Ok(id) => repo
+            .find_by_id(id)
+            .await
+            .map_err(|e| anyhow::anyhow!("Database error: {}", e))?,
+        Err(_) => repo
+            .find_by_name(name_or_id)
+            .await
+            .map_err(|e| anyhow::anyhow!("Database error: {}", e))?,
+    };
+
+    token.context("Token not found")
+}
+
+/// Токен пользователя не может дать больше, чем роль пользователя
+fn check_user_grant(
+    user: &User,
+    scopes: &[Scope],
+    allowed_domains: Option<&[(i64, String)]>,
+) -> Result<()> {
+    if !user.is_active() {
+        anyhow::bail!("User {} is disabled", user.username);
+    }
+
+    let role_scopes = user.role.scopes();
+    if let Some(scope) = scopes.iter().find(|s| !role_scopes.contains(s)) {
+        anyhow::bail!(
+            "Role {} doesn't allow scope {} (allowed: {})",
+            user.role,
+            scope,
+            format_scopes(role_scopes)
+        );
+    }
+
+    if let (Some(user_domains), Some(domains)) = (user.allowed_domain_ids(), allowed_domains)
+        && let Some((_, name)) = domains.iter().find(|(id, _)| !user_domains.contains(id))
+    {
+        anyhow::bail!("Domain {} is not assigned to {}", name, user.username);
+    }
+
+    Ok(())
+}
+
+/// Хэшер токенов с тем же pepper, что и у сервера
+fn token_hasher() -> Result<TokenHasher> {
+    let pepper = std::env::var("TOKEN_PEPPER").context("TOKEN_PEPPER must be set")?;
+    if pepper.len() < 32 {
+        anyhow::bail!("TOKEN_PEPPER must be at least 32 characters");
+    }
+
+    Ok(TokenHasher::new(&pepper))
+}
+
+/// Права и ограничения нового токена
+struct TokenGrant {
+    /// Пользователь-владелец токена
+    user: Option<User>,
+    scopes: Vec<Scope>,
+    /// (id, имя) доменов; None - без ограничений
+    allowed_domains: Option<Vec<(i64, String)>>,
+    limits: TokenLimits,
+    expires_at: Option<DateTime<Utc>>,
+}
+
+pub(crate) async fn resolve_domains(
+    domain_repo: &PgDomainRepository,
+    names: &[String],
+) -> Result<Option<Vec<(i64, String)>>> {
+    if names.is_empty() {
+        return Ok(None);
+    }
+
+    let mut resolved = Vec::with_capacity(names.len());
+    for name in names {
+        let canonical = normalize_hostname(name).unwrap_or_else(|_| name.to_string());
+        let domain = domain_repo
+            .find_by_name(&canonical)
+            .await
+            .map_err(|e| anyhow::anyhow!("Database error: {}", e))?
+            .with_context(|| format!("Domain not found: {}", name))?;
+
+        resolved.push((domain.id, domain.domain));
+    }
+
+    Ok(Some(resolved))
+}
+
+pub(crate) fn format_domains(domains: Option<&[(i64, String)]>) -> String {
+    match domains {
+        None => "any".to_string(),
+        Some(domains) => domains
+            .iter()
+            .map(|(_, name)| name.as_str())
+            .collect::<Vec<_>>()
+            .join(", "),
+    }
+}
+
+fn format_limits(limits: &TokenLimits) -> String {
+    let rate = limits
+        .requests_per_minute
+        .map(|v| format!("{} req/min", v))
+        .unwrap_or_else(|| "default req/min".to_string());
+    let quota = limits
+        .daily_link_quota
+        .map(|v| format!("{} links/day", v))
+        .unwrap_or_else(|| "unlimited links/day".to_string());
+
+    format!("{}, {}", rate, quota)
+}
+
+fn expiry_from_days(days: i64) -> Result<DateTime<Utc>> {
+    if !(1..=3650).contains(&days) {
+        anyhow::bail!("Token lifetime must be between 1 and 3650 days");
+    }
+
+    Ok(Utc::now() + Duration::days(days))
+}
+
+fn format_expiry(expires_at: Option<DateTime<Utc>>) -> String {
+    expires_at
+        .map(format_time)
+        .unwrap_or_else(|| "never".to_string())
+}
+
+fn parse_scope(value: &str) -> Result<Scope, String> {
+    value.parse()
+}
+
+fn format_scopes(scopes: &[Scope]) -> String {
+    if scopes.is_empty() {
+        return "-".to_string();
+    }
+
+    scopes
+        .iter()
+        .map(Scope::as_str)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -0,0 +1,348 @@
+//! Команды `admin user`: пользователи дашборда
+
+use url_shortener::application::services::UserService;
+use url_shortener::domain::entities::{Role, User};
+use url_shortener::domain::repositories::{DomainRepository, SessionRepository};
+use url_shortener::infrastructure::persistence::{
+    PgDomainRepository, PgSessionRepository, PgUserRepository,
+};
+
+use anyhow::Result;
+use clap::Subcommand;
+use colored::*;
+use dialoguer::{Confirm, Password};
+use sqlx::PgPool;
+use std::io::BufRead;
+use std::sync::Arc;
+
+use crate::format_time;
+use crate::tokens::{format_domains, resolve_domains};
+
+#[derive(Subcommand)]
+pub(crate) enum UserAction {
+    /// Create a new user
+    Create {
+        /// Login name (lowercase letters, digits, '.', '_' or '-')
+        username: String,
+
+        /// Role: admin, editor or viewer
+        #[arg(short, long, value_parser = parse_role)]
+        role: Role,
+
+        /// Domain the editor may create links on, repeatable
+        #[arg(short, long = "domain")]
+        domains: Vec<String>,
+
+        /// Read the password from stdin instead of prompting
+        #[arg(long)]
+        password_stdin: bool,
+    },
+
+    /// List all users
+    List,
+
+    /// Set a new password and sign the user out of the dashboard
+    ResetPassword {
+        username: String,
+
+        /// Read the password from stdin instead of prompting
+        #[arg(long)]
+        password_stdin: bool,
+    },
+
+    /// Change role and assigned domains
+    SetRole {
+        username: String,
+
+        /// Role: admin, editor or viewer
+        #[arg(value_parser = parse_role)]
+        role: Role,
+
+        /// Domain the editor may create links on, repeatable
+        #[arg(short, long = "domain")]
+        domains: Vec<String>,
+    },
+
+    /// Disable a user: blocks login, sessions and the user's tokens
+    Disable { username: String },
+
+    /// Enable a previously disabled user
+    Enable { username: String },
+}
+
+pub(crate) async fn handle_user_action(action: UserAction, pool: &PgPool) -> Result<()> {
+    let pool_arc = Arc::new(pool.clone());
+    let service = UserService::new(Arc::new(PgUserRepository::new(pool_arc.clone())));
+    let domain_repo = PgDomainRepository::new(pool_arc.clone());
+    let session_repo = PgSessionRepository::new(pool_arc);
+
+    match action {
+        UserAction::Create {
+            username,
+            role,
+            domains,
+            password_stdin,
+        } => {
+            println!("{}", "👤 Create User".bright_blue().bold());
+            println!();
+
+            let domains = resolve_user_domains(&domain_repo, role, &domains).await?;
+            let password = read_password(password_stdin)?;
+
+            let user = service
+                .create_user(
+                    &username,
+                    &password,
+                    role,
+                    domains.iter().map(|d| d.0).collect(),
+                )
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to create user: {}", e))?;
+
+            println!("  Username: {}", user.username.cyan());
+            println!("  Role:     {}", user.role.to_string().bright_white());
+            if role == Role::Editor {
+                println!("  Domains:  {}", format_domains(Some(&domains)));
+            }
+            println!();
+            println!("{}", "✅ User created successfully!".green().bold());
+            println!();
+        }
+        UserAction::List => {
+            list_users(&service, &domain_repo).await?;
+        }
+        UserAction::ResetPassword {
+            username,
+            password_stdin,
+        } => {
+            println!("{}", "🔑 Reset Password".bright_blue().bold());
+            println!();
+
+            let user = get_user(&service, &username).await?;
+            let password = read_password(password_stdin)?;
+
+            service
+                .reset_password(&user, &password)
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to reset password: {}", e))?;
+            session_repo
+                .revoke_for_user(user.id)
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to revoke sessions: {}", e))?;
+
+            println!("  User: {}", user.username.cyan());
+            println!();
+            println!(
+                "{}",
+                "✅ Password updated, dashboard sessions revoked"
+                    .green()
+                    .bold()
+            );
+            println!();
+        }
+        UserAction::SetRole {
+            username,
+            role,
+            domains,
+        } => {
+            let user = get_user(&service, &username).await?;
+            let domains = resolve_user_domains(&domain_repo, role, &domains).await?;
+
+            service
+                .set_role(&user, role, domains.iter().map(|d| d.0).collect())
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to update role: {}", e))?;
+
+            println!("  User:   {}", user.username.cyan());
+            println!("  Before: {}", user.role.to_string().bright_black());
+            println!("  After:  {}", role.to_string().bright_white());
+            if role == Role::Editor {
+                println!("  Domains: {}", format_domains(Some(&domains)));
+            }
+            println!();
+            println!("{}", "✅ Role updated".green().bold());
+            println!();
+        }
+        UserAction::Disable { username } => {
+            let user = get_user(&service, &username).await?;
+            if !user.is_active() {
+                println!("{}", "⚠️  This user is already disabled".yellow());
+                return Ok(());
+            }
+
+            let confirmed = Confirm::new()
+                .with_prompt(format!(
+                    "Disable {}? Their sessions and tokens stop working",
+                    user.username
+                ))
+                .default(false)
+                .interact()?;
+
+            if !confirmed {
+                println!("{}", "❌ Cancelled".red());
+                return Ok(());
+            }
+
+            service
+                .set_disabled(&user, true)
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to disable user: {}", e))?;
+            session_repo
+                .revoke_for_user(user.id)
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to revoke sessions: {}", e))?;
+
+            println!("{}", "✅ User disabled".green().bold());
+        }
+        UserAction::Enable { username } => {
+            let user = get_user(&service, &username).await?;
+
+            service
+                .set_disabled(&user, false)
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to enable user: {}", e))?;
+
+            println!("{}", "✅ User enabled".green().bold());
+        }
+    }
+
+    Ok(())
+}
+
+async fn list_users(
+    service: &UserService<PgUserRepository>,
+    domain_repo: &PgDomainRepository,
+) -> Result<()> {
+    println!("{}", "👥 Users".bright_blue().bold());
+    println!();
+
+    let users = service
+        .list_users()
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to list users: {}", e))?;
+
+    let domains = domain_repo
+        .list(false)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to list domains: {}", e))?;
+
+    if users.is_empty() {
+        println!("{}", "  No users found".yellow());
+        println!();
+        println!(
+            "  Create one with: {} admin user create <username> --role admin",
+            "cargo run --bin".bright_cyan()
+        );
+        return Ok(());
+    }
+
+    println!(
+        "  {:<4} {:<24} {:<8} {:<17} {:<17} {:<9} {}",
+        "ID".bright_white().bold(),
+        "Username".bright_white().bold(),
+        "Role".bright_white().bold(),
+        "Created".bright_white().bold(),
+        "Last login".bright_white().bold(),
+        "Status".bright_white().bold(),
+        "Domains".bright_white().bold()
+    );
+    println!("  {}", "─".repeat(100).bright_black());
+
+    for user in &users {
+        let status = if user.is_active() {
+            "ACTIVE".green()
+        } else {
+            "DISABLED".red()
+        };
+
+        let last_login = user
+            .last_login_at
+            .map(format_time)
+            .unwrap_or_else(|| "never".to_string());
+
+        let domain_names = match user.allowed_domain_ids() {
+            None => "any".to_string(),
+            Some([]) => "-".to_string(),
+            Some(ids) => ids
+                .iter()
+                .map(|id| {
+                    domains
+                        .iter()
+                        .find(|d| d.id == *id)
+                        .map(|d| d.domain.clone())
+                        .unwrap_or_else(|| format!("#{}", id))
+                })
+                .collect::<Vec<_>>()
+                .join(", "),
+        };
+
+        println!(
+            "  {:<4} {:<24} {:<8} {:<17} {:<17} {:<9} {}",
+            user.id.to_string().bright_black(),
+            user.username.cyan(),
+            user.role.as_str(),
+            format_time(user.created_at).bright_black(),
+            last_login.bright_black(),
+            status,
+            domain_names
+        );
+    }
+
+    println!();
+    println!("  Total: {}", users.len().to_string().bright_white().bold());
+    println!();
+
+    Ok(())
+}
+
+pub(crate) async fn find_user(pool: &PgPool, username: &str) -> Result<User> {
+    let service = UserService::new(Arc::new(PgUserRepository::new(Arc::new(pool.clone()))));
+    get_user(&service, username).await
+}
+
+async fn get_user(service: &UserService<PgUserRepository>, username: &str) -> Result<User> {
+    service
+        .get_user(username)
+        .await
+        .map_err(|e| anyhow::anyhow!("{}: {}", username, e))
+}
+
+/// Домены назначаются только редактору
+async fn resolve_user_domains(
+    domain_repo: &PgDomainRepository,
+    role: Role,
+    names: &[String],
+) -> Result<Vec<(i64, String)>> {
+    if role != Role::Editor {
+        if !names.is_empty() {
+            anyhow::bail!("--domain applies only to the editor role");
+        }
+        return Ok(Vec::new());
+    }
+
+    if names.is_empty() {
+        anyhow::bail!("Editor needs at least one --domain");
+    }
+
+    Ok(resolve_domains(domain_repo, names)
+        .await?
+        .unwrap_or_default())
+}
+
+/// Пароль из stdin (для скриптов) или интерактивно с подтверждением
+fn read_password(from_stdin: bool) -> Result<String> {
+    if from_stdin {
+        let mut line = String::new();
+        std::io::stdin().lock().read_line(&mut line)?;
+        return Ok(line.trim_end_matches(['\r', '\n']).to_string());
+    }
+
+    Ok(Password::new()
+        .with_prompt("Password")
+        .with_confirmation("Repeat password", "Passwords don't match")
+        .interact()?)
+}
+
+fn parse_role(value: &str) -> Result<Role, String> {
+    value.parse()
+}
@@ -1,5 +1,11 @@
 use anyhow::{Context, Result};
 use std::env;
+use std::str::FromStr;
+
+use crate::domain::entities::CodeStrategy;
+use crate::utils::client_ip::TrustedProxies;
+use crate::utils::code_generator::DEFAULT_RESERVED_CODES;
+use crate::utils::hostname::normalize_hostname;
 
 #[derive(Debug, Clone)]
 pub struct Config {
@@ -8,6 +14,61 @@ pub struct Config {
     pub listen_addr: String,
     pub log_level: String,
     pub click_queue_capacity: usize,
+
+    /// Reverse proxy, которым доверяем заголовки с IP клиента и Host
+    /// (какой заголовок выставляет прокси - FORWARDED_HEADER)
+    pub trusted_proxies: TrustedProxies,
+
+    // Rate limiting (запросов в минуту / burst)
+    pub rate_limit_token_per_minute: u32,
+    pub rate_limit_token_burst: u32,
+    pub rate_limit_anonymous_per_minute: u32,
+    pub rate_limit_anonymous_burst: u32,
+    pub rate_limit_dashboard_per_minute: u32,
+    pub rate_limit_dashboard_burst: u32,
+    /// Неудачные попытки аутентификации с одного IP (и входа под одним именем)
+    pub rate_limit_auth_failures_per_minute: u32,
+    pub rate_limit_auth_failures_burst: u32,
+    /// Хранить состояние лимитов в Redis (общие лимиты для всех инстансов)
+    pub rate_limit_redis: bool,
+
+    /// Серверный секрет (pepper) для HMAC хэшей API токенов
+    pub token_pepper: String,
+
+    // Сессии дашборда
+    /// Ключ подписи cookie (если не задан - случайный при каждом запуске)
+    pub session_secret: Option<String>,
+    pub session_ttl_hours: i64,
+    pub session_cookie_secure: bool,
+
+    /// Домены, на которых разрешено анонимное создание ссылок (пусто - нигде)
+    pub anonymous_shorten_domains: Vec<String>,
+
+    // Получение title/OpenGraph целевых страниц
+    pub metadata_fetch_enabled: bool,
+    pub metadata_fetch_timeout_ms: u64,
+    pub metadata_fetch_max_bytes: usize,
+    pub metadata_fetch_max_redirects: usize,
+    pub metadata_fetch_concurrency: usize,
+    pub metadata_fetch_allow_private: bool,
+    pub metadata_queue_capacity: usize,
+
+    // Проверка владения доменом
+    pub domain_verification_timeout_ms: u64,
+    pub domain_verification_allow_private: bool,
+
+    /// Сколько секунд помнить, к какому домену относится хост запроса (0 - не кэшировать)
+    pub domain_cache_ttl_secs: u64,
+
+    // Генерация кодов ссылок
+    /// Стратегия для доменов без своей (random, sequential, words)
+    pub code_strategy: CodeStrategy,
+    /// Ключ перестановки последовательных кодов (если не задан - из TOKEN_PEPPER)
+    pub code_sequence_secret: Option<String>,
+    /// Зарезервированные коды сверх маршрутов приложения
+    pub reserved_codes: Vec<String>,
+    /// Сколько секунд держать в памяти коды, зарезервированные через CLI (0 - не кэшировать)
+    pub reserved_codes_cache_ttl_secs: u64,
 }
 
 impl Config {
@@ -22,6 +83,66 @@ impl Config {
                 .ok()
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(10_000),
+            trusted_proxies: env::var("TRUSTED_PROXIES")
+                .unwrap_or_default()
+                .parse::<TrustedProxies>()
+                .context("Invalid TRUSTED_PROXIES")?
+                .with_header(
+                    env::var("FORWARDED_HEADER")
+                        .ok()
+                        .filter(|s| !s.is_empty())
+                        .map(|s| s.parse())
+                        .transpose()
+                        .map_err(anyhow::Error::msg)
+                        .context("Invalid FORWARDED_HEADER")?
+                        .unwrap_or_default(),
+                ),
+            rate_limit_token_per_minute: env_parse("RATE_LIMIT_TOKEN_PER_MINUTE", 120),
+            rate_limit_token_burst: env_parse("RATE_LIMIT_TOKEN_BURST", 20),
+            rate_limit_anonymous_per_minute: env_parse("RATE_LIMIT_ANONYMOUS_PER_MINUTE", 120),
+            rate_limit_anonymous_burst: env_parse("RATE_LIMIT_ANONYMOUS_BURST", 100),
+            rate_limit_dashboard_per_minute: env_parse("RATE_LIMIT_DASHBOARD_PER_MINUTE", 60),
+            rate_limit_dashboard_burst: env_parse("RATE_LIMIT_DASHBOARD_BURST", 10),
+            rate_limit_auth_failures_per_minute: env_parse(
+                "RATE_LIMIT_AUTH_FAILURES_PER_MINUTE",
+                5,
+            ),
+            rate_limit_auth_failures_burst: env_parse("RATE_LIMIT_AUTH_FAILURES_BURST", 10),
+            rate_limit_redis: env_parse("RATE_LIMIT_REDIS", true),
+            token_pepper: env::var("TOKEN_PEPPER").context("TOKEN_PEPPER must be set")?,
+            session_secret: env::var("SESSION_SECRET").ok().filter(|s| !s.is_empty()),
+            session_ttl_hours: env_parse("SESSION_TTL_HOURS", 12),
+            session_cookie_secure: env_parse("SESSION_COOKIE_SECURE", true),
+            anonymous_shorten_domains: env_list("ANONYMOUS_SHORTEN_DOMAINS")
+                .into_iter()
+                .map(|domain| normalize_hostname(&domain).unwrap_or(domain))
+                .collect(),
+            metadata_fetch_enabled: env_parse("METADATA_FETCH_ENABLED", true),
+            metadata_fetch_timeout_ms: env_parse("METADATA_FETCH_TIMEOUT_MS", 5_000),
+            metadata_fetch_max_bytes: env_parse("METADATA_FETCH_MAX_BYTES", 512 * 1024),
+            metadata_fetch_max_redirects: env_parse("METADATA_FETCH_MAX_REDIRECTS", 3),
+            metadata_fetch_concurrency: env_parse("METADATA_FETCH_CONCURRENCY", 4),
+            metadata_fetch_allow_private: env_parse("METADATA_FETCH_ALLOW_PRIVATE", false),
+            metadata_queue_capacity: env_parse("METADATA_QUEUE_CAPACITY", 1_000),
+            domain_verification_timeout_ms: env_parse("DOMAIN_VERIFICATION_TIMEOUT_MS", 5_000),
+            domain_verification_allow_private: env_parse(
+                "DOMAIN_VERIFICATION_ALLOW_PRIVATE",
+                false,
+            ),
+            domain_cache_ttl_secs: env_parse("DOMAIN_CACHE_TTL_SECS", 60),
+            code_strategy: env::var("CODE_STRATEGY")
+                .ok()
+                .filter(|s| !s.is_empty())
+                .map(|s| s.parse())
+                .transpose()
+                .map_err(anyhow::Error::msg)
+                .context("Invalid CODE_STRATEGY")?
+                .unwrap_or_default(),
+            code_sequence_secret: env::var("CODE_SEQUENCE_SECRET")
+                .ok()
+                .filter(|s| !s.is_empty()),
+            reserved_codes: reserved_codes_from_env(),
+            reserved_codes_cache_ttl_secs: env_parse("RESERVED_CODES_CACHE_TTL_SECS", 60),
         })
     }
 
@@ -31,15 +152,144 @@ impl Config {
             anyhow::bail!("CLICK_QUEUE_CAPACITY must be at least 100");
         }
 
+        for (name, value) in [
+            (
+                "RATE_LIMIT_TOKEN_PER_MINUTE",
+                self.rate_limit_token_per_minute,
+            ),
+            ("RATE_LIMIT_TOKEN_BURST", self.rate_limit_token_burst),
+            (
+                "RATE_LIMIT_ANONYMOUS_PER_MINUTE",
+                self.rate_limit_anonymous_per_minute,
+            ),
+            (
+                "RATE_LIMIT_ANONYMOUS_BURST",
+                self.rate_limit_anonymous_burst,
+            ),
+            (
+                "RATE_LIMIT_DASHBOARD_PER_MINUTE",
+                self.rate_limit_dashboard_per_minute,
+            ),
+            (
+                "RATE_LIMIT_DASHBOARD_BURST",
+                self.rate_limit_dashboard_burst,
+            ),
+            (
+                "RATE_LIMIT_AUTH_FAILURES_PER_MINUTE",
+                self.rate_limit_auth_failures_per_minute,
+            ),
+            (
+                "RATE_LIMIT_AUTH_FAILURES_BURST",
+                self.rate_limit_auth_failures_burst,
+            ),
+        ] {
+            if value == 0 {
+                anyhow::bail!("{} must be at least 1", name);
+            }
+        }
+
+        if self.token_pepper.len() < 32 {
+            anyhow::bail!("TOKEN_PEPPER must be at least 32 characters");
+        }
+
+        if self
+            .session_secret
+            .as_ref()
+            .is_some_and(|secret| secret.len() < 32)
+        {
+            anyhow::bail!("SESSION_SECRET must be at least 32 characters");
+        }
+
+        if !(1..=720).contains(&self.session_ttl_hours) {
+            anyhow::bail!("SESSION_TTL_HOURS must be between 1 and 720");
+        }
+
+        if self.metadata_fetch_enabled {
+            if self.metadata_fetch_timeout_ms == 0 || self.metadata_fetch_timeout_ms > 60_000 {
+                anyhow::bail!("METADATA_FETCH_TIMEOUT_MS must be between 1 and 60000");
+            }
+
+            if self.metadata_fetch_max_bytes < 1024 {
+                anyhow::bail!("METADATA_FETCH_MAX_BYTES must be at least 1024");
+            }
+
+            if self.metadata_fetch_concurrency == 0 {
+                anyhow::bail!("METADATA_FETCH_CONCURRENCY must be at least 1");
+            }
+
+            if self.metadata_queue_capacity < 100 {
+                anyhow::bail!("METADATA_QUEUE_CAPACITY must be at least 100");
+            }
+        }
+
+        if self.domain_verification_timeout_ms == 0 || self.domain_verification_timeout_ms > 60_000
+        {
+            anyhow::bail!("DOMAIN_VERIFICATION_TIMEOUT_MS must be between 1 and 60000");
+        }
+
+        if self.domain_cache_ttl_secs > 3600 {
+            anyhow::bail!("DOMAIN_CACHE_TTL_SECS must be at most 3600");
+        }
+
+        if self.reserved_codes_cache_ttl_secs > 3600 {
+            anyhow::bail!("RESERVED_CODES_CACHE_TTL_SECS must be at most 3600");
+        }
+
+        if self
+            .code_sequence_secret
+            .as_ref()
+            .is_some_and(|secret| secret.len() < 32)
+        {
+            anyhow::bail!("CODE_SEQUENCE_SECRET must be at least 32 characters");
+        }
+
         Ok(())
     }
 
+    /// Разрешено ли создавать ссылки без токена на домене
+    pub fn allows_anonymous_shorten(&self, domain: &str) -> bool {
+        self.anonymous_shorten_domains
+            .iter()
+            .any(|d| d.eq_ignore_ascii_case(domain))
+    }
+
     /// Проверка, включен ли кэш
     pub fn is_cache_enabled(&self) -> bool {
         self.redis_url.is_some()
     }
 }
 
+/// Чтение необязательной переменной окружения с значением по умолчанию
+fn env_parse<T: FromStr>(key: &str, default: T) -> T {
+    env::var(key)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default)
+}
+
+/// Список через запятую (значения приводятся к нижнему регистру)
+fn env_list(key: &str) -> Vec<String> {
+    env::var(key)
+        .map(|v| {
+            v.split(',')
+                .map(|item| item.trim().to_lowercase())
+                .filter(|item| !item.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// RESERVED_CODES (без переменной - `DEFAULT_RESERVED_CODES`, пустая - ничего)
+pub fn reserved_codes_from_env() -> Vec<String> {
+    match env::var("RESERVED_CODES") {
+        Ok(_) => env_list("RESERVED_CODES"),
+        Err(_) => DEFAULT_RESERVED_CODES
+            .iter()
+            .map(|code| code.to_string())
+            .collect(),
+    }
+}
+
 pub fn load_from_env() -> Result<Config> {
     let config = Config::from_env()?;
     config.validate()?;
@@ -0,0 +1,36 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use crate::domain::entities::{CodePolicy, CodeStrategy};
+use crate::error::AppError;
+
+/// Генератор кодов коротких ссылок. Занятые в домене коды отсеивает
+/// уникальный индекс при вставке (`LinkService` повторяет попытку).
+#[async_trait]
+pub trait CodeGenerator: Send + Sync {
+    async fn generate(&self, domain_id: i64, policy: &CodePolicy) -> Result<String, AppError>;
+}
+
+/// Генераторы всех стратегий и стратегия по умолчанию (CODE_STRATEGY)
+#[derive(Clone)]
+pub struct CodeGenerators {
+    pub default_strategy: CodeStrategy,
+    pub random: Arc<dyn CodeGenerator>,
+    pub sequential: Arc<dyn CodeGenerator>,
+    pub words: Arc<dyn CodeGenerator>,
+}
+
+impl CodeGenerators {
+    /// Стратегия домена или глобальная
+    pub fn strategy(&self, policy: &CodePolicy) -> CodeStrategy {
+        policy.strategy.unwrap_or(self.default_strategy)
+    }
+
+    pub fn for_policy(&self, policy: &CodePolicy) -> &dyn CodeGenerator {
+        match self.strategy(policy) {
+            CodeStrategy::Random => self.random.as_ref(),
+            CodeStrategy::Sequential => self.sequential.as_ref(),
+            CodeStrategy::Words => self.words.as_ref(),
+        }
+    }
+}
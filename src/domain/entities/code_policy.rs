@@ -0,0 +1,162 @@
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+/// Допустимая длина сгенерированного кода
+pub const GENERATED_CODE_LENGTH: RangeInclusive<usize> = 4..=32;
+/// Допустимые границы длины кастомного кода (шире политика домена быть не может)
+pub const CUSTOM_CODE_LENGTH: RangeInclusive<usize> = 3..=64;
+
+/// Алфавит сгенерированных кодов
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CodeAlphabet {
+    /// 0-9, A-Z, a-z
+    #[default]
+    Base62,
+    /// 0-9, a-z (удобно диктовать, не зависит от регистра)
+    Lowercase,
+    /// Base62 без похожих символов: 0/O/o, 1/l/I
+    NoLookalike,
+}
+
+impl CodeAlphabet {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Base62 => "base62",
+            Self::Lowercase => "lowercase",
+            Self::NoLookalike => "no_lookalike",
+        }
+    }
+
+    pub fn chars(&self) -> &'static [u8] {
+        match self {
+            Self::Base62 => b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz",
+            Self::Lowercase => b"0123456789abcdefghijklmnopqrstuvwxyz",
+            Self::NoLookalike => b"23456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnpqrstuvwxyz",
+        }
+    }
+}
+
+impl fmt::Display for CodeAlphabet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for CodeAlphabet {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "base62" => Ok(Self::Base62),
+            "lowercase" => Ok(Self::Lowercase),
+            "no_lookalike" => Ok(Self::NoLookalike),
+            other => Err(format!(
+                "Unknown code alphabet '{}' (base62, lowercase, no_lookalike)",
+                other
+            )),
+        }
+    }
+}
+
+/// Способ генерации кодов
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CodeStrategy {
+    /// Случайный код (с повторной попыткой при коллизии)
+    #[default]
+    Random,
+    /// Номер из последовательности Postgres, переставленный секретным ключом
+    Sequential,
+    /// Читаемый код из слов: `calm-otter-4821`
+    Words,
+}
+
+impl CodeStrategy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Random => "random",
+            Self::Sequential => "sequential",
+            Self::Words => "words",
+        }
+    }
+}
+
+impl fmt::Display for CodeStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for CodeStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "random" => Ok(Self::Random),
+            "sequential" => Ok(Self::Sequential),
+            "words" => Ok(Self::Words),
+            other => Err(format!(
+                "Unknown code strategy '{}' (random, sequential, words)",
+                other
+            )),
+        }
+    }
+}
+
+/// Правила кодов ссылок домена
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodePolicy {
+    /// Способ генерации (None - глобальный CODE_STRATEGY)
+    pub strategy: Option<CodeStrategy>,
+    /// Длина сгенерированного кода
+    pub length: usize,
+    pub alphabet: CodeAlphabet,
+    /// Границы длины кастомного кода
+    pub custom_min_length: usize,
+    pub custom_max_length: usize,
+}
+
+impl Default for CodePolicy {
+    fn default() -> Self {
+        Self {
+            strategy: None,
+            length: 12,
+            alphabet: CodeAlphabet::Base62,
+            custom_min_length: 8,
+            custom_max_length: 15,
+        }
+    }
+}
+
+impl CodePolicy {
+    /// Проверка настроек политики
+    pub fn validate(&self) -> Result<(), String> {
+        if !GENERATED_CODE_LENGTH.contains(&self.length) {
+            return Err(format!(
+                "Generated code length must be {}-{}",
+                GENERATED_CODE_LENGTH.start(),
+                GENERATED_CODE_LENGTH.end()
+            ));
+        }
+
+        if !CUSTOM_CODE_LENGTH.contains(&self.custom_min_length)
+            || !CUSTOM_CODE_LENGTH.contains(&self.custom_max_length)
+        {
+            return Err(format!(
+                "Custom code length bounds must be within {}-{}",
+                CUSTOM_CODE_LENGTH.start(),
+                CUSTOM_CODE_LENGTH.end()
+            ));
+        }
+
+        if self.custom_min_length > self.custom_max_length {
+            return Err("Custom code minimum length exceeds maximum".to_string());
+        }
+
+        Ok(())
+    }
+
+    pub fn custom_length(&self) -> RangeInclusive<usize> {
+        self.custom_min_length..=self.custom_max_length
+    }
+}
@@ -1,5 +1,18 @@
 use chrono::{DateTime, Utc};
 
+use super::{CodeAlphabet, CodePolicy, CodeStrategy};
+use std::fmt;
+use std::str::FromStr;
+
+/// Имя TXT записи: `_url-shortener.<domain>`
+pub const VERIFICATION_DNS_PREFIX: &str = "_url-shortener";
+/// Значение TXT записи: `url-shortener-verification=<token>`
+pub const VERIFICATION_DNS_VALUE_PREFIX: &str = "url-shortener-verification=";
+/// Путь HTTP challenge: `/.well-known/url-shortener/<token>` (тело ответа - токен)
+pub const VERIFICATION_HTTP_PATH: &str = "/.well-known/url-shortener/";
+/// Префикс wildcard алиаса (`*.go.example.com`)
+pub const WILDCARD_PREFIX: &str = "*.";
+
 #[derive(Debug, Clone)]
 pub struct Domain {
     pub id: i64,
@@ -7,41 +20,211 @@ pub struct Domain {
     pub is_default: bool,
     pub is_active: bool,
     pub description: Option<String>,
+    pub branding: DomainBranding,
+    pub redirects: DomainRedirects,
+    pub verification: DomainVerification,
+    /// Длина и алфавит генерируемых кодов, границы кастомных
+    pub code_policy: CodePolicy,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
-impl Domain {
-    pub fn new(
-        id: i64,
-        domain: String,
-        is_default: bool,
-        is_active: bool,
-        description: Option<String>,
-        created_at: DateTime<Utc>,
-        updated_at: DateTime<Utc>,
-    ) -> Self {
-        Self {
-            id,
-            domain,
-            is_default,
-            is_active,
-            description,
-            created_at,
-            updated_at,
+/// Оформление страниц «ссылка не найдена» домена
+#[derive(Debug, Clone, Default)]
+pub struct DomainBranding {
+    /// Логотип бренда
+    pub logo_url: Option<String>,
+    /// Текст вместо стандартного сообщения
+    pub not_found_message: Option<String>,
+    /// Сайт бренда, на который предлагается перейти
+    pub fallback_url: Option<String>,
+}
+
+/// Редиректы домена помимо коротких ссылок
+#[derive(Debug, Clone, Default)]
+pub struct DomainRedirects {
+    /// Цель для `GET /` (например, сайт бренда)
+    pub root_url: Option<String>,
+    /// Цель для несуществующих кодов вместо страницы 404
+    pub not_found_url: Option<String>,
+}
+
+/// Состояние проверки владения доменом
+#[derive(Debug, Clone, Default)]
+pub struct DomainVerification {
+    /// Токен challenge (для подтверждённых по DNS/HTTP остаётся в истории)
+    pub token: Option<String>,
+    pub verified_at: Option<DateTime<Utc>>,
+    pub method: Option<VerificationMethod>,
+    /// Время последней проверки и её ошибка
+    pub checked_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+}
+
+impl DomainVerification {
+    pub fn is_verified(&self) -> bool {
+        self.verified_at.is_some()
+    }
+
+    /// Инструкции для проверки владения `host`, пока она не пройдена
+    pub fn challenge(&self, host: &str) -> Option<VerificationChallenge> {
+        if self.is_verified() {
+            return None;
+        }
+
+        let token = self.token.clone()?;
+        Some(VerificationChallenge {
+            dns_name: format!("{}.{}", VERIFICATION_DNS_PREFIX, host),
+            dns_value: format!("{}{}", VERIFICATION_DNS_VALUE_PREFIX, token),
+            http_url: format!("http://{}{}{}", host, VERIFICATION_HTTP_PATH, token),
+            token,
+        })
+    }
+}
+
+/// Способ, которым подтверждён домен
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerificationMethod {
+    Dns,
+    Http,
+    /// Администратором через CLI или до появления проверки
+    Manual,
+}
+
+impl VerificationMethod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Dns => "dns",
+            Self::Http => "http",
+            Self::Manual => "manual",
+        }
+    }
+}
+
+impl fmt::Display for VerificationMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for VerificationMethod {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "dns" => Ok(Self::Dns),
+            "http" => Ok(Self::Http),
+            "manual" => Ok(Self::Manual),
+            other => Err(format!("Unknown verification method '{}'", other)),
         }
     }
 }
 
+/// Что нужно опубликовать владельцу домена для проверки
+#[derive(Debug, Clone)]
+pub struct VerificationChallenge {
+    pub dns_name: String,
+    pub dns_value: String,
+    pub http_url: String,
+    pub token: String,
+}
+
+impl Domain {
+    /// Инструкции для неподтверждённого домена
+    pub fn verification_challenge(&self) -> Option<VerificationChallenge> {
+        self.verification.challenge(&self.domain)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct NewDomain {
     pub domain: String,
     pub is_default: bool,
     pub description: Option<String>,
+    /// Токен challenge; None - домен подтверждён администратором и сразу активен
+    pub verification_token: Option<String>,
 }
 
+/// Дополнительное имя домена: алиас (`www.s.example.com`) или wildcard
+/// (`*.go.example.com`), ведущее в пространство ссылок основного домена.
+/// Обслуживается только после проверки владения.
+#[derive(Debug, Clone)]
+pub struct DomainAlias {
+    pub id: i64,
+    pub domain_id: i64,
+    pub host: String,
+    pub verification: DomainVerification,
+    pub created_at: DateTime<Utc>,
+}
+
+impl DomainAlias {
+    pub fn is_wildcard(&self) -> bool {
+        self.host.starts_with(WILDCARD_PREFIX)
+    }
+
+    /// Имя, владение которым проверяется: сам алиас или суффикс wildcard
+    pub fn verification_host(&self) -> &str {
+        self.host
+            .strip_prefix(WILDCARD_PREFIX)
+            .unwrap_or(&self.host)
+    }
+
+    /// Инструкции для неподтверждённого алиаса
+    pub fn verification_challenge(&self) -> Option<VerificationChallenge> {
+        self.verification.challenge(self.verification_host())
+    }
+}
+
+/// Wildcard шаблоны, под которые попадает хост, от самого точного:
+/// `a.b.example.com` -> `*.b.example.com`, `*.example.com`
+pub fn wildcard_candidates(host: &str) -> Vec<String> {
+    let mut candidates = Vec::new();
+    let mut rest = host;
+
+    // Суффикс без точки (зона верхнего уровня) wildcard не покрывает
+    while let Some((_, suffix)) = rest.split_once('.') {
+        if !suffix.contains('.') {
+            break;
+        }
+        candidates.push(format!("{}{}", WILDCARD_PREFIX, suffix));
+        rest = suffix;
+    }
+
+    candidates
+}
+
+/// Частичное обновление домена. Для полей оформления и редиректов
+/// пустая строка сбрасывает значение.
 #[derive(Debug, Clone, Default)]
 pub struct UpdateDomain {
     pub is_active: Option<bool>,
     pub description: Option<String>,
+    pub logo_url: Option<String>,
+    pub not_found_message: Option<String>,
+    pub fallback_url: Option<String>,
+    pub root_redirect_url: Option<String>,
+    pub not_found_redirect_url: Option<String>,
+    pub code_length: Option<usize>,
+    pub code_alphabet: Option<CodeAlphabet>,
+    pub custom_code_min_length: Option<usize>,
+    pub custom_code_max_length: Option<usize>,
+    /// Some(None) - вернуть глобальную стратегию CODE_STRATEGY
+    pub code_strategy: Option<Option<CodeStrategy>>,
+}
+
+impl UpdateDomain {
+    /// Политика кодов после применения обновления
+    pub fn apply_code_policy(&self, current: &CodePolicy) -> CodePolicy {
+        CodePolicy {
+            strategy: self.code_strategy.unwrap_or(current.strategy),
+            length: self.code_length.unwrap_or(current.length),
+            alphabet: self.code_alphabet.unwrap_or(current.alphabet),
+            custom_min_length: self
+                .custom_code_min_length
+                .unwrap_or(current.custom_min_length),
+            custom_max_length: self
+                .custom_code_max_length
+                .unwrap_or(current.custom_max_length),
+        }
+    }
 }
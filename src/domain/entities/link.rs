@@ -1,5 +1,19 @@
 use chrono::{DateTime, Utc};
 
+/// Описательные данные ссылки
+#[derive(Debug, Clone, Default)]
+pub struct LinkMetadata {
+    pub title: Option<String>,
+    pub notes: Option<String>,
+    /// ID API токена, которым создана ссылка
+    pub created_by: Option<i64>,
+    /// ID пользователя-владельца ссылки
+    pub owner_id: Option<i64>,
+    pub og_title: Option<String>,
+    pub og_description: Option<String>,
+    pub og_image: Option<String>,
+}
+
 #[derive(Debug, Clone)]
 pub struct Link {
     pub id: i64,
@@ -7,6 +21,7 @@ pub struct Link {
     pub long_url: String,
     pub domain: Option<String>,
     pub created_at: DateTime<Utc>,
+    pub metadata: LinkMetadata,
 }
 
 impl Link {
@@ -16,6 +31,7 @@ impl Link {
         long_url: String,
         domain: Option<String>,
         created_at: DateTime<Utc>,
+        metadata: LinkMetadata,
     ) -> Self {
         Self {
             id,
@@ -23,6 +39,7 @@ impl Link {
             long_url,
             domain,
             created_at,
+            metadata,
         }
     }
 }
@@ -32,4 +49,7 @@ pub struct NewLink {
     pub code: String,
     pub long_url: String,
     pub domain_id: i64,
+    pub metadata: LinkMetadata,
+    /// Нормализованные теги, сохраняются вместе со ссылкой
+    pub tags: Vec<String>,
 }
@@ -1,7 +1,25 @@
 pub mod click;
+pub mod code_policy;
 pub mod domain;
 pub mod link;
+pub mod page_metadata;
+pub mod principal;
+pub mod reserved_code;
+pub mod scope;
+pub mod user;
 
 pub use click::{Click, NewClick};
-pub use domain::{Domain, NewDomain, UpdateDomain};
-pub use link::{Link, NewLink};
+pub use code_policy::{
+    CUSTOM_CODE_LENGTH, CodeAlphabet, CodePolicy, CodeStrategy, GENERATED_CODE_LENGTH,
+};
+pub use domain::{
+    Domain, DomainAlias, DomainBranding, DomainRedirects, DomainVerification, NewDomain,
+    UpdateDomain, VERIFICATION_HTTP_PATH, VerificationChallenge, VerificationMethod,
+    WILDCARD_PREFIX, wildcard_candidates,
+};
+pub use link::{Link, LinkMetadata, NewLink};
+pub use page_metadata::PageMetadata;
+pub use principal::{Principal, PrincipalUser};
+pub use reserved_code::ReservedCode;
+pub use scope::Scope;
+pub use user::{NewUser, Role, User};
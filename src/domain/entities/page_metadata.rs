@@ -0,0 +1,17 @@
+/// Метаданные целевой страницы (title и OpenGraph)
+#[derive(Debug, Clone, Default)]
+pub struct PageMetadata {
+    pub title: Option<String>,
+    pub og_title: Option<String>,
+    pub og_description: Option<String>,
+    pub og_image: Option<String>,
+}
+
+impl PageMetadata {
+    pub fn is_empty(&self) -> bool {
+        self.title.is_none()
+            && self.og_title.is_none()
+            && self.og_description.is_none()
+            && self.og_image.is_none()
+    }
+}
@@ -0,0 +1,124 @@
+use serde_json::json;
+
+use super::{Domain, Role, Scope, User};
+use crate::domain::repositories::{ApiToken, TokenLimits};
+use crate::error::AppError;
+
+/// Аутентифицированный субъект запроса (прикрепляется к extensions)
+#[derive(Debug, Clone)]
+pub struct Principal {
+    /// Токен запроса (None - вход в дашборд по логину и паролю)
+    pub token_id: Option<i64>,
+    pub token_name: Option<String>,
+    /// Пользователь-владелец токена или сессии
+    pub user: Option<PrincipalUser>,
+    pub scopes: Vec<Scope>,
+    /// Домены для создания ссылок (None - без ограничений)
+    pub allowed_domain_ids: Option<Vec<i64>>,
+    pub limits: TokenLimits,
+}
+
+/// Пользователь, от имени которого выполняется запрос
+#[derive(Debug, Clone)]
+pub struct PrincipalUser {
+    pub id: i64,
+    pub username: String,
+    pub role: Role,
+}
+
+impl Principal {
+    /// Субъект по токену. Токен пользователя не даёт больше, чем роль пользователя:
+    /// права и домены пересекаются с доступными роли.
+    pub fn from_token(token: ApiToken, user: Option<&User>) -> Self {
+        let Some(user) = user else {
+            return Self {
+                token_id: Some(token.id),
+                token_name: Some(token.name),
+                user: None,
+                scopes: token.scopes,
+                allowed_domain_ids: token.allowed_domain_ids,
+                limits: token.limits,
+            };
+        };
+
+        let role_scopes = user.role.scopes();
+        let scopes = token
+            .scopes
+            .into_iter()
+            .filter(|scope| role_scopes.contains(scope))
+            .collect();
+
+        let allowed_domain_ids = match (token.allowed_domain_ids, user.allowed_domain_ids()) {
+            (Some(token_ids), Some(user_ids)) => Some(
+                token_ids
+                    .into_iter()
+                    .filter(|id| user_ids.contains(id))
+                    .collect(),
+            ),
+            (Some(token_ids), None) => Some(token_ids),
+            (None, user_ids) => user_ids.map(<[i64]>::to_vec),
+        };
+
+        Self {
+            token_id: Some(token.id),
+            token_name: Some(token.name),
+            user: Some(PrincipalUser::from(user)),
+            scopes,
+            allowed_domain_ids,
+            limits: token.limits,
+        }
+    }
+
+    /// Субъект сессии дашборда, открытой по логину и паролю
+    pub fn from_user(user: &User) -> Self {
+        Self {
+            token_id: None,
+            token_name: None,
+            user: Some(PrincipalUser::from(user)),
+            scopes: user.role.scopes().to_vec(),
+            allowed_domain_ids: user.allowed_domain_ids().map(<[i64]>::to_vec),
+            limits: TokenLimits::default(),
+        }
+    }
+
+    pub fn user_id(&self) -> Option<i64> {
+        self.user.as_ref().map(|user| user.id)
+    }
+
+    pub fn has_scope(&self, scope: Scope) -> bool {
+        self.scopes.contains(&scope)
+    }
+
+    /// Проверить наличие права, иначе 403
+    pub fn require_scope(&self, scope: Scope) -> Result<(), AppError> {
+        if self.has_scope(scope) {
+            return Ok(());
+        }
+
+        Err(AppError::forbidden(
+            "Insufficient scope",
+            json!({ "required_scope": scope.as_str() }),
+        ))
+    }
+
+    /// Проверить, что субъекту разрешено создавать ссылки на домене
+    pub fn require_domain(&self, domain: &Domain) -> Result<(), AppError> {
+        match &self.allowed_domain_ids {
+            Some(ids) if !ids.contains(&domain.id) => Err(AppError::forbidden(
+                "Not allowed to create links on this domain",
+                json!({ "domain": domain.domain }),
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl From<&User> for PrincipalUser {
+    fn from(user: &User) -> Self {
+        Self {
+            id: user.id,
+            username: user.username.clone(),
+            role: user.role,
+        }
+    }
+}
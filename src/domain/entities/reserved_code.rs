@@ -0,0 +1,11 @@
+use chrono::{DateTime, Utc};
+
+/// Код, зарезервированный через admin CLI
+#[derive(Debug, Clone)]
+pub struct ReservedCode {
+    pub id: i64,
+    pub code: String,
+    /// Домен, на котором действует резерв (None - на всех)
+    pub domain: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
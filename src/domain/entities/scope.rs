@@ -0,0 +1,57 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// Право доступа API токена
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Scope {
+    /// Создание ссылок
+    LinksWrite,
+    /// Чтение ссылок (QR коды и т.п.)
+    LinksRead,
+    /// Чтение статистики
+    StatsRead,
+    /// Управление доменами
+    DomainsAdmin,
+}
+
+impl Scope {
+    pub const ALL: [Scope; 4] = [
+        Scope::LinksWrite,
+        Scope::LinksRead,
+        Scope::StatsRead,
+        Scope::DomainsAdmin,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Scope::LinksWrite => "links:write",
+            Scope::LinksRead => "links:read",
+            Scope::StatsRead => "stats:read",
+            Scope::DomainsAdmin => "domains:admin",
+        }
+    }
+}
+
+impl fmt::Display for Scope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Scope {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Scope::ALL
+            .into_iter()
+            .find(|scope| scope.as_str() == s.trim())
+            .ok_or_else(|| {
+                let known: Vec<_> = Scope::ALL.iter().map(Scope::as_str).collect();
+                format!(
+                    "Unknown scope '{}', expected one of: {}",
+                    s,
+                    known.join(", ")
+                )
+            })
+    }
+}
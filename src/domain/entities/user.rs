@@ -0,0 +1,90 @@
+use chrono::{DateTime, Utc};
+use std::fmt;
+use std::str::FromStr;
+
+use super::Scope;
+
+/// Роль пользователя
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Role {
+    /// Полный доступ, включая управление доменами
+    Admin,
+    /// Создание ссылок на назначенных доменах и просмотр статистики
+    Editor,
+    /// Только просмотр статистики
+    Viewer,
+}
+
+impl Role {
+    pub const ALL: [Role; 3] = [Role::Admin, Role::Editor, Role::Viewer];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Admin => "admin",
+            Role::Editor => "editor",
+            Role::Viewer => "viewer",
+        }
+    }
+
+    /// Права, доступные роли (права токена пользователя ими ограничиваются)
+    pub fn scopes(&self) -> &'static [Scope] {
+        match self {
+            Role::Admin => &Scope::ALL,
+            Role::Editor => &[Scope::LinksWrite, Scope::LinksRead, Scope::StatsRead],
+            Role::Viewer => &[Scope::LinksRead, Scope::StatsRead],
+        }
+    }
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Role {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Role::ALL
+            .into_iter()
+            .find(|role| role.as_str().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| format!("Unknown role '{}', expected admin, editor or viewer", s))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct User {
+    pub id: i64,
+    pub username: String,
+    pub password_hash: String,
+    pub role: Role,
+    /// Домены, назначенные редактору
+    pub domain_ids: Vec<i64>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub disabled_at: Option<DateTime<Utc>>,
+    pub last_login_at: Option<DateTime<Utc>>,
+}
+
+impl User {
+    pub fn is_active(&self) -> bool {
+        self.disabled_at.is_none()
+    }
+
+    /// Домены для создания ссылок (None - без ограничений)
+    pub fn allowed_domain_ids(&self) -> Option<&[i64]> {
+        match self.role {
+            Role::Admin => None,
+            Role::Editor | Role::Viewer => Some(&self.domain_ids),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct NewUser {
+    pub username: String,
+    pub password_hash: String,
+    pub role: Role,
+    pub domain_ids: Vec<i64>,
+}
@@ -0,0 +1,12 @@
+/// Задача на получение метаданных целевой страницы ссылки
+#[derive(Debug, Clone)]
+pub struct MetadataJob {
+    pub link_id: i64,
+    pub url: String,
+}
+
+impl MetadataJob {
+    pub fn new(link_id: i64, url: String) -> Self {
+        Self { link_id, url }
+    }
+}
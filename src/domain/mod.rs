@@ -1,4 +1,6 @@
 pub mod click_event;
 pub mod click_worker;
+pub mod code_generator;
 pub mod entities;
+pub mod metadata_job;
 pub mod repositories;
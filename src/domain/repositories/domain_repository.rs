@@ -1,6 +1,7 @@
-use crate::domain::entities::{Domain, NewDomain, UpdateDomain};
+use crate::domain::entities::{Domain, DomainAlias, NewDomain, UpdateDomain, VerificationMethod};
 use crate::error::AppError;
 use async_trait::async_trait;
+use std::collections::HashMap;
 
 #[async_trait]
 pub trait DomainRepository: Send + Sync {
@@ -13,6 +14,14 @@ pub trait DomainRepository: Send + Sync {
     /// Найти домен по имени
     async fn find_by_name(&self, domain: &str) -> Result<Option<Domain>, AppError>;
 
+    /// Найти домен по хосту запроса: точное имя, затем подтверждённый алиас,
+    /// затем самый точный из подтверждённых wildcard шаблонов `wildcards`
+    async fn find_by_host(
+        &self,
+        host: &str,
+        wildcards: &[String],
+    ) -> Result<Option<Domain>, AppError>;
+
     /// Получить default домен
     async fn get_default(&self) -> Result<Domain, AppError>;
 
@@ -22,6 +31,9 @@ pub trait DomainRepository: Send + Sync {
     /// Обновить домен
     async fn update(&self, id: i64, update: UpdateDomain) -> Result<Domain, AppError>;
 
+    /// Переименовать домен (приведение имени к канонической форме)
+    async fn rename(&self, id: i64, domain: &str) -> Result<(), AppError>;
+
     /// Удалить домен (только если нет привязанных ссылок)
     async fn delete(&self, id: i64) -> Result<(), AppError>;
 
@@ -30,4 +42,47 @@ pub trait DomainRepository: Send + Sync {
 
     /// Количество ссылок на домене
     async fn count_links(&self, domain_id: i64) -> Result<i64, AppError>;
+
+    /// Количество ссылок по доменам (домены без ссылок отсутствуют)
+    async fn count_links_by_domain(&self) -> Result<HashMap<i64, i64>, AppError>;
+
+    /// Отметить домен подтверждённым и включить его
+    async fn mark_verified(&self, id: i64, method: VerificationMethod) -> Result<Domain, AppError>;
+
+    /// Сохранить результат неудачной проверки
+    async fn record_verification_failure(&self, id: i64, error: &str) -> Result<(), AppError>;
+
+    /// Алиасы и wildcard шаблоны домена
+    async fn list_aliases(&self, domain_id: i64) -> Result<Vec<DomainAlias>, AppError>;
+
+    /// Алиасы всех доменов
+    async fn list_all_aliases(&self) -> Result<Vec<DomainAlias>, AppError>;
+
+    /// Найти алиас по имени (в том числе wildcard шаблон)
+    async fn find_alias(&self, host: &str) -> Result<Option<DomainAlias>, AppError>;
+
+    /// Добавить алиас или wildcard шаблон. Без токена алиас сразу подтверждён
+    async fn add_alias(
+        &self,
+        domain_id: i64,
+        host: &str,
+        verification_token: Option<&str>,
+    ) -> Result<DomainAlias, AppError>;
+
+    /// Отметить алиас подтверждённым
+    async fn mark_alias_verified(
+        &self,
+        id: i64,
+        method: VerificationMethod,
+    ) -> Result<DomainAlias, AppError>;
+
+    /// Сохранить результат неудачной проверки алиаса
+    async fn record_alias_verification_failure(&self, id: i64, error: &str)
+    -> Result<(), AppError>;
+
+    /// Переименовать алиас (приведение имени к канонической форме)
+    async fn rename_alias(&self, id: i64, host: &str) -> Result<(), AppError>;
+
+    /// Удалить алиас
+    async fn remove_alias(&self, id: i64) -> Result<(), AppError>;
 }
@@ -1,30 +1,66 @@
-use crate::domain::entities::{Link, NewLink};
+use crate::domain::entities::{CodePolicy, Link, LinkMetadata, NewLink, PageMetadata};
 use crate::error::AppError;
 use async_trait::async_trait;
+use chrono::NaiveDate;
 
 #[async_trait]
 pub trait LinkRepository: Send + Sync {
-    /// Создать новую ссылку
-    async fn create(&self, new_link: NewLink) -> Result<Link, AppError>;
+    /// Создать новую ссылку; `None` - код уже занят в этом домене
+    async fn create(&self, new_link: NewLink) -> Result<Option<Link>, AppError>;
+
+    /// Следующий номер кода (стратегия sequential) в пространстве
+    /// алфавит + длина политики домена
+    async fn next_code_number(&self, domain_id: i64, policy: &CodePolicy) -> Result<i64, AppError>;
 
     /// Найти ссылку по коду и домену
     async fn find_by_code(&self, code: &str, domain_id: i64) -> Result<Option<Link>, AppError>;
 
-    /// Найти ссылку по длинному URL и домену
+    /// Найти ссылку по длинному URL и домену, созданную тем же токеном для того
+    /// же владельца и с теми же `title`/`notes` (`None` совпадает только с `None`)
     async fn find_by_long_url(
         &self,
         long_url: &str,
         domain_id: i64,
+        metadata: &LinkMetadata,
     ) -> Result<Option<Link>, AppError>;
 
-    /// Список ссылок с пагинацией и фильтром по домену
+    /// Список ссылок с пагинацией и фильтрами по домену и тегу
     async fn list(
         &self,
         page: i64,
         page_size: i64,
         domain_id: Option<i64>, // None = все домены
+        tag: Option<&str>,      // None = без фильтра по тегу
     ) -> Result<Vec<Link>, AppError>;
 
     /// Количество ссылок
-    async fn count(&self, domain_id: Option<i64>) -> Result<i64, AppError>;
+    async fn count(&self, domain_id: Option<i64>, tag: Option<&str>) -> Result<i64, AppError>;
+
+    /// Атомарно добавить `count` ссылок к суточному счётчику токена, если сумма
+    /// не превысит `limit`. Возвращает новое значение или None при превышении
+    async fn reserve_daily_quota(
+        &self,
+        token_id: i64,
+        day: NaiveDate,
+        count: i64,
+        limit: i64,
+    ) -> Result<Option<i64>, AppError>;
+
+    /// Вернуть `count` неиспользованных ссылок в суточный счётчик токена
+    async fn release_daily_quota(
+        &self,
+        token_id: i64,
+        day: NaiveDate,
+        count: i64,
+    ) -> Result<i64, AppError>;
+
+    /// Сколько ссылок токен израсходовал за сутки `day`
+    async fn daily_quota_used(&self, token_id: i64, day: NaiveDate) -> Result<i64, AppError>;
+
+    /// Сохранить метаданные целевой страницы (title не перезаписывается, если задан)
+    async fn update_page_metadata(
+        &self,
+        link_id: i64,
+        metadata: &PageMetadata,
+    ) -> Result<(), AppError>;
 }
@@ -1,9 +1,19 @@
 pub mod domain_repository;
 pub mod link_repository;
+pub mod reserved_code_repository;
+pub mod session_repository;
 pub mod stats_repository;
+pub mod tag_repository;
 pub mod token_repository;
+pub mod user_repository;
 
 pub use domain_repository::DomainRepository;
 pub use link_repository::LinkRepository;
-pub use stats_repository::{DetailedStats, LinkStats, StatsFilter, StatsRepository};
-pub use token_repository::{ApiToken, TokenRepository};
+pub use reserved_code_repository::ReservedCodeRepository;
+pub use session_repository::{DashboardSession, NewDashboardSession, SessionRepository};
+pub use stats_repository::{
+    DetailedStats, LinkStats, SortDirection, StatsFilter, StatsRepository, StatsSortField,
+};
+pub use tag_repository::{TagRepository, TagStats};
+pub use token_repository::{ApiToken, NewApiToken, TokenLimits, TokenRepository};
+pub use user_repository::UserRepository;
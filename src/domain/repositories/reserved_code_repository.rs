@@ -0,0 +1,22 @@
+use crate::domain::entities::ReservedCode;
+use crate::error::AppError;
+use async_trait::async_trait;
+
+/// Репозиторий зарезервированных кодов. Коды хранятся в нижнем регистре.
+#[async_trait]
+pub trait ReservedCodeRepository: Send + Sync {
+    /// Зарезервирован ли код глобально или на домене
+    async fn is_reserved(&self, code: &str, domain_id: i64) -> Result<bool, AppError>;
+
+    /// Все зарезервированные коды с id домена (None - на всех доменах)
+    async fn list_codes(&self) -> Result<Vec<(String, Option<i64>)>, AppError>;
+
+    /// Все зарезервированные коды
+    async fn list(&self) -> Result<Vec<ReservedCode>, AppError>;
+
+    /// Зарезервировать код (domain_id None - на всех доменах)
+    async fn add(&self, code: &str, domain_id: Option<i64>) -> Result<ReservedCode, AppError>;
+
+    /// Снять резерв, возвращает false, если кода не было
+    async fn remove(&self, code: &str, domain_id: Option<i64>) -> Result<bool, AppError>;
+}
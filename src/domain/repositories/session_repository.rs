@@ -0,0 +1,50 @@
+use crate::error::AppError;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+/// Сессия дашборда: после входа по API токену или по логину и паролю
+#[derive(Debug, Clone)]
+pub struct DashboardSession {
+    pub id: i64,
+    pub token_id: Option<i64>,
+    pub user_id: Option<i64>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+/// Данные для создания сессии
+#[derive(Debug, Clone)]
+pub struct NewDashboardSession {
+    pub session_hash: String,
+    pub token_id: Option<i64>,
+    pub user_id: Option<i64>,
+    pub expires_at: DateTime<Utc>,
+    pub ip: Option<String>,
+    pub user_agent: Option<String>,
+}
+
+/// Репозиторий сессий дашборда
+#[async_trait]
+pub trait SessionRepository: Send + Sync {
+    async fn create(&self, session: NewDashboardSession) -> Result<DashboardSession, AppError>;
+
+    /// Найти действующую сессию (не отозвана и не истекла)
+    async fn find_active_by_hash(
+        &self,
+        session_hash: &str,
+    ) -> Result<Option<DashboardSession>, AppError>;
+
+    /// Обновить время последней активности
+    async fn touch(&self, id: i64) -> Result<(), AppError>;
+
+    /// Отозвать сессию
+    async fn revoke(&self, session_hash: &str) -> Result<(), AppError>;
+
+    /// Отозвать все сессии пользователя (смена пароля, блокировка)
+    async fn revoke_for_user(&self, user_id: i64) -> Result<(), AppError>;
+
+    /// Удалить истёкшие и отозванные сессии, возвращает количество
+    async fn delete_expired(&self) -> Result<u64, AppError>;
+}
@@ -12,15 +12,64 @@ pub struct LinkStats {
     pub long_url: String,
     pub total: i64,
     pub created_at: DateTime<Utc>,
+    pub tags: Vec<String>,
+    pub title: Option<String>,
+    pub notes: Option<String>,
+    pub created_by: Option<i64>,
+    /// Имя токена, которым создана ссылка
+    pub created_by_name: Option<String>,
+    /// Имя пользователя-владельца ссылки
+    pub owner_name: Option<String>,
 }
 
 #[derive(Debug, Clone)]
 pub struct DetailedStats {
     pub link: crate::domain::entities::Link,
+    pub tags: Vec<String>,
+    /// Имя токена, которым создана ссылка
+    pub created_by_name: Option<String>,
+    /// Имя пользователя-владельца ссылки
+    pub owner_name: Option<String>,
     pub total: i64,
     pub items: Vec<Click>,
 }
 
+/// Поле сортировки списка ссылок
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StatsSortField {
+    Clicks,
+    #[default]
+    CreatedAt,
+    Code,
+}
+
+impl StatsSortField {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Clicks => "clicks",
+            Self::CreatedAt => "created_at",
+            Self::Code => "code",
+        }
+    }
+}
+
+/// Направление сортировки
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortDirection {
+    Asc,
+    #[default]
+    Desc,
+}
+
+impl SortDirection {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Asc => "asc",
+            Self::Desc => "desc",
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct StatsFilter {
     pub from_date: Option<DateTime<Utc>>,
@@ -28,6 +77,12 @@ pub struct StatsFilter {
     pub offset: i64,
     pub limit: i64,
     pub domain_id: Option<i64>,
+    pub sort_by: StatsSortField,
+    pub sort_dir: SortDirection,
+    /// Подстрока для поиска по long_url и code
+    pub search: Option<String>,
+    /// Фильтр по тегу
+    pub tag: Option<String>,
 }
 
 impl StatsFilter {
@@ -38,6 +93,10 @@ impl StatsFilter {
             offset,
             limit,
             domain_id: None,
+            sort_by: StatsSortField::default(),
+            sort_dir: SortDirection::default(),
+            search: None,
+            tag: None,
         }
     }
 
@@ -55,6 +114,26 @@ impl StatsFilter {
         self.to_date = to_date;
         self
     }
+
+    pub fn with_sort(mut self, sort_by: StatsSortField, sort_dir: SortDirection) -> Self {
+        self.sort_by = sort_by;
+        self.sort_dir = sort_dir;
+        self
+    }
+
+    pub fn with_search(mut self, search: Option<String>) -> Self {
+        self.search = search
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty());
+        self
+    }
+
+    pub fn with_tag(mut self, tag: Option<String>) -> Self {
+        self.tag = tag
+            .map(|t| t.trim().to_lowercase())
+            .filter(|t| !t.is_empty());
+        self
+    }
 }
 
 /// Репозиторий для работы со статистикой и кликами
@@ -73,8 +152,8 @@ pub trait StatsRepository: Send + Sync {
     /// Получить список всех ссылок со статистикой (с фильтрами)
     async fn get_all_stats(&self, filter: StatsFilter) -> Result<Vec<LinkStats>, AppError>;
 
-    /// Подсчитать общее количество ссылок (для пагинации)
-    async fn count_all_links(&self) -> Result<i64, AppError>;
+    /// Подсчитать количество ссылок с учётом фильтров по домену, поиску и тегу (для пагинации)
+    async fn count_all_links(&self, filter: &StatsFilter) -> Result<i64, AppError>;
 
     /// Получить количество кликов по link_id (с фильтром по дате)
     async fn count_clicks_by_link_id(
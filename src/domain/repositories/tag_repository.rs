@@ -0,0 +1,26 @@
+use crate::error::AppError;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+/// Агрегированная статистика по тегу
+#[derive(Debug, Clone)]
+pub struct TagStats {
+    pub name: String,
+    pub links_count: i64,
+    pub total_clicks: i64,
+}
+
+/// Репозиторий для работы с тегами ссылок
+#[async_trait]
+pub trait TagRepository: Send + Sync {
+    /// Привязать теги к ссылке (недостающие теги создаются)
+    async fn attach_tags(&self, link_id: i64, tags: &[String]) -> Result<(), AppError>;
+
+    /// Статистика по всем тегам (с фильтром по домену и датам кликов)
+    async fn list_stats(
+        &self,
+        domain_id: Option<i64>,
+        from_date: Option<DateTime<Utc>>,
+        to_date: Option<DateTime<Utc>>,
+    ) -> Result<Vec<TagStats>, AppError>;
+}
@@ -1,3 +1,4 @@
+use crate::domain::entities::Scope;
 use crate::error::AppError;
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
@@ -9,19 +10,80 @@ pub struct ApiToken {
     pub token_hash: String,
     pub created_at: DateTime<Utc>,
     pub revoked_at: Option<DateTime<Utc>>,
+    pub scopes: Vec<Scope>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    /// Домены, на которых токен может создавать ссылки (None - любые)
+    pub allowed_domain_ids: Option<Vec<i64>>,
+    pub limits: TokenLimits,
+    /// Открытая часть токена `ush_<lookup id>_...` (None - токен старого формата)
+    pub lookup_id: Option<String>,
+    /// Хэш ещё в старом формате (SHA-256 без pepper)
+    pub legacy_hash: bool,
+    /// Пользователь, для которого выпущен токен (None - сервисный токен)
+    pub user_id: Option<i64>,
+}
+
+/// Индивидуальные лимиты токена
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TokenLimits {
+    /// Запросов в минуту (None - значение по умолчанию)
+    pub requests_per_minute: Option<u32>,
+    /// Ссылок в сутки, UTC (None - без квоты)
+    pub daily_link_quota: Option<u32>,
+}
+
+impl ApiToken {
+    pub fn is_expired(&self) -> bool {
+        self.expires_at.is_some_and(|at| at <= Utc::now())
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.revoked_at.is_none() && !self.is_expired()
+    }
+}
+
+/// Данные для создания токена
+#[derive(Debug, Clone)]
+pub struct NewApiToken {
+    pub name: String,
+    pub token_hash: String,
+    pub scopes: Vec<Scope>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub allowed_domain_ids: Option<Vec<i64>>,
+    pub limits: TokenLimits,
+    pub lookup_id: Option<String>,
+    pub user_id: Option<i64>,
 }
 
 /// Репозиторий для работы с API токенами
 #[async_trait]
 pub trait TokenRepository: Send + Sync {
-    /// Проверить, валиден ли токен (существует и не отозван)
-    async fn validate_token(&self, token_hash: &str) -> Result<bool, AppError>;
+    /// Найти валидный токен (существует, не отозван и не истёк)
+    async fn find_active_by_hash(&self, token_hash: &str) -> Result<Option<ApiToken>, AppError>;
+
+    /// Найти валидный токен с хэшем старого формата
+    async fn find_active_by_legacy_hash(
+        &self,
+        legacy_hash: &str,
+    ) -> Result<Option<ApiToken>, AppError>;
+
+    /// Заменить хэш старого формата на новый
+    async fn upgrade_hash(&self, id: i64, token_hash: &str) -> Result<(), AppError>;
 
     /// Обновить время последнего использования токена
     async fn update_last_used(&self, token_hash: &str) -> Result<(), AppError>;
 
     /// Create new token
-    async fn create_token(&self, name: &str, token_hash: &str) -> Result<ApiToken, AppError>;
+    async fn create_token(&self, token: NewApiToken) -> Result<ApiToken, AppError>;
+
+    /// Выпустить новый токен взамен `id`; старый остаётся валидным до `old_expires_at`
+    async fn rotate_token(
+        &self,
+        id: i64,
+        token: NewApiToken,
+        old_expires_at: DateTime<Utc>,
+    ) -> Result<ApiToken, AppError>;
 
     /// List all tokens
     async fn list_tokens(&self) -> Result<Vec<ApiToken>, AppError>;
@@ -29,9 +91,15 @@ pub trait TokenRepository: Send + Sync {
     /// Find token by ID
     async fn find_by_id(&self, id: i64) -> Result<Option<ApiToken>, AppError>;
 
-    /// Find token by name
+    /// Найти токен по lookup id (`ush_<lookup id>_...`)
+    async fn find_by_lookup_id(&self, lookup_id: &str) -> Result<Option<ApiToken>, AppError>;
+
+    /// Find token by name (предпочитая активный и самый новый)
     async fn find_by_name(&self, name: &str) -> Result<Option<ApiToken>, AppError>;
 
+    /// Обновить индивидуальные лимиты токена
+    async fn update_limits(&self, id: i64, limits: TokenLimits) -> Result<(), AppError>;
+
     /// Revoke token
     async fn revoke_token(&self, id: i64) -> Result<(), AppError>;
 }
@@ -0,0 +1,26 @@
+use crate::domain::entities::{NewUser, Role, User};
+use crate::error::AppError;
+use async_trait::async_trait;
+
+/// Репозиторий пользователей
+#[async_trait]
+pub trait UserRepository: Send + Sync {
+    async fn create(&self, user: NewUser) -> Result<User, AppError>;
+
+    async fn find_by_id(&self, id: i64) -> Result<Option<User>, AppError>;
+
+    async fn find_by_username(&self, username: &str) -> Result<Option<User>, AppError>;
+
+    async fn list(&self) -> Result<Vec<User>, AppError>;
+
+    async fn update_password(&self, id: i64, password_hash: &str) -> Result<(), AppError>;
+
+    /// Изменить роль и назначенные домены
+    async fn update_role(&self, id: i64, role: Role, domain_ids: &[i64]) -> Result<(), AppError>;
+
+    /// Заблокировать (true) или разблокировать пользователя
+    async fn set_disabled(&self, id: i64, disabled: bool) -> Result<(), AppError>;
+
+    /// Отметить успешный вход
+    async fn update_last_login(&self, id: i64) -> Result<(), AppError>;
+}
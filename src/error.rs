@@ -22,11 +22,39 @@ pub struct ErrorInfo {
 
 #[derive(Debug)]
 pub enum AppError {
-    Validation { message: String, details: Value },
-    NotFound { message: String, details: Value },
-    Conflict { message: String, details: Value },
-    Unauthorized { message: String, details: Value },
-    Internal { message: String, details: Value },
+    Validation {
+        message: String,
+        details: Value,
+    },
+    NotFound {
+        message: String,
+        details: Value,
+    },
+    Gone {
+        message: String,
+        details: Value,
+    },
+    Conflict {
+        message: String,
+        details: Value,
+    },
+    Unauthorized {
+        message: String,
+        details: Value,
+    },
+    Forbidden {
+        message: String,
+        details: Value,
+    },
+    TooManyRequests {
+        message: String,
+        details: Value,
+        retry_after_secs: u64,
+    },
+    Internal {
+        message: String,
+        details: Value,
+    },
 }
 
 impl AppError {
@@ -44,6 +72,13 @@ impl AppError {
         }
     }
 
+    pub fn gone(message: impl Into<String>, details: Value) -> Self {
+        Self::Gone {
+            message: message.into(),
+            details,
+        }
+    }
+
     pub fn conflict(message: impl Into<String>, details: Value) -> Self {
         Self::Conflict {
             message: message.into(),
@@ -65,12 +100,36 @@ impl AppError {
         }
     }
 
+    pub fn forbidden(message: impl Into<String>, details: Value) -> Self {
+        Self::Forbidden {
+            message: message.into(),
+            details,
+        }
+    }
+
+    pub fn too_many_requests(
+        message: impl Into<String>,
+        details: Value,
+        retry_after_secs: u64,
+    ) -> Self {
+        Self::TooManyRequests {
+            message: message.into(),
+            details,
+            retry_after_secs,
+        }
+    }
+
     pub fn to_error_info(self) -> ErrorInfo {
         let (code, message, details) = match self {
             AppError::Validation { message, details } => ("validation_error", message, details),
             AppError::NotFound { message, details } => ("not_found", message, details),
+            AppError::Gone { message, details } => ("gone", message, details),
             AppError::Conflict { message, details } => ("conflict", message, details),
             AppError::Unauthorized { message, details } => ("unauthorized", message, details),
+            AppError::Forbidden { message, details } => ("forbidden", message, details),
+            AppError::TooManyRequests {
+                message, details, ..
+            } => ("rate_limited", message, details),
             AppError::Internal { message, details } => ("internal_error", message, details),
         };
 
@@ -84,6 +143,13 @@ impl AppError {
 
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
+        let retry_after = match &self {
+            AppError::TooManyRequests {
+                retry_after_secs, ..
+            } => Some(*retry_after_secs),
+            _ => None,
+        };
+
         let (status, code, message, details, add_www_authenticate) = match self {
             AppError::Validation { message, details } => (
                 StatusCode::BAD_REQUEST,
@@ -95,6 +161,9 @@ impl IntoResponse for AppError {
             AppError::NotFound { message, details } => {
                 (StatusCode::NOT_FOUND, "not_found", message, details, false)
             }
+            AppError::Gone { message, details } => {
+                (StatusCode::GONE, "gone", message, details, false)
+            }
             AppError::Conflict { message, details } => {
                 (StatusCode::CONFLICT, "conflict", message, details, false)
             }
@@ -105,6 +174,18 @@ impl IntoResponse for AppError {
                 details,
                 true,
             ),
+            AppError::Forbidden { message, details } => {
+                (StatusCode::FORBIDDEN, "forbidden", message, details, false)
+            }
+            AppError::TooManyRequests {
+                message, details, ..
+            } => (
+                StatusCode::TOO_MANY_REQUESTS,
+                "rate_limited",
+                message,
+                details,
+                false,
+            ),
             AppError::Internal { message, details } => (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 "internal_error",
@@ -122,13 +203,15 @@ impl IntoResponse for AppError {
             },
         };
 
+        let mut headers = HeaderMap::new();
         if add_www_authenticate {
-            let mut headers = HeaderMap::new();
             headers.insert(header::WWW_AUTHENTICATE, "Bearer".parse().unwrap());
-            (status, headers, Json(body)).into_response()
-        } else {
-            (status, Json(body)).into_response()
         }
+        if let Some(secs) = retry_after {
+            headers.insert(header::RETRY_AFTER, secs.into());
+        }
+
+        (status, headers, Json(body)).into_response()
     }
 }
 
@@ -154,8 +237,11 @@ pub fn map_sqlx_error(e: SqlxError) -> AppError {
                 let constraint = db_err.constraint().unwrap_or("unknown");
                 let (message, field) = match constraint {
                     "links_code_key" => ("This short code is already in use", "code"),
-                    "links_long_url_key" => ("This URL has already been shortened", "long_url"),
                     "api_tokens_token_hash_key" => ("Token already exists", "token"),
+                    "domain_aliases_host_key" => ("Alias already exists", "host"),
+                    "reserved_codes_global_key" | "reserved_codes_domain_key" => {
+                        ("Code is already reserved", "code")
+                    }
                     _ => {
                         tracing::warn!(
                             constraint = constraint,
@@ -290,8 +376,13 @@ impl std::fmt::Display for AppError {
         match self {
             AppError::Validation { message, .. } => write!(f, "Validation error: {}", message),
             AppError::NotFound { message, .. } => write!(f, "Not found: {}", message),
+            AppError::Gone { message, .. } => write!(f, "Gone: {}", message),
             AppError::Conflict { message, .. } => write!(f, "Conflict: {}", message),
             AppError::Unauthorized { message, .. } => write!(f, "Unauthorized: {}", message),
+            AppError::Forbidden { message, .. } => write!(f, "Forbidden: {}", message),
+            AppError::TooManyRequests { message, .. } => {
+                write!(f, "Too many requests: {}", message)
+            }
             AppError::Internal { message, .. } => write!(f, "Internal error: {}", message),
         }
     }
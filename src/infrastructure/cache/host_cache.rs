@@ -0,0 +1,60 @@
+use crate::domain::entities::Domain;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Больше записей не храним: при переполнении кэш очищается
+const MAX_ENTRIES: usize = 10_000;
+
+/// In-process кэш разрешения хоста запроса в домен (точное имя, алиас или
+/// wildcard), чтобы редирект не ходил в БД за доменом. Кэшируются и промахи -
+/// запросы на неизвестные хосты тоже не доходят до БД.
+pub struct HostCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<String, (Option<Domain>, Instant)>>,
+}
+
+impl HostCache {
+    /// `ttl` = 0 выключает кэш
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Some(None) - хост известен как не относящийся ни к одному домену
+    pub fn get(&self, host: &str) -> Option<Option<Domain>> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries
+            .get(host)
+            .filter(|(_, expires_at)| *expires_at > Instant::now())
+            .map(|(domain, _)| domain.clone())
+    }
+
+    pub fn insert(&self, host: &str, domain: Option<Domain>) {
+        if self.ttl.is_zero() {
+            return;
+        }
+
+        let now = Instant::now();
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+
+        if entries.len() >= MAX_ENTRIES {
+            entries.retain(|_, (_, expires_at)| *expires_at > now);
+            if entries.len() >= MAX_ENTRIES {
+                entries.clear();
+            }
+        }
+
+        entries.insert(host.to_string(), (domain, now + self.ttl));
+    }
+
+    /// Сбросить всё (после изменения доменов или алиасов)
+    pub fn clear(&self) {
+        self.entries
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+    }
+}
@@ -1,9 +1,14 @@
 //! Модуль кэширования для редиректов URL-shortener
 
+mod host_cache;
 mod null_cache;
 mod redis_cache;
 mod service;
 
+pub use host_cache::HostCache;
 pub use null_cache::NullCache;
-pub use redis_cache::RedisCache;
-pub use service::{CacheError, CacheResult, CacheService};
+pub use redis_cache::{RedisCache, spawn_changes_listener};
+pub use service::{
+    CacheError, CacheResult, CacheService, DOMAINS_CHANGED_CHANNEL, RESERVED_CODES_CHANGED_CHANNEL,
+    domain_prefix, redirect_key,
+};
@@ -37,6 +37,18 @@ impl CacheService for NullCache {
         Ok(())
     }
 
+    async fn invalidate_prefix(&self, _prefix: &str) -> CacheResult<()> {
+        Ok(())
+    }
+
+    async fn publish_domains_changed(&self) -> CacheResult<()> {
+        Ok(())
+    }
+
+    async fn publish_reserved_codes_changed(&self) -> CacheResult<()> {
+        Ok(())
+    }
+
     async fn health_check(&self) -> bool {
         true
     }
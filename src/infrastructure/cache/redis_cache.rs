@@ -1,8 +1,15 @@
-use super::service::{CacheError, CacheResult, CacheService};
+use super::service::{
+    CacheError, CacheResult, CacheService, DOMAINS_CHANGED_CHANNEL, RESERVED_CODES_CHANGED_CHANNEL,
+};
 use async_trait::async_trait;
-use redis::{AsyncCommands, Client, aio::ConnectionManager};
+use futures_util::StreamExt;
+use redis::{AsyncCommands, Client, RedisResult, aio::ConnectionManager};
+use std::time::Duration;
 use tracing::{debug, error, info, warn};
 
+/// Сколько ключей удалять одной командой DEL
+const SCAN_DELETE_BATCH: usize = 500;
+
 pub struct RedisCache {
     client: ConnectionManager,
     default_ttl: usize,
@@ -41,6 +48,14 @@ impl RedisCache {
     fn build_key(&self, short_code: &str) -> String {
         format!("{}{}", self.key_prefix, short_code)
     }
+
+    async fn publish_change(&self, channel: &str) -> CacheResult<()> {
+        let mut conn = self.client.clone();
+        conn.publish::<_, _, i64>(channel, "1")
+            .await
+            .map(|receivers| debug!("{} published to {} instances", channel, receivers))
+            .map_err(|e| CacheError::OperationError(format!("Redis PUBLISH failed: {}", e)))
+    }
 }
 
 #[async_trait]
@@ -113,8 +128,106 @@ impl CacheService for RedisCache {
         }
     }
 
+    async fn invalidate_prefix(&self, prefix: &str) -> CacheResult<()> {
+        let pattern = format!("{}*", self.build_key(&escape_glob(prefix)));
+        let mut conn = self.client.clone();
+
+        // SCAN вместо KEYS, чтобы не блокировать Redis на больших базах
+        let keys = {
+            let mut iter = match conn.scan_match::<_, String>(&pattern).await {
+                Ok(iter) => iter,
+                Err(e) => {
+                    warn!("Redis SCAN error for {}: {}", pattern, e);
+                    return Ok(());
+                }
+            };
+
+            let mut keys = Vec::new();
+            while let Some(key) = iter.next_item().await {
+                match key {
+                    Ok(key) => keys.push(key),
+                    Err(e) => {
+                        warn!("Redis SCAN error for {}: {}", pattern, e);
+                        break;
+                    }
+                }
+            }
+            keys
+        };
+
+        for chunk in keys.chunks(SCAN_DELETE_BATCH) {
+            if let Err(e) = conn.del::<_, i32>(chunk).await {
+                warn!("Redis DEL error for {}: {}", pattern, e);
+                return Ok(());
+            }
+        }
+
+        debug!("Cache INVALIDATE {}: {} keys", pattern, keys.len());
+        Ok(())
+    }
+
+    async fn publish_domains_changed(&self) -> CacheResult<()> {
+        self.publish_change(DOMAINS_CHANGED_CHANNEL).await
+    }
+
+    async fn publish_reserved_codes_changed(&self) -> CacheResult<()> {
+        self.publish_change(RESERVED_CODES_CHANGED_CHANNEL).await
+    }
+
     async fn health_check(&self) -> bool {
         let mut conn = self.client.clone();
         conn.ping::<()>().await.is_ok()
     }
 }
+
+/// Пауза перед повторной подпиской после обрыва соединения
+const RESUBSCRIBE_DELAY: Duration = Duration::from_secs(5);
+
+/// Вызывать `on_change` по уведомлениям в `channel` от других инстансов и
+/// admin CLI (сброс in-process кэшей). После переподключения `on_change` тоже
+/// вызывается: уведомления за время обрыва потеряны.
+pub fn spawn_changes_listener<F>(redis_url: String, channel: &'static str, on_change: F)
+where
+    F: Fn() + Send + Sync + 'static,
+{
+    tokio::spawn(async move {
+        loop {
+            if let Err(e) = listen_changes(&redis_url, channel, &on_change).await {
+                warn!("Subscription to {} failed: {}", channel, e);
+            }
+            on_change();
+            tokio::time::sleep(RESUBSCRIBE_DELAY).await;
+        }
+    });
+}
+
+async fn listen_changes(
+    redis_url: &str,
+    channel: &str,
+    on_change: &(impl Fn() + Send + Sync),
+) -> RedisResult<()> {
+    let mut pubsub = Client::open(redis_url)?.get_async_pubsub().await?;
+    pubsub.subscribe(channel).await?;
+    on_change();
+    debug!("Subscribed to {}", channel);
+
+    let mut messages = pubsub.on_message();
+    while messages.next().await.is_some() {
+        on_change();
+    }
+
+    // Поток сообщений закончился - соединение закрыто
+    Ok(())
+}
+
+/// Экранировать спецсимволы glob-шаблона Redis
+fn escape_glob(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '*' | '?' | '[' | ']' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
@@ -21,6 +21,22 @@ impl std::error::Error for CacheError {}
 
 pub type CacheResult<T> = Result<T, CacheError>;
 
+/// Ключ кэша редиректа: `domain:code`
+pub fn redirect_key(domain: &str, code: &str) -> String {
+    format!("{}:{}", domain, code)
+}
+
+/// Канал Redis с уведомлениями об изменении доменов
+pub const DOMAINS_CHANGED_CHANNEL: &str = "url-shortener:domains-changed";
+
+/// Канал Redis с уведомлениями об изменении зарезервированных кодов
+pub const RESERVED_CODES_CHANGED_CHANNEL: &str = "url-shortener:reserved-codes-changed";
+
+/// Общий префикс ключей всех редиректов домена
+pub fn domain_prefix(domain: &str) -> String {
+    format!("{}:", domain)
+}
+
 /// Трейт для абстракции кэширования редиректов
 #[async_trait]
 pub trait CacheService: Send + Sync {
@@ -38,6 +54,16 @@ pub trait CacheService: Send + Sync {
     /// Удалить из кэша (инвалидация)
     async fn invalidate(&self, short_code: &str) -> CacheResult<()>;
 
+    /// Удалить все записи с ключом, начинающимся с `prefix` (например, все коды домена)
+    async fn invalidate_prefix(&self, prefix: &str) -> CacheResult<()>;
+
+    /// Сообщить всем инстансам об изменении доменов или алиасов
+    /// (сбрасывает их `HostCache`)
+    async fn publish_domains_changed(&self) -> CacheResult<()>;
+
+    /// Сообщить всем инстансам об изменении зарезервированных кодов
+    async fn publish_reserved_codes_changed(&self) -> CacheResult<()>;
+
     /// Проверка работоспособности
     async fn health_check(&self) -> bool;
 }
@@ -0,0 +1,9 @@
+//! Стратегии генерации кодов ссылок: случайные, последовательные, из слов
+
+mod random;
+mod sequential;
+mod words;
+
+pub use random::RandomCodeGenerator;
+pub use sequential::SequentialCodeGenerator;
+pub use words::WordCodeGenerator;
@@ -0,0 +1,16 @@
+use async_trait::async_trait;
+
+use crate::domain::code_generator::CodeGenerator;
+use crate::domain::entities::CodePolicy;
+use crate::error::AppError;
+use crate::utils::code_generator::generate_code;
+
+/// Случайный код по длине и алфавиту политики
+pub struct RandomCodeGenerator;
+
+#[async_trait]
+impl CodeGenerator for RandomCodeGenerator {
+    async fn generate(&self, _domain_id: i64, policy: &CodePolicy) -> Result<String, AppError> {
+        Ok(generate_code(policy))
+    }
+}
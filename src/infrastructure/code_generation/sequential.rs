@@ -0,0 +1,182 @@
+use async_trait::async_trait;
+use hmac::{Hmac, Mac};
+use serde_json::json;
+use sha2::Sha256;
+use std::sync::Arc;
+
+use crate::domain::code_generator::CodeGenerator;
+use crate::domain::entities::CodePolicy;
+use crate::domain::repositories::LinkRepository;
+use crate::error::AppError;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Раундов сети Фейстеля
+const ROUNDS: u8 = 4;
+/// Предел пространства кодов (длинные коды дополняются первым символом алфавита)
+const MAX_SPACE_BITS: u32 = 120;
+
+/// Последовательный код: номер из счётчика домена (`link_code_sequences`,
+/// свой для каждой пары алфавит + длина), переставленный ключом
+/// (сеть Фейстеля с cycle-walking) и записанный в алфавите политики.
+/// Без ключа соседние номера не дают угадываемых соседних кодов, а коллизий
+/// нет, пока не исчерпано пространство `алфавит^длина`.
+///
+/// Счётчик - строка таблицы, а не `SEQUENCE`: пространств кодов заранее не
+/// знаем (их задают политики доменов), а последовательность на каждое пришлось
+/// бы создавать DDL во время работы. UPSERT счётчика выполняется отдельным
+/// автокоммитным запросом вне транзакции создания ссылки, поэтому блокировка
+/// строки держится только на время самого UPDATE.
+pub struct SequentialCodeGenerator<L: LinkRepository> {
+    link_repository: Arc<L>,
+    permutation: KeyedPermutation,
+}
+
+impl<L: LinkRepository> SequentialCodeGenerator<L> {
+    pub fn new(link_repository: Arc<L>, secret: &str) -> Self {
+        Self {
+            link_repository,
+            permutation: KeyedPermutation::new(secret),
+        }
+    }
+}
+
+/// Перестановка номеров, заданная ключом
+struct KeyedPermutation {
+    key: Vec<u8>,
+}
+
+impl KeyedPermutation {
+    fn new(secret: &str) -> Self {
+        // Отдельный ключ, чтобы секрет можно было разделять с другими HMAC
+        let mut mac =
+            HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+        mac.update(b"link-code-sequence");
+
+        Self {
+            key: mac.finalize().into_bytes().to_vec(),
+        }
+    }
+
+    /// Биекция на [0, space)
+    fn permute(&self, value: u128, space: u128) -> u128 {
+        // Чётное число бит, чтобы половины были равны
+        let bits = (u128::BITS - (space - 1).leading_zeros()).max(2);
+        let half = bits.div_ceil(2);
+
+        let mut permuted = value;
+        loop {
+            permuted = self.feistel(permuted, half);
+            if permuted < space {
+                return permuted;
+            }
+        }
+    }
+
+    fn feistel(&self, value: u128, half: u32) -> u128 {
+        let mask = (1u128 << half) - 1;
+        let (mut left, mut right) = (value >> half, value & mask);
+
+        for round in 0..ROUNDS {
+            let next = left ^ (self.round_function(round, right) & mask);
+            left = right;
+            right = next;
+        }
+
+        (left << half) | right
+    }
+
+    fn round_function(&self, round: u8, value: u128) -> u128 {
+        let mut mac = HmacSha256::new_from_slice(&self.key).expect("HMAC accepts any key length");
+        mac.update(&[round]);
+        mac.update(&value.to_be_bytes());
+
+        let digest = mac.finalize().into_bytes();
+        let mut bytes = [0u8; 16];
+        bytes.copy_from_slice(&digest[..16]);
+        u128::from_be_bytes(bytes)
+    }
+}
+
+#[async_trait]
+impl<L: LinkRepository> CodeGenerator for SequentialCodeGenerator<L> {
+    async fn generate(&self, domain_id: i64, policy: &CodePolicy) -> Result<String, AppError> {
+        let alphabet = policy.alphabet.chars();
+        let base = alphabet.len() as u128;
+        let space = base
+            .checked_pow(policy.length as u32)
+            .filter(|space| *space <= 1u128 << MAX_SPACE_BITS)
+            .unwrap_or(1u128 << MAX_SPACE_BITS);
+
+        let number = self
+            .link_repository
+            .next_code_number(domain_id, policy)
+            .await? as u128;
+        if number >= space {
+            return Err(AppError::internal(
+                "Sequential code space exhausted",
+                json!({
+                    "reason": "Increase the code length of the domain",
+                    "domain_id": domain_id,
+                    "length": policy.length,
+                    "alphabet": policy.alphabet.as_str()
+                }),
+            ));
+        }
+
+        let mut value = self.permutation.permute(number, space);
+        let mut code = vec![alphabet[0]; policy.length];
+        for slot in code.iter_mut().rev() {
+            *slot = alphabet[(value % base) as usize];
+            value /= base;
+        }
+
+        Ok(code.into_iter().map(char::from).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn assert_bijection(space: u128) {
+        let permutation = KeyedPermutation::new("test-secret-0123456789abcdef0123456789");
+
+        let outputs: HashSet<u128> = (0..space)
+            .map(|value| {
+                let permuted = permutation.permute(value, space);
+                assert!(
+                    permuted < space,
+                    "{} -> {} is out of range",
+                    value,
+                    permuted
+                );
+                permuted
+            })
+            .collect();
+
+        assert_eq!(outputs.len() as u128, space);
+    }
+
+    #[test]
+    fn permutation_is_bijection_over_three_lowercase_chars() {
+        assert_bijection(26u128.pow(3));
+    }
+
+    #[test]
+    fn permutation_is_bijection_over_tiny_spaces() {
+        for space in [1, 2, 3, 5, 62] {
+            assert_bijection(space);
+        }
+    }
+
+    #[test]
+    fn permutation_depends_on_key() {
+        let space = 26u128.pow(3);
+        let first = KeyedPermutation::new("first-secret");
+        let second = KeyedPermutation::new("second-secret");
+
+        assert!((0..16).any(|value| first.permute(value, space) != second.permute(value, space)));
+    }
+}
@@ -0,0 +1,74 @@
+use async_trait::async_trait;
+use rand_core::{OsRng, TryRngCore};
+
+use crate::domain::code_generator::CodeGenerator;
+use crate::domain::entities::CodePolicy;
+use crate::error::AppError;
+
+/// Номер в конце кода: 0000-9999
+const NUMBER_RANGE: u16 = 10_000;
+
+/// Читаемый код `прилагательное-существительное-NNNN` (~164 млн вариантов).
+/// Длина и алфавит политики не применяются.
+pub struct WordCodeGenerator;
+
+#[async_trait]
+impl CodeGenerator for WordCodeGenerator {
+    async fn generate(&self, _domain_id: i64, _policy: &CodePolicy) -> Result<String, AppError> {
+        // Значения номера из хвоста u16 отбрасываем, чтобы номера были равновероятны
+        let limit = u16::MAX - u16::MAX % NUMBER_RANGE;
+        let mut buffer = [0u8; 4];
+
+        loop {
+            OsRng
+                .try_fill_bytes(&mut buffer)
+                .expect("Failed to generate random bytes");
+
+            let number = u16::from_be_bytes([buffer[2], buffer[3]]);
+            if number >= limit {
+                continue;
+            }
+
+            // Списки по 128 слов: младшие 7 бит байта дают равновероятный индекс
+            return Ok(format!(
+                "{}-{}-{:04}",
+                ADJECTIVES[(buffer[0] & 0x7f) as usize],
+                NOUNS[(buffer[1] & 0x7f) as usize],
+                number % NUMBER_RANGE
+            ));
+        }
+    }
+}
+
+const ADJECTIVES: [&str; 128] = [
+    "able", "agile", "amber", "ample", "azure", "bold", "brave", "breezy", "bright", "brisk",
+    "calm", "candid", "cheery", "chilly", "clever", "cosmic", "cozy", "crisp", "curly", "daring",
+    "dandy", "dapper", "deft", "eager", "early", "easy", "epic", "fair", "fancy", "fast", "fluffy",
+    "fond", "frank", "fresh", "frosty", "funny", "gentle", "giant", "glad", "golden", "grand",
+    "green", "happy", "hardy", "hazy", "hearty", "humble", "icy", "jolly", "jumpy", "keen", "kind",
+    "lively", "lucky", "lunar", "merry", "mellow", "mighty", "misty", "modest", "neat", "nimble",
+    "noble", "olive", "open", "peppy", "perky", "plucky", "polite", "proud", "quick", "quiet",
+    "rapid", "rare", "ready", "regal", "rosy", "royal", "rustic", "sandy", "sharp", "shiny",
+    "silent", "silky", "silver", "simple", "sleek", "smart", "smooth", "snowy", "social", "solar",
+    "solid", "sunny", "super", "sweet", "swift", "tidy", "tiny", "topaz", "tranquil", "true",
+    "trusty", "upbeat", "urban", "vast", "velvet", "vivid", "warm", "wavy", "wild", "windy",
+    "wise", "witty", "young", "zany", "zesty", "bouncy", "cool", "crimson", "dreamy", "fuzzy",
+    "glossy", "jazzy", "lofty", "loyal", "mystic", "nifty",
+];
+
+const NOUNS: [&str; 128] = [
+    "otter", "badger", "beaver", "bison", "camel", "cobra", "condor", "coyote", "crane", "dingo",
+    "dolphin", "eagle", "falcon", "ferret", "finch", "gecko", "gopher", "heron", "hippo", "husky",
+    "ibis", "iguana", "jackal", "jaguar", "koala", "lemur", "leopard", "llama", "lynx", "magpie",
+    "mole", "moose", "newt", "ocelot", "orca", "osprey", "owl", "panda", "parrot", "pelican",
+    "penguin", "puffin", "puma", "quail", "rabbit", "raven", "robin", "salmon", "seal", "shark",
+    "sloth", "snail", "sparrow", "squid", "stork", "swan", "tapir", "tiger", "toucan", "trout",
+    "turtle", "walrus", "weasel", "whale", "wolf", "wombat", "yak", "zebra", "acorn", "anchor",
+    "arrow", "atlas", "beacon", "bell", "breeze", "brook", "cactus", "canyon", "cedar", "cloud",
+    "comet", "coral", "crystal", "delta", "desert", "ember", "fern", "field", "forest", "galaxy",
+    "garden", "glacier", "harbor", "island", "jungle", "lagoon", "lantern", "maple", "meadow",
+    "meteor", "moon", "mountain", "nebula", "ocean", "orchid", "pebble", "planet", "prairie",
+    "quartz", "rainbow", "reef", "river", "rocket", "sail", "summit", "sunrise", "thunder",
+    "tulip", "valley", "willow", "bamboo", "birch", "cliff", "dune", "fjord", "geyser", "grove",
+    "hill",
+];
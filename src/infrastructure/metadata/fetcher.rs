@@ -0,0 +1,31 @@
+use async_trait::async_trait;
+
+use crate::domain::entities::PageMetadata;
+
+/// Ошибки получения метаданных страницы
+#[derive(Debug, thiserror::Error)]
+pub enum MetadataFetchError {
+    #[error("Invalid URL: {0}")]
+    InvalidUrl(String),
+
+    #[error("Target address is not allowed: {0}")]
+    Blocked(String),
+
+    #[error("Request failed: {0}")]
+    Request(String),
+
+    #[error("Unexpected HTTP status: {0}")]
+    Status(u16),
+
+    #[error("Response is not an HTML page: {0}")]
+    NotHtml(String),
+}
+
+pub type MetadataFetchResult<T> = Result<T, MetadataFetchError>;
+
+/// Трейт для загрузки метаданных страницы (позволяет подменить HTTP-клиент)
+#[async_trait]
+pub trait MetadataFetcher: Send + Sync {
+    /// Загрузить страницу и извлечь title / OpenGraph
+    async fn fetch(&self, url: &str) -> MetadataFetchResult<PageMetadata>;
+}
@@ -0,0 +1,412 @@
+use super::fetcher::{MetadataFetchError, MetadataFetchResult, MetadataFetcher};
+use crate::domain::entities::PageMetadata;
+use crate::utils::html_meta::extract_page_metadata;
+use async_trait::async_trait;
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use reqwest::header::{ACCEPT, CONTENT_TYPE};
+use reqwest::{Client, redirect};
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+use url::{Host, Url};
+
+/// Ограничения HTTP-клиента для загрузки страниц
+#[derive(Debug, Clone)]
+pub struct HttpMetadataFetcherConfig {
+    pub timeout: Duration,
+    pub max_bytes: usize,
+    pub max_redirects: usize,
+    /// Разрешить запросы к loopback/private адресам (для локальной разработки)
+    pub allow_private: bool,
+}
+
+/// Загрузка метаданных страниц через reqwest
+pub struct HttpMetadataFetcher {
+    client: Client,
+    max_bytes: usize,
+    allow_private: bool,
+}
+
+impl HttpMetadataFetcher {
+    pub fn new(config: HttpMetadataFetcherConfig) -> MetadataFetchResult<Self> {
+        let max_redirects = config.max_redirects;
+        let allow_private = config.allow_private;
+
+        let redirect_policy = redirect::Policy::custom(move |attempt| {
+            if attempt.previous().len() > max_redirects {
+                attempt.error("too many redirects")
+            } else if !allow_private && !is_allowed_url(attempt.url()) {
+                attempt.error("redirect to a non-public address")
+            } else {
+                attempt.follow()
+            }
+        });
+
+        let mut builder = Client::builder()
+            .timeout(config.timeout)
+            .connect_timeout(config.timeout)
+            .redirect(redirect_policy)
+            .user_agent(concat!(
+                "url-shortener/",
+                env!("CARGO_PKG_VERSION"),
+                " (link preview)"
+            ));
+
+        if !allow_private {
+            builder = builder.dns_resolver(Arc::new(PublicOnlyResolver));
+        }
+
+        let client = builder
+            .build()
+            .map_err(|e| MetadataFetchError::Request(e.to_string()))?;
+
+        Ok(Self {
+            client,
+            max_bytes: config.max_bytes,
+            allow_private,
+        })
+    }
+}
+
+#[async_trait]
+impl MetadataFetcher for HttpMetadataFetcher {
+    async fn fetch(&self, url: &str) -> MetadataFetchResult<PageMetadata> {
+        let url = Url::parse(url).map_err(|e| MetadataFetchError::InvalidUrl(e.to_string()))?;
+
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(MetadataFetchError::InvalidUrl(format!(
+                "unsupported scheme: {}",
+                url.scheme()
+            )));
+        }
+
+        if !self.allow_private && !is_allowed_url(&url) {
+            return Err(MetadataFetchError::Blocked(url.to_string()));
+        }
+
+        let mut response = self
+            .client
+            .get(url)
+            .header(ACCEPT, "text/html,application/xhtml+xml;q=0.9,*/*;q=0.1")
+            .send()
+            .await
+            .map_err(|e| MetadataFetchError::Request(e.to_string()))?;
+
+        if !response.status().is_success() {
+            return Err(MetadataFetchError::Status(response.status().as_u16()));
+        }
+
+        if let Some(content_type) = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            && !content_type.to_ascii_lowercase().contains("html")
+        {
+            return Err(MetadataFetchError::NotHtml(content_type.to_string()));
+        }
+
+        let final_url = response.url().clone();
+
+        // Читаем не больше max_bytes: метаданные находятся в <head>
+        let mut body = Vec::new();
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|e| MetadataFetchError::Request(e.to_string()))?
+        {
+            let remaining = self.max_bytes - body.len();
+            body.extend_from_slice(&chunk[..chunk.len().min(remaining)]);
+
+            if body.len() >= self.max_bytes {
+                break;
+            }
+        }
+
+        let html = String::from_utf8_lossy(&body);
+        Ok(extract_page_metadata(&html, &final_url))
+    }
+}
+
+/// DNS резолвер, отбрасывающий непубличные адреса (защита от SSRF)
+pub(crate) struct PublicOnlyResolver;
+
+impl Resolve for PublicOnlyResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let host = name.as_str().to_string();
+            let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host.as_str(), 0))
+                .await?
+                .filter(|addr| is_public_ip(addr.ip()))
+                .collect();
+
+            if addrs.is_empty() {
+                return Err(format!("{} has no public addresses", host).into());
+            }
+
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+/// IP-литералы в URL не проходят через резолвер, проверяем их отдельно
+pub(crate) fn is_allowed_url(url: &Url) -> bool {
+    match url.host() {
+        Some(Host::Ipv4(ip)) => is_public_ip(IpAddr::V4(ip)),
+        Some(Host::Ipv6(ip)) => is_public_ip(IpAddr::V6(ip)),
+        Some(Host::Domain(domain)) => {
+            let domain = domain.trim_end_matches('.').to_ascii_lowercase();
+            domain != "localhost" && !domain.ends_with(".localhost")
+        }
+        None => false,
+    }
+}
+
+fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => {
+            let [a, b, ..] = v4.octets();
+            !(v4.is_private()
+                || v4.is_loopback()
+                || v4.is_link_local()
+                || v4.is_unspecified()
+                || v4.is_broadcast()
+                || v4.is_documentation()
+                || v4.is_multicast()
+                // 100.64.0.0/10 (CGNAT)
+                || (a == 100 && (64..128).contains(&b))
+                // 0.0.0.0/8
+                || a == 0)
+        }
+        IpAddr::V6(v6) => {
+            if let Some(v4) = v6.to_ipv4_mapped() {
+                return is_public_ip(IpAddr::V4(v4));
+            }
+
+            let first = v6.segments()[0];
+            !(v6.is_loopback()
+                || v6.is_unspecified()
+                || v6.is_multicast()
+                // fc00::/7 (unique local)
+                || (first & 0xfe00) == 0xfc00
+                // fe80::/10 (link-local)
+                || (first & 0xffc0) == 0xfe80)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+
+    fn allowed(url: &str) -> bool {
+        is_allowed_url(&Url::parse(url).unwrap())
+    }
+
+    fn fetcher() -> HttpMetadataFetcher {
+        HttpMetadataFetcher::new(HttpMetadataFetcherConfig {
+            timeout: Duration::from_secs(1),
+            max_bytes: 1024,
+            max_redirects: 0,
+            allow_private: false,
+        })
+        .unwrap()
+    }
+
+    fn local_fetcher(
+        timeout: Duration,
+        max_bytes: usize,
+        max_redirects: usize,
+    ) -> HttpMetadataFetcher {
+        HttpMetadataFetcher::new(HttpMetadataFetcherConfig {
+            timeout,
+            max_bytes,
+            max_redirects,
+            allow_private: true,
+        })
+        .unwrap()
+    }
+
+    /// Локальный HTTP-сервер: на каждое соединение вызывает `respond` с путём запроса
+    async fn serve<H, Fut>(respond: H) -> String
+    where
+        H: Fn(TcpStream, String) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let respond = Arc::new(respond);
+
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let respond = respond.clone();
+                tokio::spawn(async move {
+                    let path = read_request_path(&mut stream).await;
+                    respond(stream, path).await;
+                });
+            }
+        });
+
+        base
+    }
+
+    async fn read_request_path(stream: &mut TcpStream) -> String {
+        let mut request = Vec::new();
+        let mut buf = [0u8; 1024];
+
+        while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+            let n = stream.read(&mut buf).await.unwrap();
+            if n == 0 {
+                break;
+            }
+            request.extend_from_slice(&buf[..n]);
+        }
+
+        String::from_utf8_lossy(&request)
+            .split_whitespace()
+            .nth(1)
+            .unwrap_or("/")
+            .to_string()
+    }
+
+    fn html_head(content_length: usize) -> String {
+        format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            content_length
+        )
+    }
+
+    #[test]
+    fn blocks_non_public_addresses() {
+        for url in [
+            "http://127.0.0.1/",
+            "http://10.0.0.5/",
+            "http://172.16.0.1/",
+            "http://192.168.1.1/",
+            "http://169.254.169.254/latest/meta-data/",
+            "http://100.64.0.1/",
+            "http://0.0.0.0/",
+            "http://[::1]/",
+            "http://[fd00::1]/",
+            "http://[fe80::1]/",
+            "http://[::ffff:127.0.0.1]/",
+            "http://localhost:8080/",
+            "http://api.localhost./",
+            "http://LOCALHOST/",
+        ] {
+            assert!(!allowed(url), "{} must be blocked", url);
+        }
+    }
+
+    #[test]
+    fn allows_public_addresses() {
+        for url in [
+            "https://example.com/page",
+            "http://93.184.216.34/",
+            "http://[2606:2800:220:1::1]/",
+            "https://localhost.example.com/",
+        ] {
+            assert!(allowed(url), "{} must be allowed", url);
+        }
+    }
+
+    #[tokio::test]
+    async fn fetch_rejects_blocked_target_before_request() {
+        let result = fetcher().fetch("http://127.0.0.1:9/admin").await;
+
+        assert!(matches!(result, Err(MetadataFetchError::Blocked(_))));
+    }
+
+    #[tokio::test]
+    async fn fetch_rejects_unsupported_scheme() {
+        let result = fetcher().fetch("file:///etc/passwd").await;
+
+        assert!(matches!(result, Err(MetadataFetchError::InvalidUrl(_))));
+    }
+
+    #[tokio::test]
+    async fn fetch_times_out_on_slow_body() {
+        let base = serve(|mut stream, _| async move {
+            let body = "<title>Slow</title>";
+            let _ = stream.write_all(html_head(body.len() * 2).as_bytes()).await;
+            let _ = stream.write_all(body.as_bytes()).await;
+            tokio::time::sleep(Duration::from_secs(5)).await;
+        })
+        .await;
+
+        let started = std::time::Instant::now();
+        let result = local_fetcher(Duration::from_millis(300), 1024, 0)
+            .fetch(&format!("{}/slow", base))
+            .await;
+
+        assert!(matches!(result, Err(MetadataFetchError::Request(_))));
+        assert!(started.elapsed() < Duration::from_secs(3));
+    }
+
+    #[tokio::test]
+    async fn fetch_reads_at_most_max_bytes() {
+        let base = serve(|mut stream, _| async move {
+            let body = format!(
+                "<html><head><title>Early</title>{}<meta property=\"og:title\" content=\"Late\"></head></html>",
+                " ".repeat(64 * 1024)
+            );
+            let _ = stream.write_all(html_head(body.len()).as_bytes()).await;
+            let _ = stream.write_all(body.as_bytes()).await;
+        })
+        .await;
+
+        let metadata = local_fetcher(Duration::from_secs(2), 1024, 0)
+            .fetch(&format!("{}/big", base))
+            .await
+            .unwrap();
+
+        assert_eq!(metadata.title.as_deref(), Some("Early"));
+        assert_eq!(metadata.og_title, None);
+    }
+
+    /// `/hop/N` перенаправляет на `/hop/N-1`, `/hop/0` отдаёт страницу
+    async fn redirect_chain_server() -> String {
+        serve(|mut stream, path| async move {
+            let hops: usize = path
+                .strip_prefix("/hop/")
+                .and_then(|n| n.parse().ok())
+                .unwrap_or(0);
+
+            let response = if hops == 0 {
+                let body = "<title>Landing</title>";
+                format!("{}{}", html_head(body.len()), body)
+            } else {
+                format!(
+                    "HTTP/1.1 302 Found\r\nLocation: /hop/{}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                    hops - 1
+                )
+            };
+            let _ = stream.write_all(response.as_bytes()).await;
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn fetch_follows_redirects_within_cap() {
+        let base = redirect_chain_server().await;
+
+        let metadata = local_fetcher(Duration::from_secs(2), 1024, 3)
+            .fetch(&format!("{}/hop/3", base))
+            .await
+            .unwrap();
+
+        assert_eq!(metadata.title.as_deref(), Some("Landing"));
+    }
+
+    #[tokio::test]
+    async fn fetch_stops_redirect_chain_longer_than_cap() {
+        let base = redirect_chain_server().await;
+
+        let result = local_fetcher(Duration::from_secs(2), 1024, 3)
+            .fetch(&format!("{}/hop/4", base))
+            .await;
+
+        assert!(matches!(result, Err(MetadataFetchError::Request(_))));
+    }
+}
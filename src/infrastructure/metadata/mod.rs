@@ -0,0 +1,10 @@
+//! Получение title и OpenGraph метаданных целевых страниц
+
+mod fetcher;
+mod http_fetcher;
+mod worker;
+
+pub use fetcher::{MetadataFetchError, MetadataFetchResult, MetadataFetcher};
+pub use http_fetcher::{HttpMetadataFetcher, HttpMetadataFetcherConfig};
+pub(crate) use http_fetcher::{PublicOnlyResolver, is_allowed_url};
+pub use worker::{PageMetadataStore, run_metadata_worker};
@@ -0,0 +1,183 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+use tokio::sync::{Semaphore, mpsc};
+
+use super::fetcher::MetadataFetcher;
+use crate::domain::entities::PageMetadata;
+use crate::domain::metadata_job::MetadataJob;
+use crate::domain::repositories::LinkRepository;
+use crate::error::AppError;
+
+/// Куда воркер сохраняет метаданные (единственное, что ему нужно от хранилища ссылок)
+#[async_trait]
+pub trait PageMetadataStore: Send + Sync {
+    async fn update_page_metadata(
+        &self,
+        link_id: i64,
+        metadata: &PageMetadata,
+    ) -> Result<(), AppError>;
+}
+
+#[async_trait]
+impl<L: LinkRepository> PageMetadataStore for L {
+    async fn update_page_metadata(
+        &self,
+        link_id: i64,
+        metadata: &PageMetadata,
+    ) -> Result<(), AppError> {
+        LinkRepository::update_page_metadata(self, link_id, metadata).await
+    }
+}
+
+/// Воркер для фонового получения метаданных страниц
+pub async fn run_metadata_worker<F, L>(
+    mut rx: mpsc::Receiver<MetadataJob>,
+    fetcher: Arc<F>,
+    link_repository: Arc<L>,
+    concurrency: usize,
+) where
+    F: MetadataFetcher + 'static,
+    L: PageMetadataStore + 'static,
+{
+    tracing::info!("Metadata worker started");
+
+    let semaphore = Arc::new(Semaphore::new(concurrency));
+
+    while let Some(job) = rx.recv().await {
+        metrics::counter!("metadata_worker_received_total").increment(1);
+
+        let Ok(permit) = semaphore.clone().acquire_owned().await else {
+            break;
+        };
+
+        let fetcher = fetcher.clone();
+        let link_repository = link_repository.clone();
+
+        tokio::spawn(async move {
+            let _permit = permit;
+            process_job(job, fetcher.as_ref(), link_repository.as_ref()).await;
+        });
+    }
+
+    tracing::info!("Metadata worker stopped");
+}
+
+async fn process_job<F, L>(job: MetadataJob, fetcher: &F, link_repository: &L)
+where
+    F: MetadataFetcher,
+    L: PageMetadataStore,
+{
+    let metadata = match fetcher.fetch(&job.url).await {
+        Ok(metadata) => metadata,
+        Err(e) => {
+            metrics::counter!("metadata_worker_failed_total").increment(1);
+            tracing::debug!(
+                link_id = job.link_id,
+                url = %job.url,
+                error = %e,
+                "Metadata worker: failed to fetch page"
+            );
+            return;
+        }
+    };
+
+    if metadata.is_empty() {
+        metrics::counter!("metadata_worker_empty_total").increment(1);
+    }
+
+    match link_repository
+        .update_page_metadata(job.link_id, &metadata)
+        .await
+    {
+        Ok(()) => {
+            metrics::counter!("metadata_worker_processed_total").increment(1);
+            tracing::debug!(link_id = job.link_id, "Page metadata stored");
+        }
+        Err(e) => {
+            metrics::counter!("metadata_worker_failed_total").increment(1);
+            tracing::error!(
+                link_id = job.link_id,
+                error = ?e,
+                "Metadata worker: failed to store page metadata"
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::metadata::{MetadataFetchError, MetadataFetchResult};
+    use crate::utils::html_meta::extract_page_metadata;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+    use url::Url;
+
+    /// Отдаёт заранее заданные страницы вместо HTTP
+    struct StaticPages(HashMap<&'static str, &'static str>);
+
+    #[async_trait]
+    impl MetadataFetcher for StaticPages {
+        async fn fetch(&self, url: &str) -> MetadataFetchResult<PageMetadata> {
+            let html = self.0.get(url).ok_or(MetadataFetchError::Status(404))?;
+            let page_url =
+                Url::parse(url).map_err(|e| MetadataFetchError::InvalidUrl(e.to_string()))?;
+            Ok(extract_page_metadata(html, &page_url))
+        }
+    }
+
+    /// Запоминает сохранённые метаданные
+    #[derive(Default)]
+    struct RecordingLinks(Mutex<Vec<(i64, PageMetadata)>>);
+
+    #[async_trait]
+    impl PageMetadataStore for RecordingLinks {
+        async fn update_page_metadata(
+            &self,
+            link_id: i64,
+            metadata: &PageMetadata,
+        ) -> Result<(), AppError> {
+            self.0.lock().unwrap().push((link_id, metadata.clone()));
+            Ok(())
+        }
+    }
+
+    fn pages() -> StaticPages {
+        StaticPages(HashMap::from([(
+            "https://example.com/post",
+            r#"<title>Post</title>
+               <meta property="og:title" content="Post on Example">
+               <meta property="og:image" content="/cover.png">"#,
+        )]))
+    }
+
+    #[tokio::test]
+    async fn stores_extracted_metadata() {
+        let links = RecordingLinks::default();
+        let job = MetadataJob::new(7, "https://example.com/post".to_string());
+
+        process_job(job, &pages(), &links).await;
+
+        let stored = links.0.lock().unwrap();
+        assert_eq!(stored.len(), 1);
+
+        let (link_id, metadata) = &stored[0];
+        assert_eq!(*link_id, 7);
+        assert_eq!(metadata.title.as_deref(), Some("Post"));
+        assert_eq!(metadata.og_title.as_deref(), Some("Post on Example"));
+        assert_eq!(
+            metadata.og_image.as_deref(),
+            Some("https://example.com/cover.png")
+        );
+    }
+
+    #[tokio::test]
+    async fn skips_links_whose_page_failed_to_load() {
+        let links = RecordingLinks::default();
+        let job = MetadataJob::new(8, "https://example.com/missing".to_string());
+
+        process_job(job, &pages(), &links).await;
+
+        assert!(links.0.lock().unwrap().is_empty());
+    }
+}
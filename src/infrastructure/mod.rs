@@ -1,2 +1,6 @@
 pub mod cache;
+pub mod code_generation;
+pub mod metadata;
 pub mod persistence;
+pub mod rate_limit;
+pub mod verification;
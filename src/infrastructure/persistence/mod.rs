@@ -1,9 +1,17 @@
 pub mod pg_domain_repository;
 pub mod pg_link_repository;
+pub mod pg_reserved_code_repository;
+pub mod pg_session_repository;
 pub mod pg_stats_repository;
+pub mod pg_tag_repository;
 pub mod pg_token_repository;
+pub mod pg_user_repository;
 
 pub use pg_domain_repository::PgDomainRepository;
 pub use pg_link_repository::PgLinkRepository;
+pub use pg_reserved_code_repository::PgReservedCodeRepository;
+pub use pg_session_repository::PgSessionRepository;
 pub use pg_stats_repository::PgStatsRepository;
+pub use pg_tag_repository::PgTagRepository;
 pub use pg_token_repository::PgTokenRepository;
+pub use pg_user_repository::PgUserRepository;
@@ -1,8 +1,13 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use sqlx::PgPool;
+use std::collections::HashMap;
 use std::sync::Arc;
 
-use crate::domain::entities::{Domain, NewDomain, UpdateDomain};
+use crate::domain::entities::{
+    CodePolicy, Domain, DomainAlias, DomainBranding, DomainRedirects, DomainVerification,
+    NewDomain, UpdateDomain, VerificationMethod,
+};
 use crate::domain::repositories::DomainRepository;
 use crate::error::AppError;
 use serde_json::json;
@@ -17,37 +22,147 @@ impl PgDomainRepository {
     }
 }
 
+/// Строка таблицы domains (набор колонок одинаков во всех запросах)
+struct DomainRow {
+    id: i64,
+    domain: String,
+    is_default: bool,
+    is_active: bool,
+    description: Option<String>,
+    logo_url: Option<String>,
+    not_found_message: Option<String>,
+    fallback_url: Option<String>,
+    root_redirect_url: Option<String>,
+    not_found_redirect_url: Option<String>,
+    verification_token: Option<String>,
+    verified_at: Option<DateTime<Utc>>,
+    verification_method: Option<String>,
+    verification_checked_at: Option<DateTime<Utc>>,
+    verification_error: Option<String>,
+    code_strategy: Option<String>,
+    code_length: i16,
+    code_alphabet: String,
+    custom_code_min_length: i16,
+    custom_code_max_length: i16,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+/// Строка таблицы domain_aliases
+struct AliasRow {
+    id: i64,
+    domain_id: i64,
+    host: String,
+    verification_token: Option<String>,
+    verified_at: Option<DateTime<Utc>>,
+    verification_method: Option<String>,
+    verification_checked_at: Option<DateTime<Utc>>,
+    verification_error: Option<String>,
+    created_at: DateTime<Utc>,
+}
+
+impl From<AliasRow> for DomainAlias {
+    fn from(row: AliasRow) -> Self {
+        Self {
+            id: row.id,
+            domain_id: row.domain_id,
+            host: row.host,
+            verification: DomainVerification {
+                token: row.verification_token,
+                verified_at: row.verified_at,
+                method: row
+                    .verification_method
+                    .and_then(|method| method.parse().ok()),
+                checked_at: row.verification_checked_at,
+                last_error: row.verification_error,
+            },
+            created_at: row.created_at,
+        }
+    }
+}
+
+impl From<DomainRow> for Domain {
+    fn from(row: DomainRow) -> Self {
+        Self {
+            id: row.id,
+            domain: row.domain,
+            is_default: row.is_default,
+            is_active: row.is_active,
+            description: row.description,
+            branding: DomainBranding {
+                logo_url: row.logo_url,
+                not_found_message: row.not_found_message,
+                fallback_url: row.fallback_url,
+            },
+            redirects: DomainRedirects {
+                root_url: row.root_redirect_url,
+                not_found_url: row.not_found_redirect_url,
+            },
+            verification: DomainVerification {
+                token: row.verification_token,
+                verified_at: row.verified_at,
+                method: row
+                    .verification_method
+                    .and_then(|method| method.parse().ok()),
+                checked_at: row.verification_checked_at,
+                last_error: row.verification_error,
+            },
+            code_policy: CodePolicy {
+                strategy: row.code_strategy.and_then(|strategy| strategy.parse().ok()),
+                length: row.code_length as usize,
+                alphabet: row.code_alphabet.parse().unwrap_or_default(),
+                custom_min_length: row.custom_code_min_length as usize,
+                custom_max_length: row.custom_code_max_length as usize,
+            },
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
 #[async_trait]
 impl DomainRepository for PgDomainRepository {
     async fn create(&self, new_domain: NewDomain) -> Result<Domain, AppError> {
-        let row = sqlx::query!(
+        let row = sqlx::query_as!(
+            DomainRow,
             r#"
-            INSERT INTO domains (domain, is_default, description)
-            VALUES ($1, $2, $3)
-            RETURNING id, domain, is_default, is_active, description, created_at, updated_at
+            INSERT INTO domains (
+                domain, is_default, description, is_active,
+                verification_token, verified_at, verification_method
+            )
+            VALUES (
+                $1, $2, $3, $4::text IS NULL,
+                $4, CASE WHEN $4::text IS NULL THEN NOW() END,
+                CASE WHEN $4::text IS NULL THEN 'manual' END
+            )
+            RETURNING id, domain, is_default, is_active, description,
+                      logo_url, not_found_message, fallback_url,
+                      root_redirect_url, not_found_redirect_url, verification_token, verified_at,
+                      verification_method, verification_checked_at, verification_error,
+                      code_strategy, code_length, code_alphabet, custom_code_min_length, custom_code_max_length,
+                      created_at, updated_at
             "#,
             new_domain.domain,
             new_domain.is_default,
-            new_domain.description
+            new_domain.description,
+            new_domain.verification_token
         )
         .fetch_one(self.pool.as_ref())
         .await?;
 
-        Ok(Domain::new(
-            row.id,
-            row.domain,
-            row.is_default,
-            row.is_active,
-            row.description,
-            row.created_at,
-            row.updated_at,
-        ))
+        Ok(row.into())
     }
 
     async fn find_by_id(&self, id: i64) -> Result<Option<Domain>, AppError> {
-        let row = sqlx::query!(
+        let row = sqlx::query_as!(
+            DomainRow,
             r#"
-            SELECT id, domain, is_default, is_active, description, created_at, updated_at
+            SELECT id, domain, is_default, is_active, description,
+                   logo_url, not_found_message, fallback_url,
+                   root_redirect_url, not_found_redirect_url, verification_token, verified_at,
+                   verification_method, verification_checked_at, verification_error,
+                   code_strategy, code_length, code_alphabet, custom_code_min_length, custom_code_max_length,
+                   created_at, updated_at
             FROM domains
             WHERE id = $1
             "#,
@@ -56,23 +171,19 @@ impl DomainRepository for PgDomainRepository {
         .fetch_optional(self.pool.as_ref())
         .await?;
 
-        Ok(row.map(|r| {
-            Domain::new(
-                r.id,
-                r.domain,
-                r.is_default,
-                r.is_active,
-                r.description,
-                r.created_at,
-                r.updated_at,
-            )
-        }))
+        Ok(row.map(Domain::from))
     }
 
     async fn find_by_name(&self, domain: &str) -> Result<Option<Domain>, AppError> {
-        let row = sqlx::query!(
+        let row = sqlx::query_as!(
+            DomainRow,
             r#"
-            SELECT id, domain, is_default, is_active, description, created_at, updated_at
+            SELECT id, domain, is_default, is_active, description,
+                   logo_url, not_found_message, fallback_url,
+                   root_redirect_url, not_found_redirect_url, verification_token, verified_at,
+                   verification_method, verification_checked_at, verification_error,
+                   code_strategy, code_length, code_alphabet, custom_code_min_length, custom_code_max_length,
+                   created_at, updated_at
             FROM domains
             WHERE domain = $1
             "#,
@@ -81,23 +192,19 @@ impl DomainRepository for PgDomainRepository {
         .fetch_optional(self.pool.as_ref())
         .await?;
 
-        Ok(row.map(|r| {
-            Domain::new(
-                r.id,
-                r.domain,
-                r.is_default,
-                r.is_active,
-                r.description,
-                r.created_at,
-                r.updated_at,
-            )
-        }))
+        Ok(row.map(Domain::from))
     }
 
     async fn get_default(&self) -> Result<Domain, AppError> {
-        let row = sqlx::query!(
+        let row = sqlx::query_as!(
+            DomainRow,
             r#"
-            SELECT id, domain, is_default, is_active, description, created_at, updated_at
+            SELECT id, domain, is_default, is_active, description,
+                   logo_url, not_found_message, fallback_url,
+                   root_redirect_url, not_found_redirect_url, verification_token, verified_at,
+                   verification_method, verification_checked_at, verification_error,
+                   code_strategy, code_length, code_alphabet, custom_code_min_length, custom_code_max_length,
+                   created_at, updated_at
             FROM domains
             WHERE is_default = TRUE
             LIMIT 1
@@ -106,18 +213,7 @@ impl DomainRepository for PgDomainRepository {
         .fetch_optional(self.pool.as_ref())
         .await?;
 
-        row.map(|r| {
-            Domain::new(
-                r.id,
-                r.domain,
-                r.is_default,
-                r.is_active,
-                r.description,
-                r.created_at,
-                r.updated_at,
-            )
-        })
-        .ok_or_else(|| {
+        row.map(Domain::from).ok_or_else(|| {
             AppError::internal(
                 "No default domain configured",
                 json!({"hint": "Run migrations or create a default domain"}),
@@ -126,9 +222,15 @@ impl DomainRepository for PgDomainRepository {
     }
 
     async fn list(&self, only_active: bool) -> Result<Vec<Domain>, AppError> {
-        let rows = sqlx::query!(
+        let rows = sqlx::query_as!(
+            DomainRow,
             r#"
-        SELECT id, domain, is_default, is_active, description, created_at, updated_at
+        SELECT id, domain, is_default, is_active, description,
+               logo_url, not_found_message, fallback_url,
+               root_redirect_url, not_found_redirect_url, verification_token, verified_at,
+               verification_method, verification_checked_at, verification_error,
+               code_strategy, code_length, code_alphabet, custom_code_min_length, custom_code_max_length,
+               created_at, updated_at
         FROM domains
         WHERE ($1::boolean IS NULL OR is_active = $1)
         ORDER BY is_default DESC, domain
@@ -138,49 +240,76 @@ impl DomainRepository for PgDomainRepository {
         .fetch_all(self.pool.as_ref())
         .await?;
 
-        Ok(rows
-            .into_iter()
-            .map(|r| {
-                Domain::new(
-                    r.id,
-                    r.domain,
-                    r.is_default,
-                    r.is_active,
-                    r.description,
-                    r.created_at,
-                    r.updated_at,
-                )
-            })
-            .collect())
+        Ok(rows.into_iter().map(Domain::from).collect())
     }
 
     async fn update(&self, id: i64, update: UpdateDomain) -> Result<Domain, AppError> {
-        let row = sqlx::query!(
+        let row = sqlx::query_as!(
+            DomainRow,
             r#"
             UPDATE domains
             SET
                 is_active = COALESCE($2, is_active),
                 description = COALESCE($3, description),
+                logo_url = CASE WHEN $4::text IS NULL THEN logo_url ELSE NULLIF($4, '') END,
+                not_found_message = CASE
+                    WHEN $5::text IS NULL THEN not_found_message ELSE NULLIF($5, '')
+                END,
+                fallback_url = CASE WHEN $6::text IS NULL THEN fallback_url ELSE NULLIF($6, '') END,
+                root_redirect_url = CASE
+                    WHEN $7::text IS NULL THEN root_redirect_url ELSE NULLIF($7, '')
+                END,
+                not_found_redirect_url = CASE
+                    WHEN $8::text IS NULL THEN not_found_redirect_url ELSE NULLIF($8, '')
+                END,
+                code_length = COALESCE($9, code_length),
+                code_alphabet = COALESCE($10, code_alphabet),
+                custom_code_min_length = COALESCE($11, custom_code_min_length),
+                custom_code_max_length = COALESCE($12, custom_code_max_length),
+                code_strategy = CASE
+                    WHEN $13::text IS NULL THEN code_strategy ELSE NULLIF($13, '')
+                END,
                 updated_at = NOW()
             WHERE id = $1
-            RETURNING id, domain, is_default, is_active, description, created_at, updated_at
+            RETURNING id, domain, is_default, is_active, description,
+                      logo_url, not_found_message, fallback_url,
+                      root_redirect_url, not_found_redirect_url, verification_token, verified_at,
+                      verification_method, verification_checked_at, verification_error,
+                      code_strategy, code_length, code_alphabet, custom_code_min_length, custom_code_max_length,
+                      created_at, updated_at
             "#,
             id,
             update.is_active,
-            update.description
+            update.description,
+            update.logo_url,
+            update.not_found_message,
+            update.fallback_url,
+            update.root_redirect_url,
+            update.not_found_redirect_url,
+            update.code_length.map(|length| length as i16),
+            update.code_alphabet.map(|alphabet| alphabet.as_str()),
+            update.custom_code_min_length.map(|length| length as i16),
+            update.custom_code_max_length.map(|length| length as i16),
+            update
+                .code_strategy
+                .map(|strategy| strategy.map_or("", |strategy| strategy.as_str()))
         )
         .fetch_one(self.pool.as_ref())
         .await?;
 
-        Ok(Domain::new(
-            row.id,
-            row.domain,
-            row.is_default,
-            row.is_active,
-            row.description,
-            row.created_at,
-            row.updated_at,
-        ))
+        Ok(row.into())
+    }
+
+    async fn rename(&self, id: i64, domain: &str) -> Result<(), AppError> {
+        sqlx::query!(
+            "UPDATE domains SET domain = $2, updated_at = NOW() WHERE id = $1",
+            id,
+            domain
+        )
+        .execute(self.pool.as_ref())
+        .await?;
+
+        Ok(())
     }
 
     async fn delete(&self, id: i64) -> Result<(), AppError> {
@@ -225,4 +354,242 @@ impl DomainRepository for PgDomainRepository {
 
         Ok(count.unwrap_or(0))
     }
+
+    async fn count_links_by_domain(&self) -> Result<HashMap<i64, i64>, AppError> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT domain_id AS "domain_id!", COUNT(*) AS "count!"
+            FROM links
+            WHERE domain_id IS NOT NULL
+            GROUP BY domain_id
+            "#
+        )
+        .fetch_all(self.pool.as_ref())
+        .await?;
+
+        Ok(rows.into_iter().map(|r| (r.domain_id, r.count)).collect())
+    }
+
+    async fn mark_verified(&self, id: i64, method: VerificationMethod) -> Result<Domain, AppError> {
+        let row = sqlx::query_as!(
+            DomainRow,
+            r#"
+            UPDATE domains
+            SET
+                verified_at = NOW(),
+                verification_method = $2,
+                verification_checked_at = NOW(),
+                verification_error = NULL,
+                is_active = TRUE
+            WHERE id = $1
+            RETURNING id, domain, is_default, is_active, description,
+                      logo_url, not_found_message, fallback_url,
+                      root_redirect_url, not_found_redirect_url, verification_token, verified_at,
+                      verification_method, verification_checked_at, verification_error,
+                      code_strategy, code_length, code_alphabet, custom_code_min_length, custom_code_max_length,
+                      created_at, updated_at
+            "#,
+            id,
+            method.as_str()
+        )
+        .fetch_one(self.pool.as_ref())
+        .await?;
+
+        Ok(row.into())
+    }
+
+    async fn record_verification_failure(&self, id: i64, error: &str) -> Result<(), AppError> {
+        sqlx::query!(
+            r#"
+            UPDATE domains
+            SET verification_checked_at = NOW(), verification_error = $2
+            WHERE id = $1
+            "#,
+            id,
+            error
+        )
+        .execute(self.pool.as_ref())
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find_by_host(
+        &self,
+        host: &str,
+        wildcards: &[String],
+    ) -> Result<Option<Domain>, AppError> {
+        // Приоритет: точное имя > алиас > самый длинный (точный) wildcard
+        let row = sqlx::query_as!(
+            DomainRow,
+            r#"
+            SELECT d.id, d.domain, d.is_default, d.is_active, d.description,
+                   d.logo_url, d.not_found_message, d.fallback_url,
+                   d.root_redirect_url, d.not_found_redirect_url, d.verification_token,
+                   d.verified_at, d.verification_method, d.verification_checked_at,
+                   d.verification_error, d.code_strategy, d.code_length, d.code_alphabet,
+                   d.custom_code_min_length, d.custom_code_max_length,
+                   d.created_at, d.updated_at
+            FROM domains d
+            LEFT JOIN domain_aliases a ON a.domain_id = d.id AND a.verified_at IS NOT NULL
+            WHERE d.domain = $1 OR a.host = $1 OR a.host = ANY($2)
+            ORDER BY
+                CASE WHEN d.domain = $1 THEN 0 WHEN a.host = $1 THEN 1 ELSE 2 END,
+                length(a.host) DESC
+            LIMIT 1
+            "#,
+            host,
+            wildcards
+        )
+        .fetch_optional(self.pool.as_ref())
+        .await?;
+
+        Ok(row.map(Domain::from))
+    }
+
+    async fn list_aliases(&self, domain_id: i64) -> Result<Vec<DomainAlias>, AppError> {
+        let rows = sqlx::query_as!(
+            AliasRow,
+            r#"
+            SELECT id, domain_id, host, verification_token, verified_at, verification_method,
+                   verification_checked_at, verification_error, created_at
+            FROM domain_aliases
+            WHERE domain_id = $1
+            ORDER BY host
+            "#,
+            domain_id
+        )
+        .fetch_all(self.pool.as_ref())
+        .await?;
+
+        Ok(rows.into_iter().map(DomainAlias::from).collect())
+    }
+
+    async fn list_all_aliases(&self) -> Result<Vec<DomainAlias>, AppError> {
+        let rows = sqlx::query_as!(
+            AliasRow,
+            r#"
+            SELECT id, domain_id, host, verification_token, verified_at, verification_method,
+                   verification_checked_at, verification_error, created_at
+            FROM domain_aliases
+            ORDER BY host
+            "#
+        )
+        .fetch_all(self.pool.as_ref())
+        .await?;
+
+        Ok(rows.into_iter().map(DomainAlias::from).collect())
+    }
+
+    async fn find_alias(&self, host: &str) -> Result<Option<DomainAlias>, AppError> {
+        let row = sqlx::query_as!(
+            AliasRow,
+            r#"
+            SELECT id, domain_id, host, verification_token, verified_at, verification_method,
+                   verification_checked_at, verification_error, created_at
+            FROM domain_aliases
+            WHERE host = $1
+            "#,
+            host
+        )
+        .fetch_optional(self.pool.as_ref())
+        .await?;
+
+        Ok(row.map(DomainAlias::from))
+    }
+
+    async fn add_alias(
+        &self,
+        domain_id: i64,
+        host: &str,
+        verification_token: Option<&str>,
+    ) -> Result<DomainAlias, AppError> {
+        let row = sqlx::query_as!(
+            AliasRow,
+            r#"
+            INSERT INTO domain_aliases (domain_id, host, verification_token, verified_at, verification_method)
+            VALUES (
+                $1, $2, $3,
+                CASE WHEN $3::text IS NULL THEN NOW() END,
+                CASE WHEN $3::text IS NULL THEN 'manual' END
+            )
+            RETURNING id, domain_id, host, verification_token, verified_at, verification_method,
+                      verification_checked_at, verification_error, created_at
+            "#,
+            domain_id,
+            host,
+            verification_token
+        )
+        .fetch_one(self.pool.as_ref())
+        .await?;
+
+        Ok(row.into())
+    }
+
+    async fn mark_alias_verified(
+        &self,
+        id: i64,
+        method: VerificationMethod,
+    ) -> Result<DomainAlias, AppError> {
+        let row = sqlx::query_as!(
+            AliasRow,
+            r#"
+            UPDATE domain_aliases
+            SET
+                verified_at = NOW(),
+                verification_method = $2,
+                verification_checked_at = NOW(),
+                verification_error = NULL
+            WHERE id = $1
+            RETURNING id, domain_id, host, verification_token, verified_at, verification_method,
+                      verification_checked_at, verification_error, created_at
+            "#,
+            id,
+            method.as_str()
+        )
+        .fetch_one(self.pool.as_ref())
+        .await?;
+
+        Ok(row.into())
+    }
+
+    async fn record_alias_verification_failure(
+        &self,
+        id: i64,
+        error: &str,
+    ) -> Result<(), AppError> {
+        sqlx::query!(
+            r#"
+            UPDATE domain_aliases
+            SET verification_checked_at = NOW(), verification_error = $2
+            WHERE id = $1
+            "#,
+            id,
+            error
+        )
+        .execute(self.pool.as_ref())
+        .await?;
+
+        Ok(())
+    }
+
+    async fn rename_alias(&self, id: i64, host: &str) -> Result<(), AppError> {
+        sqlx::query!(
+            "UPDATE domain_aliases SET host = $2 WHERE id = $1",
+            id,
+            host
+        )
+        .execute(self.pool.as_ref())
+        .await?;
+
+        Ok(())
+    }
+
+    async fn remove_alias(&self, id: i64) -> Result<(), AppError> {
+        sqlx::query!("DELETE FROM domain_aliases WHERE id = $1", id)
+            .execute(self.pool.as_ref())
+            .await?;
+
+        Ok(())
+    }
 }
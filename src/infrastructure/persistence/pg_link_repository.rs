@@ -1,10 +1,12 @@
 use async_trait::async_trait;
+use chrono::NaiveDate;
 use sqlx::PgPool;
 use std::sync::Arc;
 
-use crate::domain::entities::{Link, NewLink};
+use crate::domain::entities::{CodePolicy, Link, LinkMetadata, NewLink, PageMetadata};
 use crate::domain::repositories::LinkRepository;
 use crate::error::AppError;
+use crate::infrastructure::persistence::pg_tag_repository::insert_link_tags;
 
 pub struct PgLinkRepository {
     pool: Arc<PgPool>,
@@ -18,43 +20,95 @@ impl PgLinkRepository {
 
 #[async_trait]
 impl LinkRepository for PgLinkRepository {
-    async fn create(&self, new_link: NewLink) -> Result<Link, AppError> {
+    async fn create(&self, new_link: NewLink) -> Result<Option<Link>, AppError> {
+        let mut tx = self.pool.begin().await?;
+
         let row = sqlx::query!(
             r#"
         WITH inserted AS (
-            INSERT INTO links (code, long_url, domain_id)
-            VALUES ($1, $2, $3)
-            RETURNING id, code, long_url, domain_id, created_at
+            INSERT INTO links (code, long_url, domain_id, title, notes, created_by, owner_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (code, COALESCE(domain_id, 0)) DO NOTHING
+            RETURNING id, code, long_url, domain_id, created_at, title, notes, created_by, owner_id,
+                      og_title, og_description, og_image
         )
         SELECT
             i.id,
             i.code,
             i.long_url,
             d.domain,
-            i.created_at
+            i.created_at,
+            i.title,
+            i.notes,
+            i.created_by,
+            i.owner_id,
+            i.og_title,
+            i.og_description,
+            i.og_image
         FROM inserted i
         LEFT JOIN domains d ON d.id = i.domain_id
         "#,
             new_link.code,
             new_link.long_url,
-            new_link.domain_id
+            new_link.domain_id,
+            new_link.metadata.title,
+            new_link.metadata.notes,
+            new_link.metadata.created_by,
+            new_link.metadata.owner_id
         )
-        .fetch_one(self.pool.as_ref())
+        .fetch_optional(&mut *tx)
         .await?;
 
-        Ok(Link::new(
+        let Some(row) = row else {
+            return Ok(None);
+        };
+
+        // Теги - в той же транзакции: ошибка не оставит ссылку без них
+        insert_link_tags(&mut tx, row.id, &new_link.tags).await?;
+        tx.commit().await?;
+
+        Ok(Some(Link::new(
             row.id,
             row.code,
             row.long_url,
             row.domain,
             row.created_at,
-        ))
+            LinkMetadata {
+                title: row.title,
+                notes: row.notes,
+                created_by: row.created_by,
+                owner_id: row.owner_id,
+                og_title: row.og_title,
+                og_description: row.og_description,
+                og_image: row.og_image,
+            },
+        )))
+    }
+
+    async fn next_code_number(&self, domain_id: i64, policy: &CodePolicy) -> Result<i64, AppError> {
+        let number = sqlx::query_scalar!(
+            r#"
+            INSERT INTO link_code_sequences (domain_id, alphabet, length, next_number)
+            VALUES ($1, $2, $3, 1)
+            ON CONFLICT (domain_id, alphabet, length)
+            DO UPDATE SET next_number = link_code_sequences.next_number + 1
+            RETURNING next_number - 1 AS "number!"
+            "#,
+            domain_id,
+            policy.alphabet.as_str(),
+            policy.length as i16
+        )
+        .fetch_one(self.pool.as_ref())
+        .await?;
+
+        Ok(number)
     }
 
     async fn find_by_code(&self, code: &str, domain_id: i64) -> Result<Option<Link>, AppError> {
         let row = sqlx::query!(
             r#"
-            SELECT l.id, l.code, l.long_url, d.domain as "domain?", l.created_at
+            SELECT l.id, l.code, l.long_url, d.domain as "domain?", l.created_at,
+                   l.title, l.notes, l.created_by, l.owner_id, l.og_title, l.og_description, l.og_image
             FROM links l
             LEFT JOIN domains d ON d.id = l.domain_id
             WHERE code = $1 AND domain_id = $2
@@ -65,28 +119,74 @@ impl LinkRepository for PgLinkRepository {
         .fetch_optional(self.pool.as_ref())
         .await?;
 
-        Ok(row.map(|r| Link::new(r.id, r.code, r.long_url, r.domain, r.created_at)))
+        Ok(row.map(|r| {
+            Link::new(
+                r.id,
+                r.code,
+                r.long_url,
+                r.domain,
+                r.created_at,
+                LinkMetadata {
+                    title: r.title,
+                    notes: r.notes,
+                    created_by: r.created_by,
+                    owner_id: r.owner_id,
+                    og_title: r.og_title,
+                    og_description: r.og_description,
+                    og_image: r.og_image,
+                },
+            )
+        }))
     }
 
     async fn find_by_long_url(
         &self,
         long_url: &str,
         domain_id: i64,
+        metadata: &LinkMetadata,
     ) -> Result<Option<Link>, AppError> {
         let row = sqlx::query!(
             r#"
-            SELECT l.id, l.code, l.long_url, d.domain as "domain?", l.created_at
+            SELECT l.id, l.code, l.long_url, d.domain as "domain?", l.created_at,
+                   l.title, l.notes, l.created_by, l.owner_id, l.og_title, l.og_description, l.og_image
             FROM links l
             LEFT JOIN domains d ON d.id = l.domain_id
-            WHERE long_url = $1 AND domain_id = $2
+            WHERE l.long_url = $1 AND l.domain_id = $2
+              AND l.created_by IS NOT DISTINCT FROM $3
+              AND l.owner_id IS NOT DISTINCT FROM $4
+              AND l.title IS NOT DISTINCT FROM $5
+              AND l.notes IS NOT DISTINCT FROM $6
+            ORDER BY l.id
+            LIMIT 1
             "#,
             long_url,
-            domain_id
+            domain_id,
+            metadata.created_by,
+            metadata.owner_id,
+            metadata.title,
+            metadata.notes
         )
         .fetch_optional(self.pool.as_ref())
         .await?;
 
-        Ok(row.map(|r| Link::new(r.id, r.code, r.long_url, r.domain, r.created_at)))
+        Ok(row.map(|r| {
+            Link::new(
+                r.id,
+                r.code,
+                r.long_url,
+                r.domain,
+                r.created_at,
+                LinkMetadata {
+                    title: r.title,
+                    notes: r.notes,
+                    created_by: r.created_by,
+                    owner_id: r.owner_id,
+                    og_title: r.og_title,
+                    og_description: r.og_description,
+                    og_image: r.og_image,
+                },
+            )
+        }))
     }
 
     async fn list(
@@ -94,44 +194,175 @@ impl LinkRepository for PgLinkRepository {
         page: i64,
         page_size: i64,
         domain_id: Option<i64>,
+        tag: Option<&str>,
     ) -> Result<Vec<Link>, AppError> {
         let offset = (page - 1) * page_size;
 
         // ИСПРАВЛЕНО: один запрос с условием
         let rows = sqlx::query!(
             r#"
-        SELECT l.id, l.code, l.long_url, d.domain as "domain?", l.created_at
+        SELECT l.id, l.code, l.long_url, d.domain as "domain?", l.created_at,
+               l.title, l.notes, l.created_by, l.owner_id, l.og_title, l.og_description, l.og_image
         FROM links l
         LEFT JOIN domains d ON d.id = l.domain_id
         WHERE ($1::bigint IS NULL OR domain_id = $1)
+          AND ($4::text IS NULL OR EXISTS (
+              SELECT 1
+              FROM link_tags lt
+              JOIN tags t ON t.id = lt.tag_id
+              WHERE lt.link_id = l.id AND t.name = $4
+          ))
         ORDER BY created_at DESC
         LIMIT $2 OFFSET $3
         "#,
             domain_id,
             page_size,
-            offset
+            offset,
+            tag
         )
         .fetch_all(self.pool.as_ref())
         .await?;
 
         Ok(rows
             .into_iter()
-            .map(|r| Link::new(r.id, r.code, r.long_url, r.domain, r.created_at))
+            .map(|r| {
+                Link::new(
+                    r.id,
+                    r.code,
+                    r.long_url,
+                    r.domain,
+                    r.created_at,
+                    LinkMetadata {
+                        title: r.title,
+                        notes: r.notes,
+                        created_by: r.created_by,
+                        owner_id: r.owner_id,
+                        og_title: r.og_title,
+                        og_description: r.og_description,
+                        og_image: r.og_image,
+                    },
+                )
+            })
             .collect())
     }
 
     // ДОБАВЛЕНО: реализация count
-    async fn count(&self, domain_id: Option<i64>) -> Result<i64, AppError> {
-        let count = if let Some(domain_id) = domain_id {
-            sqlx::query_scalar!("SELECT COUNT(*) FROM links WHERE domain_id = $1", domain_id)
-                .fetch_one(self.pool.as_ref())
-                .await?
-        } else {
-            sqlx::query_scalar!("SELECT COUNT(*) FROM links")
-                .fetch_one(self.pool.as_ref())
-                .await?
-        };
+    async fn count(&self, domain_id: Option<i64>, tag: Option<&str>) -> Result<i64, AppError> {
+        let count = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*)
+            FROM links l
+            WHERE ($1::bigint IS NULL OR l.domain_id = $1)
+              AND ($2::text IS NULL OR EXISTS (
+                  SELECT 1
+                  FROM link_tags lt
+                  JOIN tags t ON t.id = lt.tag_id
+                  WHERE lt.link_id = l.id AND t.name = $2
+              ))
+            "#,
+            domain_id,
+            tag
+        )
+        .fetch_one(self.pool.as_ref())
+        .await?;
 
         Ok(count.unwrap_or(0))
     }
+
+    async fn reserve_daily_quota(
+        &self,
+        token_id: i64,
+        day: NaiveDate,
+        count: i64,
+        limit: i64,
+    ) -> Result<Option<i64>, AppError> {
+        // Строка счётчика блокируется на время UPDATE, поэтому параллельные
+        // запросы токена не могут вместе превысить квоту
+        let used = sqlx::query_scalar!(
+            r#"
+            INSERT INTO token_daily_usage AS u (token_id, day, links_created)
+            SELECT $1, $2, $3::bigint
+            WHERE $3::bigint <= $4::bigint
+            ON CONFLICT (token_id, day) DO UPDATE
+            SET links_created = u.links_created + EXCLUDED.links_created
+            WHERE u.links_created + EXCLUDED.links_created <= $4::bigint
+            RETURNING links_created
+            "#,
+            token_id,
+            day,
+            count,
+            limit
+        )
+        .fetch_optional(self.pool.as_ref())
+        .await?;
+
+        Ok(used)
+    }
+
+    async fn release_daily_quota(
+        &self,
+        token_id: i64,
+        day: NaiveDate,
+        count: i64,
+    ) -> Result<i64, AppError> {
+        let used = sqlx::query_scalar!(
+            r#"
+            UPDATE token_daily_usage
+            SET links_created = GREATEST(links_created - $3, 0)
+            WHERE token_id = $1 AND day = $2
+            RETURNING links_created
+            "#,
+            token_id,
+            day,
+            count
+        )
+        .fetch_optional(self.pool.as_ref())
+        .await?;
+
+        Ok(used.unwrap_or(0))
+    }
+
+    async fn daily_quota_used(&self, token_id: i64, day: NaiveDate) -> Result<i64, AppError> {
+        let used = sqlx::query_scalar!(
+            r#"
+            SELECT links_created
+            FROM token_daily_usage
+            WHERE token_id = $1 AND day = $2
+            "#,
+            token_id,
+            day
+        )
+        .fetch_optional(self.pool.as_ref())
+        .await?;
+
+        Ok(used.unwrap_or(0))
+    }
+
+    async fn update_page_metadata(
+        &self,
+        link_id: i64,
+        metadata: &PageMetadata,
+    ) -> Result<(), AppError> {
+        sqlx::query!(
+            r#"
+            UPDATE links
+            SET
+                title = COALESCE(title, $2),
+                og_title = $3,
+                og_description = $4,
+                og_image = $5,
+                metadata_fetched_at = NOW()
+            WHERE id = $1
+            "#,
+            link_id,
+            metadata.title,
+            metadata.og_title,
+            metadata.og_description,
+            metadata.og_image
+        )
+        .execute(self.pool.as_ref())
+        .await?;
+
+        Ok(())
+    }
 }
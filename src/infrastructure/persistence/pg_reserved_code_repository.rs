@@ -0,0 +1,99 @@
+use async_trait::async_trait;
+use sqlx::PgPool;
+use std::sync::Arc;
+
+use crate::domain::entities::ReservedCode;
+use crate::domain::repositories::ReservedCodeRepository;
+use crate::error::AppError;
+
+/// PostgreSQL реализация репозитория зарезервированных кодов
+pub struct PgReservedCodeRepository {
+    pool: Arc<PgPool>,
+}
+
+impl PgReservedCodeRepository {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ReservedCodeRepository for PgReservedCodeRepository {
+    async fn is_reserved(&self, code: &str, domain_id: i64) -> Result<bool, AppError> {
+        let reserved = sqlx::query_scalar!(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM reserved_codes
+                WHERE code = lower($1) AND (domain_id IS NULL OR domain_id = $2)
+            ) AS "reserved!"
+            "#,
+            code,
+            domain_id
+        )
+        .fetch_one(self.pool.as_ref())
+        .await?;
+
+        Ok(reserved)
+    }
+
+    async fn list_codes(&self) -> Result<Vec<(String, Option<i64>)>, AppError> {
+        let rows = sqlx::query!("SELECT code, domain_id FROM reserved_codes")
+            .fetch_all(self.pool.as_ref())
+            .await?;
+
+        Ok(rows.into_iter().map(|r| (r.code, r.domain_id)).collect())
+    }
+
+    async fn list(&self) -> Result<Vec<ReservedCode>, AppError> {
+        let codes = sqlx::query_as!(
+            ReservedCode,
+            r#"
+            SELECT r.id, r.code, d.domain AS "domain?", r.created_at
+            FROM reserved_codes r
+            LEFT JOIN domains d ON d.id = r.domain_id
+            ORDER BY d.domain NULLS FIRST, r.code
+            "#
+        )
+        .fetch_all(self.pool.as_ref())
+        .await?;
+
+        Ok(codes)
+    }
+
+    async fn add(&self, code: &str, domain_id: Option<i64>) -> Result<ReservedCode, AppError> {
+        let reserved = sqlx::query_as!(
+            ReservedCode,
+            r#"
+            WITH inserted AS (
+                INSERT INTO reserved_codes (code, domain_id)
+                VALUES (lower($1), $2)
+                RETURNING id, code, domain_id, created_at
+            )
+            SELECT i.id, i.code, d.domain AS "domain?", i.created_at
+            FROM inserted i
+            LEFT JOIN domains d ON d.id = i.domain_id
+            "#,
+            code,
+            domain_id
+        )
+        .fetch_one(self.pool.as_ref())
+        .await?;
+
+        Ok(reserved)
+    }
+
+    async fn remove(&self, code: &str, domain_id: Option<i64>) -> Result<bool, AppError> {
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM reserved_codes
+            WHERE code = lower($1) AND domain_id IS NOT DISTINCT FROM $2
+            "#,
+            code,
+            domain_id
+        )
+        .execute(self.pool.as_ref())
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
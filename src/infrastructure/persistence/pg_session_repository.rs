@@ -0,0 +1,137 @@
+use async_trait::async_trait;
+use sqlx::PgPool;
+use std::sync::Arc;
+
+use crate::domain::repositories::{DashboardSession, NewDashboardSession, SessionRepository};
+use crate::error::AppError;
+
+/// PostgreSQL реализация репозитория сессий дашборда
+pub struct PgSessionRepository {
+    pool: Arc<PgPool>,
+}
+
+impl PgSessionRepository {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl SessionRepository for PgSessionRepository {
+    async fn create(&self, session: NewDashboardSession) -> Result<DashboardSession, AppError> {
+        let row = sqlx::query!(
+            r#"
+            INSERT INTO dashboard_sessions (
+                session_hash, token_id, user_id, expires_at, ip, user_agent
+            )
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING id, token_id, user_id, created_at, expires_at, last_seen_at, revoked_at
+            "#,
+            session.session_hash,
+            session.token_id,
+            session.user_id,
+            session.expires_at,
+            session.ip,
+            session.user_agent
+        )
+        .fetch_one(self.pool.as_ref())
+        .await?;
+
+        Ok(DashboardSession {
+            id: row.id,
+            token_id: row.token_id,
+            user_id: row.user_id,
+            created_at: row.created_at,
+            expires_at: row.expires_at,
+            last_seen_at: row.last_seen_at,
+            revoked_at: row.revoked_at,
+        })
+    }
+
+    async fn find_active_by_hash(
+        &self,
+        session_hash: &str,
+    ) -> Result<Option<DashboardSession>, AppError> {
+        let row = sqlx::query!(
+            r#"
+            SELECT id, token_id, user_id, created_at, expires_at, last_seen_at, revoked_at
+            FROM dashboard_sessions
+            WHERE session_hash = $1
+              AND revoked_at IS NULL
+              AND expires_at > NOW()
+            "#,
+            session_hash
+        )
+        .fetch_optional(self.pool.as_ref())
+        .await?;
+
+        Ok(row.map(|r| DashboardSession {
+            id: r.id,
+            token_id: r.token_id,
+            user_id: r.user_id,
+            created_at: r.created_at,
+            expires_at: r.expires_at,
+            last_seen_at: r.last_seen_at,
+            revoked_at: r.revoked_at,
+        }))
+    }
+
+    async fn touch(&self, id: i64) -> Result<(), AppError> {
+        // Не чаще раза в минуту, чтобы не писать в БД на каждый запрос
+        sqlx::query!(
+            r#"
+            UPDATE dashboard_sessions
+            SET last_seen_at = NOW()
+            WHERE id = $1 AND last_seen_at < NOW() - INTERVAL '1 minute'
+            "#,
+            id
+        )
+        .execute(self.pool.as_ref())
+        .await?;
+
+        Ok(())
+    }
+
+    async fn revoke(&self, session_hash: &str) -> Result<(), AppError> {
+        sqlx::query!(
+            r#"
+            UPDATE dashboard_sessions
+            SET revoked_at = NOW()
+            WHERE session_hash = $1 AND revoked_at IS NULL
+            "#,
+            session_hash
+        )
+        .execute(self.pool.as_ref())
+        .await?;
+
+        Ok(())
+    }
+
+    async fn revoke_for_user(&self, user_id: i64) -> Result<(), AppError> {
+        sqlx::query!(
+            r#"
+            UPDATE dashboard_sessions
+            SET revoked_at = NOW()
+            WHERE user_id = $1 AND revoked_at IS NULL
+            "#,
+            user_id
+        )
+        .execute(self.pool.as_ref())
+        .await?;
+
+        Ok(())
+    }
+
+    async fn delete_expired(&self) -> Result<u64, AppError> {
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM dashboard_sessions
+            WHERE expires_at <= NOW() OR revoked_at IS NOT NULL
+            "#
+        )
+        .execute(self.pool.as_ref())
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
@@ -2,7 +2,7 @@ use async_trait::async_trait;
 use sqlx::PgPool;
 use std::sync::Arc;
 
-use crate::domain::entities::{Click, Link, NewClick};
+use crate::domain::entities::{Click, Link, LinkMetadata, NewClick};
 use crate::domain::repositories::{DetailedStats, LinkStats, StatsFilter, StatsRepository};
 use crate::error::AppError;
 
@@ -51,9 +51,32 @@ impl StatsRepository for PgStatsRepository {
     ) -> Result<Option<DetailedStats>, AppError> {
         let link_row = sqlx::query!(
             r#"
-            SELECT l.id, l.code, l.long_url, d.domain as "domain?", l.created_at
+            SELECT
+                l.id,
+                l.code,
+                l.long_url,
+                d.domain as "domain?",
+                l.created_at,
+                l.title,
+                l.notes,
+                l.created_by,
+                l.owner_id,
+                l.og_title,
+                l.og_description,
+                l.og_image,
+                tok.name as "created_by_name?",
+                u.username as "owner_name?",
+                ARRAY(
+                    SELECT t.name
+                    FROM link_tags lt
+                    JOIN tags t ON t.id = lt.tag_id
+                    WHERE lt.link_id = l.id
+                    ORDER BY t.name
+                ) as "tags!"
             FROM links l
             LEFT JOIN domains d ON d.id = l.domain_id
+            LEFT JOIN api_tokens tok ON tok.id = l.created_by
+            LEFT JOIN users u ON u.id = l.owner_id
             WHERE code = $1 AND ($2::bigint IS NULL OR domain_id = $2)
             "#,
             code,
@@ -73,6 +96,15 @@ impl StatsRepository for PgStatsRepository {
             link_row.long_url,
             link_row.domain,
             link_row.created_at,
+            LinkMetadata {
+                title: link_row.title,
+                notes: link_row.notes,
+                created_by: link_row.created_by,
+                owner_id: link_row.owner_id,
+                og_title: link_row.og_title,
+                og_description: link_row.og_description,
+                og_image: link_row.og_image,
+            },
         );
 
         // Подсчитываем общее количество кликов с учётом фильтров по дате
@@ -105,10 +137,19 @@ impl StatsRepository for PgStatsRepository {
             .map(|r| Click::new(r.id, r.link_id, r.clicked_at, r.user_agent, r.referer, r.ip))
             .collect();
 
-        Ok(Some(DetailedStats { link, total, items }))
+        Ok(Some(DetailedStats {
+            link,
+            tags: link_row.tags,
+            created_by_name: link_row.created_by_name,
+            owner_name: link_row.owner_name,
+            total,
+            items,
+        }))
     }
 
     async fn get_all_stats(&self, filter: StatsFilter) -> Result<Vec<LinkStats>, AppError> {
+        let search = filter.search.as_deref().map(like_pattern);
+
         let rows = sqlx::query!(
             r#"
             SELECT
@@ -116,16 +157,44 @@ impl StatsRepository for PgStatsRepository {
                 l.code,
                 l.long_url,
                 l.created_at,
+                l.title,
+                l.notes,
+                l.created_by,
+                tok.name as "created_by_name?",
+                u.username as "owner_name?",
                 d.domain as "domain?",
-                COUNT(lc.id) as "clicks!"
+                COUNT(lc.id) as "clicks!",
+                ARRAY(
+                    SELECT t.name
+                    FROM link_tags lt
+                    JOIN tags t ON t.id = lt.tag_id
+                    WHERE lt.link_id = l.id
+                    ORDER BY t.name
+                ) as "tags!"
             FROM links l
             LEFT JOIN link_clicks lc ON l.id = lc.link_id
                 AND ($1::timestamptz IS NULL OR lc.clicked_at >= $1)
                 AND ($2::timestamptz IS NULL OR lc.clicked_at <= $2)
             LEFT JOIN domains d ON d.id = l.domain_id
+            LEFT JOIN api_tokens tok ON tok.id = l.created_by
+            LEFT JOIN users u ON u.id = l.owner_id
             WHERE ($5::bigint IS NULL OR l.domain_id = $5)
-            GROUP BY l.id, l.code, l.long_url, l.created_at, d.domain
-            ORDER BY l.created_at DESC
+              AND ($6::text IS NULL OR l.long_url ILIKE $6 OR l.code ILIKE $6)
+              AND ($9::text IS NULL OR EXISTS (
+                  SELECT 1
+                  FROM link_tags lt
+                  JOIN tags t ON t.id = lt.tag_id
+                  WHERE lt.link_id = l.id AND t.name = $9
+              ))
+            GROUP BY l.id, d.domain, tok.name, u.username
+            ORDER BY
+                CASE WHEN $7 = 'clicks' AND $8 = 'asc' THEN COUNT(lc.id) END ASC,
+                CASE WHEN $7 = 'clicks' AND $8 = 'desc' THEN COUNT(lc.id) END DESC,
+                CASE WHEN $7 = 'code' AND $8 = 'asc' THEN l.code END ASC,
+                CASE WHEN $7 = 'code' AND $8 = 'desc' THEN l.code END DESC,
+                CASE WHEN $7 = 'created_at' AND $8 = 'asc' THEN l.created_at END ASC,
+                CASE WHEN $7 = 'created_at' AND $8 = 'desc' THEN l.created_at END DESC,
+                l.id DESC
             LIMIT $3 OFFSET $4
             "#,
             filter.from_date,
@@ -133,6 +202,10 @@ impl StatsRepository for PgStatsRepository {
             filter.limit,
             filter.offset,
             filter.domain_id,
+            search,
+            filter.sort_by.as_str(),
+            filter.sort_dir.as_str(),
+            filter.tag,
         )
         .fetch_all(self.pool.as_ref())
         .await?;
@@ -146,16 +219,35 @@ impl StatsRepository for PgStatsRepository {
                 long_url: r.long_url,
                 total: r.clicks,
                 created_at: r.created_at,
+                tags: r.tags,
+                title: r.title,
+                notes: r.notes,
+                created_by: r.created_by,
+                created_by_name: r.created_by_name,
+                owner_name: r.owner_name,
             })
             .collect())
     }
 
-    async fn count_all_links(&self) -> Result<i64, AppError> {
+    async fn count_all_links(&self, filter: &StatsFilter) -> Result<i64, AppError> {
+        let search = filter.search.as_deref().map(like_pattern);
+
         let row = sqlx::query!(
             r#"
             SELECT COUNT(*) as count
-            FROM links
-            "#
+            FROM links l
+            WHERE ($1::bigint IS NULL OR l.domain_id = $1)
+              AND ($2::text IS NULL OR l.long_url ILIKE $2 OR l.code ILIKE $2)
+              AND ($3::text IS NULL OR EXISTS (
+                  SELECT 1
+                  FROM link_tags lt
+                  JOIN tags t ON t.id = lt.tag_id
+                  WHERE lt.link_id = l.id AND t.name = $3
+              ))
+            "#,
+            filter.domain_id,
+            search,
+            filter.tag,
         )
         .fetch_one(self.pool.as_ref())
         .await?;
@@ -187,3 +279,12 @@ impl StatsRepository for PgStatsRepository {
         Ok(row.count.unwrap_or(0))
     }
 }
+
+/// Шаблон для ILIKE: подстрока с экранированными спецсимволами
+fn like_pattern(search: &str) -> String {
+    let escaped = search
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    format!("%{}%", escaped)
+}
@@ -0,0 +1,102 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{PgConnection, PgPool};
+use std::sync::Arc;
+
+use crate::domain::repositories::{TagRepository, TagStats};
+use crate::error::AppError;
+
+/// Привязать теги к ссылке (недостающие теги создаются). Принимает соединение,
+/// чтобы `PgLinkRepository::create` мог вызвать её в своей транзакции
+pub(crate) async fn insert_link_tags(
+    conn: &mut PgConnection,
+    link_id: i64,
+    tags: &[String],
+) -> Result<(), AppError> {
+    if tags.is_empty() {
+        return Ok(());
+    }
+
+    sqlx::query!(
+        r#"
+        WITH input AS (
+            SELECT DISTINCT unnest($2::text[]) AS name
+        ),
+        -- DO UPDATE возвращает id и для тега, который параллельно создала другая
+        -- транзакция (DO NOTHING его не вернул бы, а снимок запроса его не видит)
+        all_tags AS (
+            INSERT INTO tags (name)
+            SELECT name FROM input ORDER BY name
+            ON CONFLICT (name) DO UPDATE SET name = EXCLUDED.name
+            RETURNING id
+        )
+        INSERT INTO link_tags (link_id, tag_id)
+        SELECT $1, id FROM all_tags
+        ON CONFLICT DO NOTHING
+        "#,
+        link_id,
+        tags
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+/// PostgreSQL реализация репозитория тегов
+pub struct PgTagRepository {
+    pool: Arc<PgPool>,
+}
+
+impl PgTagRepository {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl TagRepository for PgTagRepository {
+    async fn attach_tags(&self, link_id: i64, tags: &[String]) -> Result<(), AppError> {
+        let mut conn = self.pool.acquire().await?;
+        insert_link_tags(&mut conn, link_id, tags).await
+    }
+
+    async fn list_stats(
+        &self,
+        domain_id: Option<i64>,
+        from_date: Option<DateTime<Utc>>,
+        to_date: Option<DateTime<Utc>>,
+    ) -> Result<Vec<TagStats>, AppError> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                t.name,
+                COUNT(DISTINCT l.id) as "links_count!",
+                COUNT(lc.id) as "total_clicks!"
+            FROM tags t
+            LEFT JOIN link_tags lt ON lt.tag_id = t.id
+            LEFT JOIN links l ON l.id = lt.link_id
+            LEFT JOIN link_clicks lc ON lc.link_id = l.id
+                AND ($2::timestamptz IS NULL OR lc.clicked_at >= $2)
+                AND ($3::timestamptz IS NULL OR lc.clicked_at <= $3)
+            WHERE $1::bigint IS NULL OR l.domain_id = $1
+            GROUP BY t.id, t.name
+            ORDER BY t.name
+            "#,
+            domain_id,
+            from_date,
+            to_date
+        )
+        .fetch_all(self.pool.as_ref())
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| TagStats {
+                name: r.name,
+                links_count: r.links_count,
+                total_clicks: r.total_clicks,
+            })
+            .collect())
+    }
+}
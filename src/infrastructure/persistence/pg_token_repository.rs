@@ -1,8 +1,10 @@
 use async_trait::async_trait;
-use sqlx::PgPool;
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Postgres, Transaction};
 use std::sync::Arc;
 
-use crate::domain::repositories::{ApiToken, TokenRepository};
+use crate::domain::entities::Scope;
+use crate::domain::repositories::{ApiToken, NewApiToken, TokenLimits, TokenRepository};
 use crate::error::AppError;
 
 /// PostgreSQL реализация репозитория токенов
@@ -16,22 +18,116 @@ impl PgTokenRepository {
     }
 }
 
+/// Строка таблицы api_tokens вместе с привязанными доменами
+struct TokenRow {
+    id: i64,
+    name: String,
+    token_hash: String,
+    created_at: DateTime<Utc>,
+    revoked_at: Option<DateTime<Utc>>,
+    scopes: Vec<String>,
+    expires_at: Option<DateTime<Utc>>,
+    last_used_at: Option<DateTime<Utc>>,
+    restrict_domains: bool,
+    rate_limit_per_minute: Option<i32>,
+    daily_link_quota: Option<i32>,
+    lookup_id: Option<String>,
+    hash_version: i16,
+    user_id: Option<i64>,
+    domain_ids: Vec<i64>,
+}
+
+impl From<TokenRow> for ApiToken {
+    fn from(row: TokenRow) -> Self {
+        Self {
+            id: row.id,
+            name: row.name,
+            token_hash: row.token_hash,
+            created_at: row.created_at,
+            revoked_at: row.revoked_at,
+            scopes: parse_scopes(row.scopes),
+            expires_at: row.expires_at,
+            last_used_at: row.last_used_at,
+            allowed_domain_ids: row.restrict_domains.then_some(row.domain_ids),
+            limits: token_limits(row.rate_limit_per_minute, row.daily_link_quota),
+            lookup_id: row.lookup_id,
+            legacy_hash: row.hash_version == 1,
+            user_id: row.user_id,
+        }
+    }
+}
+
 #[async_trait]
 impl TokenRepository for PgTokenRepository {
-    async fn validate_token(&self, token_hash: &str) -> Result<bool, AppError> {
-        let row = sqlx::query!(
+    async fn find_active_by_hash(&self, token_hash: &str) -> Result<Option<ApiToken>, AppError> {
+        let row = sqlx::query_as!(
+            TokenRow,
             r#"
-            SELECT id
-            FROM api_tokens
-            WHERE token_hash = $1
-              AND revoked_at IS NULL
+            SELECT t.id, t.name, t.token_hash, t.created_at, t.revoked_at, t.scopes,
+                   t.expires_at, t.last_used_at, t.restrict_domains,
+                   t.rate_limit_per_minute, t.daily_link_quota, t.lookup_id, t.hash_version,
+                   t.user_id,
+                   ARRAY(
+                       SELECT atd.domain_id FROM api_token_domains atd
+                       WHERE atd.token_id = t.id ORDER BY atd.domain_id
+                   ) AS "domain_ids!"
+            FROM api_tokens t
+            WHERE t.token_hash = $1
+              AND t.hash_version = 2
+              AND t.revoked_at IS NULL
+              AND (t.expires_at IS NULL OR t.expires_at > NOW())
             "#,
             token_hash
         )
         .fetch_optional(self.pool.as_ref())
         .await?;
 
-        Ok(row.is_some())
+        Ok(row.map(ApiToken::from))
+    }
+
+    async fn find_active_by_legacy_hash(
+        &self,
+        legacy_hash: &str,
+    ) -> Result<Option<ApiToken>, AppError> {
+        let row = sqlx::query_as!(
+            TokenRow,
+            r#"
+            SELECT t.id, t.name, t.token_hash, t.created_at, t.revoked_at, t.scopes,
+                   t.expires_at, t.last_used_at, t.restrict_domains,
+                   t.rate_limit_per_minute, t.daily_link_quota, t.lookup_id, t.hash_version,
+                   t.user_id,
+                   ARRAY(
+                       SELECT atd.domain_id FROM api_token_domains atd
+                       WHERE atd.token_id = t.id ORDER BY atd.domain_id
+                   ) AS "domain_ids!"
+            FROM api_tokens t
+            WHERE t.token_hash = $1
+              AND t.hash_version = 1
+              AND t.revoked_at IS NULL
+              AND (t.expires_at IS NULL OR t.expires_at > NOW())
+            "#,
+            legacy_hash
+        )
+        .fetch_optional(self.pool.as_ref())
+        .await?;
+
+        Ok(row.map(ApiToken::from))
+    }
+
+    async fn upgrade_hash(&self, id: i64, token_hash: &str) -> Result<(), AppError> {
+        sqlx::query!(
+            r#"
+            UPDATE api_tokens
+            SET token_hash = $2, hash_version = 2
+            WHERE id = $1 AND hash_version = 1
+            "#,
+            id,
+            token_hash
+        )
+        .execute(self.pool.as_ref())
+        .await?;
+
+        Ok(())
     }
 
     async fn update_last_used(&self, token_hash: &str) -> Result<(), AppError> {
@@ -50,91 +146,151 @@ impl TokenRepository for PgTokenRepository {
         Ok(())
     }
 
-    async fn create_token(&self, name: &str, token_hash: &str) -> Result<ApiToken, AppError> {
-        let row = sqlx::query!(
+    async fn create_token(&self, token: NewApiToken) -> Result<ApiToken, AppError> {
+        let mut tx = self.pool.begin().await?;
+        let created = insert_token(&mut tx, token).await?;
+        tx.commit().await?;
+
+        Ok(created)
+    }
+
+    async fn rotate_token(
+        &self,
+        id: i64,
+        token: NewApiToken,
+        old_expires_at: DateTime<Utc>,
+    ) -> Result<ApiToken, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        // Старый токен доживает окно перекрытия (но не дольше своего срока)
+        sqlx::query!(
             r#"
-            INSERT INTO api_tokens (name, token_hash)
-            VALUES ($1, $2)
-            RETURNING id, name, token_hash, created_at, revoked_at
+            UPDATE api_tokens
+            SET expires_at = LEAST(COALESCE(expires_at, $2), $2)
+            WHERE id = $1 AND revoked_at IS NULL
             "#,
-            name,
-            token_hash
+            id,
+            old_expires_at
         )
-        .fetch_one(self.pool.as_ref())
+        .execute(&mut *tx)
         .await?;
 
-        Ok(ApiToken {
-            id: row.id,
-            name: row.name,
-            token_hash: row.token_hash,
-            created_at: row.created_at,
-            revoked_at: row.revoked_at,
-        })
+        let created = insert_token(&mut tx, token).await?;
+        tx.commit().await?;
+
+        Ok(created)
     }
 
     async fn list_tokens(&self) -> Result<Vec<ApiToken>, AppError> {
-        let rows = sqlx::query!(
+        let rows = sqlx::query_as!(
+            TokenRow,
             r#"
-            SELECT id, name, token_hash, created_at, revoked_at
-            FROM api_tokens
-            ORDER BY created_at DESC
+            SELECT t.id, t.name, t.token_hash, t.created_at, t.revoked_at, t.scopes,
+                   t.expires_at, t.last_used_at, t.restrict_domains,
+                   t.rate_limit_per_minute, t.daily_link_quota, t.lookup_id, t.hash_version,
+                   t.user_id,
+                   ARRAY(
+                       SELECT atd.domain_id FROM api_token_domains atd
+                       WHERE atd.token_id = t.id ORDER BY atd.domain_id
+                   ) AS "domain_ids!"
+            FROM api_tokens t
+            ORDER BY t.created_at DESC
             "#
         )
         .fetch_all(self.pool.as_ref())
         .await?;
 
-        Ok(rows
-            .into_iter()
-            .map(|row| ApiToken {
-                id: row.id,
-                name: row.name,
-                token_hash: row.token_hash,
-                created_at: row.created_at,
-                revoked_at: row.revoked_at,
-            })
-            .collect())
+        Ok(rows.into_iter().map(ApiToken::from).collect())
     }
 
     async fn find_by_id(&self, id: i64) -> Result<Option<ApiToken>, AppError> {
-        let row = sqlx::query!(
+        let row = sqlx::query_as!(
+            TokenRow,
             r#"
-            SELECT id, name, token_hash, created_at, revoked_at
-            FROM api_tokens
-            WHERE id = $1
+            SELECT t.id, t.name, t.token_hash, t.created_at, t.revoked_at, t.scopes,
+                   t.expires_at, t.last_used_at, t.restrict_domains,
+                   t.rate_limit_per_minute, t.daily_link_quota, t.lookup_id, t.hash_version,
+                   t.user_id,
+                   ARRAY(
+                       SELECT atd.domain_id FROM api_token_domains atd
+                       WHERE atd.token_id = t.id ORDER BY atd.domain_id
+                   ) AS "domain_ids!"
+            FROM api_tokens t
+            WHERE t.id = $1
             "#,
             id
         )
         .fetch_optional(self.pool.as_ref())
         .await?;
 
-        Ok(row.map(|r| ApiToken {
-            id: r.id,
-            name: r.name,
-            token_hash: r.token_hash,
-            created_at: r.created_at,
-            revoked_at: r.revoked_at,
-        }))
+        Ok(row.map(ApiToken::from))
+    }
+
+    async fn find_by_lookup_id(&self, lookup_id: &str) -> Result<Option<ApiToken>, AppError> {
+        let row = sqlx::query_as!(
+            TokenRow,
+            r#"
+            SELECT t.id, t.name, t.token_hash, t.created_at, t.revoked_at, t.scopes,
+                   t.expires_at, t.last_used_at, t.restrict_domains,
+                   t.rate_limit_per_minute, t.daily_link_quota, t.lookup_id, t.hash_version,
+                   t.user_id,
+                   ARRAY(
+                       SELECT atd.domain_id FROM api_token_domains atd
+                       WHERE atd.token_id = t.id ORDER BY atd.domain_id
+                   ) AS "domain_ids!"
+            FROM api_tokens t
+            WHERE t.lookup_id = $1
+            "#,
+            lookup_id
+        )
+        .fetch_optional(self.pool.as_ref())
+        .await?;
+
+        Ok(row.map(ApiToken::from))
     }
 
     async fn find_by_name(&self, name: &str) -> Result<Option<ApiToken>, AppError> {
-        let row = sqlx::query!(
+        // После ротации имя повторяется - берём действующий и самый новый
+        let row = sqlx::query_as!(
+            TokenRow,
             r#"
-            SELECT id, name, token_hash, created_at, revoked_at
-            FROM api_tokens
-            WHERE name = $1
+            SELECT t.id, t.name, t.token_hash, t.created_at, t.revoked_at, t.scopes,
+                   t.expires_at, t.last_used_at, t.restrict_domains,
+                   t.rate_limit_per_minute, t.daily_link_quota, t.lookup_id, t.hash_version,
+                   t.user_id,
+                   ARRAY(
+                       SELECT atd.domain_id FROM api_token_domains atd
+                       WHERE atd.token_id = t.id ORDER BY atd.domain_id
+                   ) AS "domain_ids!"
+            FROM api_tokens t
+            WHERE t.name = $1
+            ORDER BY (t.revoked_at IS NULL) DESC, t.created_at DESC
+            LIMIT 1
             "#,
             name
         )
         .fetch_optional(self.pool.as_ref())
         .await?;
 
-        Ok(row.map(|r| ApiToken {
-            id: r.id,
-            name: r.name,
-            token_hash: r.token_hash,
-            created_at: r.created_at,
-            revoked_at: r.revoked_at,
-        }))
+        Ok(row.map(ApiToken::from))
+    }
+
+    async fn update_limits(&self, id: i64, limits: TokenLimits) -> Result<(), AppError> {
+        sqlx::query!(
+            r#"
+            UPDATE api_tokens
+            SET rate_limit_per_minute = $2,
+                daily_link_quota = $3
+            WHERE id = $1
+            "#,
+            id,
+            limits.requests_per_minute.map(|v| v as i32),
+            limits.daily_link_quota.map(|v| v as i32)
+        )
+        .execute(self.pool.as_ref())
+        .await?;
+
+        Ok(())
     }
 
     async fn revoke_token(&self, id: i64) -> Result<(), AppError> {
@@ -152,3 +308,72 @@ impl TokenRepository for PgTokenRepository {
         Ok(())
     }
 }
+
+/// Вставка токена вместе с ограничением по доменам
+async fn insert_token(
+    tx: &mut Transaction<'_, Postgres>,
+    token: NewApiToken,
+) -> Result<ApiToken, AppError> {
+    let scopes = scope_names(&token.scopes);
+
+    let row = sqlx::query_as!(
+        TokenRow,
+        r#"
+        INSERT INTO api_tokens (
+            name, token_hash, scopes, expires_at, restrict_domains,
+            rate_limit_per_minute, daily_link_quota, lookup_id, user_id
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        RETURNING id, name, token_hash, created_at, revoked_at, scopes, expires_at, last_used_at,
+                  restrict_domains, rate_limit_per_minute, daily_link_quota, lookup_id,
+                  hash_version, user_id, '{}'::bigint[] AS "domain_ids!"
+        "#,
+        token.name,
+        token.token_hash,
+        &scopes,
+        token.expires_at,
+        token.allowed_domain_ids.is_some(),
+        token.limits.requests_per_minute.map(|v| v as i32),
+        token.limits.daily_link_quota.map(|v| v as i32),
+        token.lookup_id,
+        token.user_id
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    if let Some(domain_ids) = &token.allowed_domain_ids {
+        sqlx::query!(
+            r#"
+            INSERT INTO api_token_domains (token_id, domain_id)
+            SELECT $1, UNNEST($2::bigint[])
+            ON CONFLICT DO NOTHING
+            "#,
+            row.id,
+            domain_ids
+        )
+        .execute(&mut **tx)
+        .await?;
+    }
+
+    // Домены только что вставлены - берём их из запроса, а не из RETURNING
+    Ok(ApiToken {
+        allowed_domain_ids: token.allowed_domain_ids,
+        ..row.into()
+    })
+}
+
+fn token_limits(requests_per_minute: Option<i32>, daily_link_quota: Option<i32>) -> TokenLimits {
+    TokenLimits {
+        requests_per_minute: requests_per_minute.map(|v| v as u32),
+        daily_link_quota: daily_link_quota.map(|v| v as u32),
+    }
+}
+
+fn scope_names(scopes: &[Scope]) -> Vec<String> {
+    scopes.iter().map(|s| s.as_str().to_string()).collect()
+}
+
+/// Неизвестные значения пропускаем (БД ограничивает их CHECK-констрейнтом)
+fn parse_scopes(raw: Vec<String>) -> Vec<Scope> {
+    raw.iter().filter_map(|s| s.parse().ok()).collect()
+}
@@ -0,0 +1,239 @@
+use async_trait::async_trait;
+use sqlx::PgPool;
+use std::sync::Arc;
+
+use crate::domain::entities::{NewUser, Role, User};
+use crate::domain::repositories::UserRepository;
+use crate::error::AppError;
+
+/// PostgreSQL реализация репозитория пользователей
+pub struct PgUserRepository {
+    pool: Arc<PgPool>,
+}
+
+impl PgUserRepository {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl UserRepository for PgUserRepository {
+    async fn create(&self, user: NewUser) -> Result<User, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        let row = sqlx::query!(
+            r#"
+            INSERT INTO users (username, password_hash, role)
+            VALUES ($1, $2, $3)
+            RETURNING id, username, password_hash, role, created_at, updated_at,
+                      disabled_at, last_login_at
+            "#,
+            user.username,
+            user.password_hash,
+            user.role.as_str()
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO user_domains (user_id, domain_id)
+            SELECT $1, UNNEST($2::bigint[])
+            ON CONFLICT DO NOTHING
+            "#,
+            row.id,
+            &user.domain_ids
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(User {
+            id: row.id,
+            username: row.username,
+            password_hash: row.password_hash,
+            role: parse_role(&row.role),
+            domain_ids: user.domain_ids,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+            disabled_at: row.disabled_at,
+            last_login_at: row.last_login_at,
+        })
+    }
+
+    async fn find_by_id(&self, id: i64) -> Result<Option<User>, AppError> {
+        let row = sqlx::query!(
+            r#"
+            SELECT u.id, u.username, u.password_hash, u.role, u.created_at, u.updated_at,
+                   u.disabled_at, u.last_login_at,
+                   ARRAY(
+                       SELECT ud.domain_id FROM user_domains ud
+                       WHERE ud.user_id = u.id ORDER BY ud.domain_id
+                   ) AS "domain_ids!"
+            FROM users u
+            WHERE u.id = $1
+            "#,
+            id
+        )
+        .fetch_optional(self.pool.as_ref())
+        .await?;
+
+        Ok(row.map(|r| User {
+            id: r.id,
+            username: r.username,
+            password_hash: r.password_hash,
+            role: parse_role(&r.role),
+            domain_ids: r.domain_ids,
+            created_at: r.created_at,
+            updated_at: r.updated_at,
+            disabled_at: r.disabled_at,
+            last_login_at: r.last_login_at,
+        }))
+    }
+
+    async fn find_by_username(&self, username: &str) -> Result<Option<User>, AppError> {
+        let row = sqlx::query!(
+            r#"
+            SELECT u.id, u.username, u.password_hash, u.role, u.created_at, u.updated_at,
+                   u.disabled_at, u.last_login_at,
+                   ARRAY(
+                       SELECT ud.domain_id FROM user_domains ud
+                       WHERE ud.user_id = u.id ORDER BY ud.domain_id
+                   ) AS "domain_ids!"
+            FROM users u
+            WHERE u.username = $1
+            "#,
+            username
+        )
+        .fetch_optional(self.pool.as_ref())
+        .await?;
+
+        Ok(row.map(|r| User {
+            id: r.id,
+            username: r.username,
+            password_hash: r.password_hash,
+            role: parse_role(&r.role),
+            domain_ids: r.domain_ids,
+            created_at: r.created_at,
+            updated_at: r.updated_at,
+            disabled_at: r.disabled_at,
+            last_login_at: r.last_login_at,
+        }))
+    }
+
+    async fn list(&self) -> Result<Vec<User>, AppError> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT u.id, u.username, u.password_hash, u.role, u.created_at, u.updated_at,
+                   u.disabled_at, u.last_login_at,
+                   ARRAY(
+                       SELECT ud.domain_id FROM user_domains ud
+                       WHERE ud.user_id = u.id ORDER BY ud.domain_id
+                   ) AS "domain_ids!"
+            FROM users u
+            ORDER BY u.username
+            "#
+        )
+        .fetch_all(self.pool.as_ref())
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| User {
+                id: r.id,
+                username: r.username,
+                password_hash: r.password_hash,
+                role: parse_role(&r.role),
+                domain_ids: r.domain_ids,
+                created_at: r.created_at,
+                updated_at: r.updated_at,
+                disabled_at: r.disabled_at,
+                last_login_at: r.last_login_at,
+            })
+            .collect())
+    }
+
+    async fn update_password(&self, id: i64, password_hash: &str) -> Result<(), AppError> {
+        sqlx::query!(
+            r#"
+            UPDATE users
+            SET password_hash = $2, updated_at = NOW()
+            WHERE id = $1
+            "#,
+            id,
+            password_hash
+        )
+        .execute(self.pool.as_ref())
+        .await?;
+
+        Ok(())
+    }
+
+    async fn update_role(&self, id: i64, role: Role, domain_ids: &[i64]) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query!(
+            r#"
+            UPDATE users
+            SET role = $2, updated_at = NOW()
+            WHERE id = $1
+            "#,
+            id,
+            role.as_str()
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!("DELETE FROM user_domains WHERE user_id = $1", id)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO user_domains (user_id, domain_id)
+            SELECT $1, UNNEST($2::bigint[])
+            ON CONFLICT DO NOTHING
+            "#,
+            id,
+            domain_ids
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn set_disabled(&self, id: i64, disabled: bool) -> Result<(), AppError> {
+        sqlx::query!(
+            r#"
+            UPDATE users
+            SET disabled_at = CASE WHEN $2 THEN COALESCE(disabled_at, NOW()) END,
+                updated_at = NOW()
+            WHERE id = $1
+            "#,
+            id,
+            disabled
+        )
+        .execute(self.pool.as_ref())
+        .await?;
+
+        Ok(())
+    }
+
+    async fn update_last_login(&self, id: i64) -> Result<(), AppError> {
+        sqlx::query!("UPDATE users SET last_login_at = NOW() WHERE id = $1", id)
+            .execute(self.pool.as_ref())
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// Неизвестные значения невозможны (CHECK-констрейнт), по умолчанию - минимальные права
+fn parse_role(raw: &str) -> Role {
+    raw.parse().unwrap_or(Role::Viewer)
+}
@@ -0,0 +1,169 @@
+use super::service::{RateLimit, RateLimitDecision, RateLimiter};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Как часто вычищать устаревшие ключи (в проверках)
+const CLEANUP_EVERY: u64 = 1024;
+
+/// In-process GCRA: состояние - "theoretical arrival time" на ключ
+pub struct InMemoryRateLimiter {
+    state: Mutex<State>,
+}
+
+struct State {
+    tats: HashMap<String, Instant>,
+    checks: u64,
+}
+
+impl InMemoryRateLimiter {
+    pub fn new() -> Self {
+        Self {
+            state: Mutex::new(State {
+                tats: HashMap::new(),
+                checks: 0,
+            }),
+        }
+    }
+
+    /// `commit` - сохранить запрос (false - только проверить)
+    fn check_at(
+        &self,
+        key: &str,
+        limit: RateLimit,
+        now: Instant,
+        commit: bool,
+    ) -> RateLimitDecision {
+        let interval = limit.emission_interval();
+        let burst_offset = interval * limit.burst;
+
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+
+        state.checks += 1;
+        if state.checks.is_multiple_of(CLEANUP_EVERY) {
+            state.tats.retain(|_, tat| *tat > now);
+        }
+
+        let tat = state.tats.get(key).copied().unwrap_or(now).max(now);
+        let new_tat = tat + interval;
+
+        // Запрос разрешён, если new_tat не дальше burst_offset от текущего момента
+        let allow_at = new_tat.checked_sub(burst_offset).unwrap_or(now);
+        if allow_at > now {
+            return RateLimitDecision {
+                allowed: false,
+                remaining: 0,
+                retry_after: allow_at - now,
+            };
+        }
+
+        if commit {
+            state.tats.insert(key.to_string(), new_tat);
+        }
+
+        let slack = now - allow_at;
+        RateLimitDecision {
+            allowed: true,
+            remaining: (slack.as_nanos() / interval.as_nanos().max(1)) as u32,
+            retry_after: Duration::ZERO,
+        }
+    }
+}
+
+impl Default for InMemoryRateLimiter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl RateLimiter for InMemoryRateLimiter {
+    async fn check(&self, key: &str, limit: RateLimit) -> RateLimitDecision {
+        self.check_at(key, limit, Instant::now(), true)
+    }
+
+    async fn peek(&self, key: &str, limit: RateLimit) -> RateLimitDecision {
+        self.check_at(key, limit, Instant::now(), false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 60 в минуту - один запрос в секунду, до 3 подряд
+    const LIMIT: RateLimit = RateLimit {
+        per_minute: 60,
+        burst: 3,
+    };
+
+    #[test]
+    fn allows_burst_then_rejects() {
+        let limiter = InMemoryRateLimiter::new();
+        let now = Instant::now();
+
+        let remaining: Vec<u32> = (0..3)
+            .map(|_| {
+                let decision = limiter.check_at("key", LIMIT, now, true);
+                assert!(decision.allowed);
+                decision.remaining
+            })
+            .collect();
+        assert_eq!(remaining, [2, 1, 0]);
+
+        let rejected = limiter.check_at("key", LIMIT, now, true);
+        assert!(!rejected.allowed);
+        assert_eq!(rejected.remaining, 0);
+        assert_eq!(rejected.retry_after, Duration::from_secs(1));
+    }
+
+    #[test]
+    fn refills_one_request_per_interval() {
+        let limiter = InMemoryRateLimiter::new();
+        let start = Instant::now();
+
+        for _ in 0..3 {
+            assert!(limiter.check_at("key", LIMIT, start, true).allowed);
+        }
+
+        let early = limiter.check_at("key", LIMIT, start + Duration::from_millis(400), true);
+        assert!(!early.allowed);
+        assert_eq!(early.retry_after, Duration::from_millis(600));
+
+        let later = start + Duration::from_secs(1);
+        assert!(limiter.check_at("key", LIMIT, later, true).allowed);
+        assert!(!limiter.check_at("key", LIMIT, later, true).allowed);
+
+        // После долгой паузы доступен весь burst, но не больше
+        let idle = later + Duration::from_secs(60);
+        assert_eq!(limiter.check_at("key", LIMIT, idle, true).remaining, 2);
+    }
+
+    #[test]
+    fn peek_does_not_consume() {
+        let limiter = InMemoryRateLimiter::new();
+        let now = Instant::now();
+
+        for _ in 0..10 {
+            let decision = limiter.check_at("key", LIMIT, now, false);
+            assert!(decision.allowed);
+            assert_eq!(decision.remaining, 2);
+        }
+
+        assert_eq!(limiter.check_at("key", LIMIT, now, true).remaining, 2);
+    }
+
+    #[test]
+    fn keys_are_independent() {
+        let limiter = InMemoryRateLimiter::new();
+        let now = Instant::now();
+
+        for _ in 0..3 {
+            assert!(limiter.check_at("first", LIMIT, now, true).allowed);
+        }
+
+        assert!(!limiter.check_at("first", LIMIT, now, true).allowed);
+        assert!(limiter.check_at("second", LIMIT, now, true).allowed);
+    }
+}
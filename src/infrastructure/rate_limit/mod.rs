@@ -0,0 +1,9 @@
+//! Хранилище состояния rate limiting (GCRA)
+
+mod memory_limiter;
+mod redis_limiter;
+mod service;
+
+pub use memory_limiter::InMemoryRateLimiter;
+pub use redis_limiter::RedisRateLimiter;
+pub use service::{RateLimit, RateLimitDecision, RateLimiter};
@@ -0,0 +1,224 @@
+use super::memory_limiter::InMemoryRateLimiter;
+use super::service::{RateLimit, RateLimitDecision, RateLimiter};
+use async_trait::async_trait;
+use redis::{AsyncCommands, Client, Script, aio::ConnectionManager};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// GCRA в Lua: атомарно для всех инстансов, время берётся из Redis.
+/// KEYS[1] - ключ, ARGV[1] - интервал (мкс), ARGV[2] - burst,
+/// ARGV[3] - 1 учесть запрос, 0 только проверить.
+/// Возвращает {allowed, remaining, retry_after_us}.
+const GCRA_SCRIPT: &str = r#"
+local t = redis.call('TIME')
+local now = tonumber(t[1]) * 1000000 + tonumber(t[2])
+local interval = tonumber(ARGV[1])
+local burst_offset = interval * tonumber(ARGV[2])
+
+local tat = tonumber(redis.call('GET', KEYS[1]))
+if tat == nil or tat < now then
+    tat = now
+end
+
+local new_tat = tat + interval
+local allow_at = new_tat - burst_offset
+local diff = now - allow_at
+
+if diff < 0 then
+    return {0, 0, -diff}
+end
+
+if ARGV[3] == '1' then
+    redis.call('SET', KEYS[1], string.format('%d', new_tat), 'PX', math.ceil((new_tat - now) / 1000))
+end
+return {1, math.floor(diff / interval), 0}
+"#;
+
+/// Rate limiter с общим состоянием в Redis.
+/// При недоступности Redis переключается на in-process лимиты.
+pub struct RedisRateLimiter {
+    client: ConnectionManager,
+    script: Script,
+    key_prefix: String,
+    fallback: InMemoryRateLimiter,
+    /// Redis сейчас недоступен (логируем только смену состояния)
+    degraded: AtomicBool,
+}
+
+impl RedisRateLimiter {
+    pub async fn connect(redis_url: &str) -> Result<Self, redis::RedisError> {
+        let client = Client::open(redis_url)?;
+        let manager = ConnectionManager::new(client).await?;
+
+        let mut test_conn = manager.clone();
+        test_conn.ping::<()>().await?;
+
+        info!("✓ Rate limiter connected to Redis");
+
+        Ok(Self {
+            client: manager,
+            script: Script::new(GCRA_SCRIPT),
+            key_prefix: "ratelimit:".to_string(),
+            fallback: InMemoryRateLimiter::new(),
+            degraded: AtomicBool::new(false),
+        })
+    }
+}
+
+impl RedisRateLimiter {
+    async fn run(&self, key: &str, limit: RateLimit, commit: bool) -> RateLimitDecision {
+        let mut conn = self.client.clone();
+        let interval_us = limit.emission_interval().as_micros() as u64;
+
+        let result: Result<(i64, i64, i64), _> = self
+            .script
+            .key(format!("{}{}", self.key_prefix, key))
+            .arg(interval_us)
+            .arg(limit.burst)
+            .arg(u8::from(commit))
+            .invoke_async(&mut conn)
+            .await;
+
+        match result {
+            Ok((allowed, remaining, retry_after_us)) => {
+                if self.degraded.swap(false, Ordering::Relaxed) {
+                    metrics::gauge!("rate_limit_redis_degraded").set(0.0);
+                    info!("Redis rate limiter recovered, using shared limits again");
+                }
+
+                RateLimitDecision {
+                    allowed: allowed == 1,
+                    remaining: remaining.max(0) as u32,
+                    retry_after: Duration::from_micros(retry_after_us.max(0) as u64),
+                }
+            }
+            Err(e) => {
+                metrics::counter!("rate_limit_fallback_total").increment(1);
+                if !self.degraded.swap(true, Ordering::Relaxed) {
+                    metrics::gauge!("rate_limit_redis_degraded").set(1.0);
+                    warn!("Redis rate limiter error, using in-process limits: {}", e);
+                }
+
+                if commit {
+                    self.fallback.check(key, limit).await
+                } else {
+                    self.fallback.peek(key, limit).await
+                }
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl RateLimiter for RedisRateLimiter {
+    async fn check(&self, key: &str, limit: RateLimit) -> RateLimitDecision {
+        self.run(key, limit, true).await
+    }
+
+    async fn peek(&self, key: &str, limit: RateLimit) -> RateLimitDecision {
+        self.run(key, limit, false).await
+    }
+}
+
+/// Проверки GCRA_SCRIPT на живом Redis: запускаются только при заданном REDIS_URL
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    /// 60 в минуту - один запрос в секунду, до 3 подряд
+    const LIMIT: RateLimit = RateLimit {
+        per_minute: 60,
+        burst: 3,
+    };
+
+    async fn limiter() -> Option<RedisRateLimiter> {
+        let redis_url = std::env::var("REDIS_URL").ok()?;
+        let mut limiter = RedisRateLimiter::connect(&redis_url)
+            .await
+            .expect("REDIS_URL is set but Redis is unavailable");
+
+        // Свой префикс на каждый запуск, чтобы не видеть состояние прошлых
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        limiter.key_prefix = format!("ratelimit:test:{nanos}:");
+        Some(limiter)
+    }
+
+    /// Решение именно скрипта, а не in-process запасного лимитера
+    async fn run(limiter: &RedisRateLimiter, key: &str, commit: bool) -> RateLimitDecision {
+        let decision = limiter.run(key, LIMIT, commit).await;
+        assert!(!limiter.degraded.load(Ordering::Relaxed));
+        decision
+    }
+
+    #[tokio::test]
+    async fn allows_burst_then_rejects() {
+        let Some(limiter) = limiter().await else {
+            return;
+        };
+
+        let mut remaining = Vec::new();
+        for _ in 0..3 {
+            let decision = run(&limiter, "key", true).await;
+            assert!(decision.allowed);
+            remaining.push(decision.remaining);
+        }
+        assert_eq!(remaining, [2, 1, 0]);
+
+        let rejected = run(&limiter, "key", true).await;
+        assert!(!rejected.allowed);
+        assert_eq!(rejected.remaining, 0);
+        assert!(rejected.retry_after > Duration::from_millis(900));
+        assert!(rejected.retry_after <= Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn refills_one_request_per_interval() {
+        let Some(limiter) = limiter().await else {
+            return;
+        };
+
+        for _ in 0..3 {
+            assert!(run(&limiter, "key", true).await.allowed);
+        }
+        assert!(!run(&limiter, "key", true).await.allowed);
+
+        tokio::time::sleep(Duration::from_secs(1)).await;
+
+        assert!(run(&limiter, "key", true).await.allowed);
+        assert!(!run(&limiter, "key", true).await.allowed);
+    }
+
+    #[tokio::test]
+    async fn peek_does_not_consume() {
+        let Some(limiter) = limiter().await else {
+            return;
+        };
+
+        for _ in 0..10 {
+            let decision = run(&limiter, "key", false).await;
+            assert!(decision.allowed);
+            assert_eq!(decision.remaining, 2);
+        }
+
+        assert_eq!(run(&limiter, "key", true).await.remaining, 2);
+    }
+
+    #[tokio::test]
+    async fn keys_are_independent() {
+        let Some(limiter) = limiter().await else {
+            return;
+        };
+
+        for _ in 0..3 {
+            assert!(run(&limiter, "first", true).await.allowed);
+        }
+
+        assert!(!run(&limiter, "first", true).await.allowed);
+        assert!(run(&limiter, "second", true).await.allowed);
+    }
+}
@@ -0,0 +1,43 @@
+use async_trait::async_trait;
+use std::time::Duration;
+
+/// Лимит запросов: `per_minute` в среднем и до `burst` подряд
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    pub per_minute: u32,
+    pub burst: u32,
+}
+
+impl RateLimit {
+    pub fn new(per_minute: u32, burst: u32) -> Self {
+        Self {
+            per_minute: per_minute.max(1),
+            burst: burst.max(1),
+        }
+    }
+
+    /// Интервал между запросами при равномерной нагрузке
+    pub fn emission_interval(&self) -> Duration {
+        Duration::from_secs(60) / self.per_minute
+    }
+}
+
+/// Результат проверки лимита
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimitDecision {
+    pub allowed: bool,
+    /// Сколько запросов ещё можно выполнить прямо сейчас
+    pub remaining: u32,
+    /// Через сколько повторить запрос (для отклонённых)
+    pub retry_after: Duration,
+}
+
+/// Трейт для хранилища rate limiting (in-process или общее для инстансов)
+#[async_trait]
+pub trait RateLimiter: Send + Sync {
+    /// Учесть запрос по ключу и решить, пропускать ли его
+    async fn check(&self, key: &str, limit: RateLimit) -> RateLimitDecision;
+
+    /// Пропустили бы запрос по ключу - без учёта самого запроса
+    async fn peek(&self, key: &str, limit: RateLimit) -> RateLimitDecision;
+}
@@ -0,0 +1,33 @@
+use async_trait::async_trait;
+
+/// Ошибки проверки владения доменом
+#[derive(Debug, thiserror::Error)]
+pub enum VerificationError {
+    #[error("DNS lookup failed: {0}")]
+    Dns(String),
+
+    #[error("Target address is not allowed: {0}")]
+    Blocked(String),
+
+    #[error("Request failed: {0}")]
+    Request(String),
+
+    #[error("Unexpected HTTP status: {0}")]
+    Status(u16),
+}
+
+pub type VerificationResult<T> = Result<T, VerificationError>;
+
+/// Трейт для чтения TXT записей (позволяет подменить DNS резолвер)
+#[async_trait]
+pub trait TxtResolver: Send + Sync {
+    /// TXT записи имени; пустой список, если записей нет
+    async fn txt_records(&self, name: &str) -> VerificationResult<Vec<String>>;
+}
+
+/// Трейт для загрузки HTTP challenge (позволяет подменить HTTP-клиент)
+#[async_trait]
+pub trait ChallengeFetcher: Send + Sync {
+    /// Тело ответа на GET запрос
+    async fn fetch(&self, url: &str) -> VerificationResult<String>;
+}
@@ -0,0 +1,47 @@
+use super::checker::{TxtResolver, VerificationError, VerificationResult};
+use async_trait::async_trait;
+use hickory_resolver::TokioResolver;
+use std::time::Duration;
+
+/// TXT записи через системные DNS серверы (/etc/resolv.conf)
+pub struct DnsTxtResolver {
+    resolver: TokioResolver,
+}
+
+impl DnsTxtResolver {
+    pub fn new(timeout: Duration) -> VerificationResult<Self> {
+        let mut builder =
+            TokioResolver::builder_tokio().map_err(|e| VerificationError::Dns(e.to_string()))?;
+
+        // Свежая запись должна проверяться сразу после добавления
+        let options = builder.options_mut();
+        options.timeout = timeout;
+        options.cache_size = 0;
+
+        Ok(Self {
+            resolver: builder.build(),
+        })
+    }
+}
+
+#[async_trait]
+impl TxtResolver for DnsTxtResolver {
+    async fn txt_records(&self, name: &str) -> VerificationResult<Vec<String>> {
+        let lookup = match self.resolver.txt_lookup(name).await {
+            Ok(lookup) => lookup,
+            Err(e) if e.is_no_records_found() => return Ok(Vec::new()),
+            Err(e) => return Err(VerificationError::Dns(e.to_string())),
+        };
+
+        // Длинная запись приходит несколькими строками - склеиваем
+        Ok(lookup
+            .iter()
+            .map(|txt| {
+                txt.txt_data()
+                    .iter()
+                    .map(|chunk| String::from_utf8_lossy(chunk))
+                    .collect::<String>()
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,88 @@
+use super::checker::{ChallengeFetcher, VerificationError, VerificationResult};
+use crate::infrastructure::metadata::{PublicOnlyResolver, is_allowed_url};
+use async_trait::async_trait;
+use reqwest::{Client, redirect};
+use std::sync::Arc;
+use std::time::Duration;
+use url::Url;
+
+/// Challenge - короткий токен, больше читать незачем
+const MAX_CHALLENGE_BYTES: usize = 1024;
+
+#[derive(Debug, Clone)]
+pub struct HttpChallengeFetcherConfig {
+    pub timeout: Duration,
+    /// Разрешить запросы к loopback/private адресам (для локальной разработки)
+    pub allow_private: bool,
+}
+
+/// Загрузка HTTP challenge через reqwest (без редиректов)
+pub struct HttpChallengeFetcher {
+    client: Client,
+    allow_private: bool,
+}
+
+impl HttpChallengeFetcher {
+    pub fn new(config: HttpChallengeFetcherConfig) -> VerificationResult<Self> {
+        let mut builder = Client::builder()
+            .timeout(config.timeout)
+            .connect_timeout(config.timeout)
+            .redirect(redirect::Policy::none())
+            .user_agent(concat!(
+                "url-shortener/",
+                env!("CARGO_PKG_VERSION"),
+                " (domain verification)"
+            ));
+
+        if !config.allow_private {
+            builder = builder.dns_resolver(Arc::new(PublicOnlyResolver));
+        }
+
+        let client = builder
+            .build()
+            .map_err(|e| VerificationError::Request(e.to_string()))?;
+
+        Ok(Self {
+            client,
+            allow_private: config.allow_private,
+        })
+    }
+}
+
+#[async_trait]
+impl ChallengeFetcher for HttpChallengeFetcher {
+    async fn fetch(&self, url: &str) -> VerificationResult<String> {
+        let url = Url::parse(url).map_err(|e| VerificationError::Request(e.to_string()))?;
+
+        if !self.allow_private && !is_allowed_url(&url) {
+            return Err(VerificationError::Blocked(url.to_string()));
+        }
+
+        let mut response = self
+            .client
+            .get(url)
+            .send()
+            .await
+            .map_err(|e| VerificationError::Request(e.to_string()))?;
+
+        if !response.status().is_success() {
+            return Err(VerificationError::Status(response.status().as_u16()));
+        }
+
+        let mut body = Vec::new();
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|e| VerificationError::Request(e.to_string()))?
+        {
+            let remaining = MAX_CHALLENGE_BYTES - body.len();
+            body.extend_from_slice(&chunk[..chunk.len().min(remaining)]);
+
+            if body.len() >= MAX_CHALLENGE_BYTES {
+                break;
+            }
+        }
+
+        Ok(String::from_utf8_lossy(&body).into_owned())
+    }
+}
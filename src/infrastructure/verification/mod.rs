@@ -0,0 +1,11 @@
+//! Проверка владения доменом: TXT запись в DNS или HTTP challenge
+
+mod checker;
+mod dns_resolver;
+mod http_fetcher;
+mod verifier;
+
+pub use checker::{ChallengeFetcher, TxtResolver, VerificationError, VerificationResult};
+pub use dns_resolver::DnsTxtResolver;
+pub use http_fetcher::{HttpChallengeFetcher, HttpChallengeFetcherConfig};
+pub use verifier::DomainVerifier;
@@ -0,0 +1,157 @@
+use super::checker::{ChallengeFetcher, TxtResolver, VerificationResult};
+use super::dns_resolver::DnsTxtResolver;
+use super::http_fetcher::{HttpChallengeFetcher, HttpChallengeFetcherConfig};
+use crate::domain::entities::{VerificationChallenge, VerificationMethod};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Проверка challenge домена: сначала TXT запись, затем HTTP
+pub struct DomainVerifier {
+    resolver: Arc<dyn TxtResolver>,
+    fetcher: Arc<dyn ChallengeFetcher>,
+}
+
+impl DomainVerifier {
+    pub fn new(resolver: Arc<dyn TxtResolver>, fetcher: Arc<dyn ChallengeFetcher>) -> Self {
+        Self { resolver, fetcher }
+    }
+
+    /// Системный DNS резолвер и reqwest клиент
+    pub fn from_network(timeout: Duration, allow_private: bool) -> VerificationResult<Self> {
+        let resolver = DnsTxtResolver::new(timeout)?;
+        let fetcher = HttpChallengeFetcher::new(HttpChallengeFetcherConfig {
+            timeout,
+            allow_private,
+        })?;
+
+        Ok(Self::new(Arc::new(resolver), Arc::new(fetcher)))
+    }
+
+    /// Способ, которым подтверждён домен, или описание причин неудачи обеих проверок
+    pub async fn check(
+        &self,
+        challenge: &VerificationChallenge,
+    ) -> Result<VerificationMethod, String> {
+        let dns_error = match self.resolver.txt_records(&challenge.dns_name).await {
+            Ok(records) if records.iter().any(|r| r.trim() == challenge.dns_value) => {
+                return Ok(VerificationMethod::Dns);
+            }
+            Ok(records) if records.is_empty() => "TXT record not found".to_string(),
+            Ok(_) => "TXT record does not match".to_string(),
+            Err(e) => e.to_string(),
+        };
+
+        let http_error = match self.fetcher.fetch(&challenge.http_url).await {
+            Ok(body) if body.trim() == challenge.token => return Ok(VerificationMethod::Http),
+            Ok(_) => "Response body does not match the token".to_string(),
+            Err(e) => e.to_string(),
+        };
+
+        Err(format!("dns: {}; http: {}", dns_error, http_error))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::verification::VerificationError;
+    use async_trait::async_trait;
+
+    /// Резолвер с заранее заданным ответом
+    struct FakeResolver(Result<Vec<&'static str>, &'static str>);
+
+    #[async_trait]
+    impl TxtResolver for FakeResolver {
+        async fn txt_records(&self, _: &str) -> VerificationResult<Vec<String>> {
+            match &self.0 {
+                Ok(records) => Ok(records.iter().map(|r| r.to_string()).collect()),
+                Err(e) => Err(VerificationError::Dns(e.to_string())),
+            }
+        }
+    }
+
+    /// HTTP-клиент с заранее заданным ответом
+    struct FakeFetcher(Result<&'static str, u16>);
+
+    #[async_trait]
+    impl ChallengeFetcher for FakeFetcher {
+        async fn fetch(&self, _: &str) -> VerificationResult<String> {
+            match self.0 {
+                Ok(body) => Ok(body.to_string()),
+                Err(status) => Err(VerificationError::Status(status)),
+            }
+        }
+    }
+
+    fn challenge() -> VerificationChallenge {
+        VerificationChallenge {
+            dns_name: "_url-shortener-challenge.s.example.com".to_string(),
+            dns_value: "url-shortener-verification=abc123".to_string(),
+            http_url: "http://s.example.com/.well-known/url-shortener-verification".to_string(),
+            token: "abc123".to_string(),
+        }
+    }
+
+    fn verifier(resolver: FakeResolver, fetcher: FakeFetcher) -> DomainVerifier {
+        DomainVerifier::new(Arc::new(resolver), Arc::new(fetcher))
+    }
+
+    #[tokio::test]
+    async fn accepts_matching_txt_record() {
+        let verifier = verifier(
+            FakeResolver(Ok(vec!["other", " url-shortener-verification=abc123 "])),
+            FakeFetcher(Err(404)),
+        );
+
+        assert_eq!(
+            verifier.check(&challenge()).await,
+            Ok(VerificationMethod::Dns)
+        );
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_http_challenge() {
+        let verifier = verifier(FakeResolver(Ok(vec![])), FakeFetcher(Ok("abc123\n")));
+
+        assert_eq!(
+            verifier.check(&challenge()).await,
+            Ok(VerificationMethod::Http)
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_mismatched_values() {
+        let verifier = verifier(
+            FakeResolver(Ok(vec!["url-shortener-verification=zzz"])),
+            FakeFetcher(Ok("zzz")),
+        );
+
+        assert_eq!(
+            verifier.check(&challenge()).await,
+            Err(
+                "dns: TXT record does not match; http: Response body does not match the token"
+                    .to_string()
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn reports_both_failures() {
+        let verifier = verifier(FakeResolver(Err("NXDOMAIN")), FakeFetcher(Err(503)));
+
+        assert_eq!(
+            verifier.check(&challenge()).await,
+            Err("dns: DNS lookup failed: NXDOMAIN; http: Unexpected HTTP status: 503".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn reports_missing_txt_record() {
+        let verifier = verifier(FakeResolver(Ok(vec![])), FakeFetcher(Err(404)));
+
+        assert_eq!(
+            verifier.check(&challenge()).await,
+            Err("dns: TXT record not found; http: Unexpected HTTP status: 404".to_string())
+        );
+    }
+}
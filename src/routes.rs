@@ -1,22 +1,64 @@
 use crate::api;
-use crate::api::handlers::redirect_handler;
+use crate::api::handlers::{redirect_handler, root_handler, verification_challenge_handler};
 use crate::api::middleware::{auth, rate_limit, tracing};
 use crate::state::AppState;
 use crate::web;
-use crate::web::middleware::web_auth;
+use crate::web::middleware::{csrf, web_auth};
 use axum::routing::get;
 use axum::{Router, middleware};
 use tower::Layer;
 use tower_http::normalize_path::{NormalizePath, NormalizePathLayer};
 use tower_http::services::ServeDir;
 
+const API_PATH: &str = "/api";
+const DASHBOARD_PATH: &str = "/dashboard";
+const STATIC_PATH: &str = "/static";
+const WELL_KNOWN_PATH: &str = "/.well-known";
+const VERIFICATION_CHALLENGE_PATH: &str = "/.well-known/url-shortener/{token}";
+
+/// Первые сегменты путей, которые обслуживает не `/{code}`.
+/// Такие коды резервируются автоматически (`ReservedCodes`); каждый путь,
+/// добавленный в `app_router`, должен попасть сюда (проверяется тестом).
+pub const TOP_LEVEL_ROUTES: &[&str] = &[API_PATH, DASHBOARD_PATH, STATIC_PATH, WELL_KNOWN_PATH];
+
+/// Сегменты `TOP_LEVEL_ROUTES` без ведущего `/`
+pub fn reserved_route_codes() -> impl Iterator<Item = &'static str> {
+    TOP_LEVEL_ROUTES
+        .iter()
+        .map(|path| path.trim_start_matches('/'))
+}
+
 pub fn app_router(state: AppState) -> NormalizePath<Router> {
     // API
+    // Лимиты API считаются по токену, поэтому rate limit идёт после аутентификации;
+    // неудачные попытки аутентификации ограничиваются по IP до неё
+    // Запросы дашборда с cookie сессии дополнительно проверяются на CSRF
     let api_protected = api::routes::protected_routes()
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            rate_limit::api_layer,
+        ))
         .route_layer(middleware::from_fn_with_state(state.clone(), auth::layer))
-        .layer(rate_limit::secure_layer());
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            rate_limit::auth_failure_layer,
+        ))
+        .route_layer(middleware::from_fn(csrf::api_layer));
 
-    let api_public = api::routes::public_routes().layer(rate_limit::layer());
+    let api_public = api::routes::public_routes()
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            rate_limit::api_layer,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            auth::optional_layer,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            rate_limit::auth_failure_layer,
+        ))
+        .route_layer(middleware::from_fn(csrf::api_layer));
 
     let api_router = Router::new().merge(api_protected).merge(api_public);
 
@@ -26,21 +68,94 @@ pub fn app_router(state: AppState) -> NormalizePath<Router> {
             state.clone(),
             web_auth::layer,
         ))
-        .layer(rate_limit::secure_layer());
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            rate_limit::dashboard_layer,
+        ))
+        .layer(middleware::from_fn_with_state(state.clone(), csrf::layer));
 
-    let web_public = web::routes::public_routes().layer(rate_limit::layer());
+    let web_public = web::routes::public_routes()
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            rate_limit::public_layer,
+        ))
+        .layer(middleware::from_fn_with_state(state.clone(), csrf::layer));
 
     let web_router = Router::new().merge(web_protected).merge(web_public);
 
     // Объединяем
+    // Корень и коды разрешаются по домену из Host
     let router = Router::new()
+        .route("/", get(root_handler))
         .route("/{code}", get(redirect_handler))
-        .nest("/api", api_router)
-        .nest("/dashboard", web_router)
-        .nest_service("/static", ServeDir::new("static"))
+        .route(
+            VERIFICATION_CHALLENGE_PATH,
+            get(verification_challenge_handler),
+        )
+        .nest(API_PATH, api_router)
+        .nest(DASHBOARD_PATH, web_router)
+        .nest_service(STATIC_PATH, ServeDir::new("static"))
         .with_state(state)
         .layer(tracing::layer());
 
     // Нормализация путей
     NormalizePathLayer::trim_trailing_slash().layer(router)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use regex::Regex;
+    use std::collections::HashMap;
+
+    const SOURCE: &str = include_str!("routes.rs");
+
+    /// Пути, переданные в `.route` / `.nest` / `.nest_service` внутри `app_router`
+    fn app_router_paths() -> Vec<String> {
+        let constants: HashMap<&str, &str> = Regex::new(r#"const ([A-Z_]+): &str = "([^"]*)";"#)
+            .unwrap()
+            .captures_iter(SOURCE)
+            .map(|c| (c.get(1).unwrap().as_str(), c.get(2).unwrap().as_str()))
+            .collect();
+
+        let start = SOURCE.find("pub fn app_router").unwrap();
+        let end = SOURCE.find("#[cfg(test)]").unwrap();
+        let body = &SOURCE[start..end];
+
+        Regex::new(r#"\.(?:route|nest|nest_service)\(\s*(?:"([^"]*)"|([A-Z_]+))"#)
+            .unwrap()
+            .captures_iter(body)
+            .map(|c| match (c.get(1), c.get(2)) {
+                (Some(literal), _) => literal.as_str().to_string(),
+                (_, Some(name)) => constants
+                    .get(name.as_str())
+                    .unwrap_or_else(|| panic!("unknown path constant {}", name.as_str()))
+                    .to_string(),
+                _ => unreachable!(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn every_top_level_route_is_reserved() {
+        let paths = app_router_paths();
+        assert!(paths.iter().any(|path| path == API_PATH));
+        assert!(paths.iter().any(|path| path == "/{code}"));
+
+        let reserved: Vec<&str> = reserved_route_codes().collect();
+        for path in &paths {
+            let segment = path.trim_start_matches('/').split('/').next().unwrap();
+
+            // Корень и сам `/{code}` - не отдельные коды
+            if segment.is_empty() || segment.starts_with('{') {
+                continue;
+            }
+
+            assert!(
+                reserved.contains(&segment),
+                "route {} is not covered by TOP_LEVEL_ROUTES",
+                path
+            );
+        }
+    }
+}
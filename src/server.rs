@@ -1,7 +1,15 @@
 use crate::config::Config;
 use crate::domain::click_worker::run_click_worker;
-use crate::infrastructure::cache::{CacheService, NullCache, RedisCache};
+use crate::infrastructure::cache::{
+    CacheService, DOMAINS_CHANGED_CHANNEL, NullCache, RESERVED_CODES_CHANGED_CHANNEL, RedisCache,
+    spawn_changes_listener,
+};
+use crate::infrastructure::metadata::{
+    HttpMetadataFetcher, HttpMetadataFetcherConfig, run_metadata_worker,
+};
 use crate::infrastructure::persistence::{PgDomainRepository, PgLinkRepository, PgStatsRepository};
+use crate::infrastructure::rate_limit::{InMemoryRateLimiter, RateLimiter, RedisRateLimiter};
+use crate::infrastructure::verification::DomainVerifier;
 use crate::routes::app_router;
 use crate::state::AppState;
 
@@ -11,6 +19,7 @@ use axum::extract::Request;
 use sqlx::PgPool;
 use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::mpsc;
 
 /// Запуск HTTP сервера с полной инициализацией
@@ -36,10 +45,33 @@ pub async fn run(config: Config) -> Result<()> {
         Arc::new(NullCache::new())
     };
 
-    // 3. Создание очереди кликов
+    // 3. Хранилище rate limiting: общее в Redis или in-process
+    let rate_limiter: Arc<dyn RateLimiter> = match &config.redis_url {
+        Some(redis_url) if config.rate_limit_redis => {
+            match RedisRateLimiter::connect(redis_url).await {
+                Ok(limiter) => {
+                    tracing::info!("Rate limiting shared via Redis");
+                    Arc::new(limiter)
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        "Failed to connect rate limiter to Redis: {}. Using in-process limits.",
+                        e
+                    );
+                    Arc::new(InMemoryRateLimiter::new())
+                }
+            }
+        }
+        _ => {
+            tracing::info!("Rate limiting is per-process");
+            Arc::new(InMemoryRateLimiter::new())
+        }
+    };
+
+    // 4. Создание очереди кликов
     let (click_tx, click_rx) = mpsc::channel(config.click_queue_capacity);
 
-    // 4. Запуск воркера
+    // 5. Запуск воркера
     let pool_arc = Arc::new(pool.clone());
     let stats_repository = Arc::new(PgStatsRepository::new(pool_arc.clone()));
     let domain_repository = Arc::new(PgDomainRepository::new(pool_arc.clone()));
@@ -48,17 +80,74 @@ pub async fn run(config: Config) -> Result<()> {
         click_rx,
         stats_repository,
         domain_repository,
-        link_repository,
+        link_repository.clone(),
     ));
     tracing::info!("Click worker started");
 
-    // 5. Создание состояния приложения (добавляем cache)
-    let state = AppState::new(Arc::new(pool), click_tx, cache);
+    // 6. Запуск воркера метаданных страниц (если включен)
+    let metadata_tx = if config.metadata_fetch_enabled {
+        let fetcher = HttpMetadataFetcher::new(HttpMetadataFetcherConfig {
+            timeout: Duration::from_millis(config.metadata_fetch_timeout_ms),
+            max_bytes: config.metadata_fetch_max_bytes,
+            max_redirects: config.metadata_fetch_max_redirects,
+            allow_private: config.metadata_fetch_allow_private,
+        })?;
+
+        let (metadata_tx, metadata_rx) = mpsc::channel(config.metadata_queue_capacity);
+        tokio::spawn(run_metadata_worker(
+            metadata_rx,
+            Arc::new(fetcher),
+            link_repository,
+            config.metadata_fetch_concurrency,
+        ));
+        Some(metadata_tx)
+    } else {
+        tracing::info!("Page metadata fetching disabled");
+        None
+    };
+
+    let domain_verifier = DomainVerifier::from_network(
+        Duration::from_millis(config.domain_verification_timeout_ms),
+        config.domain_verification_allow_private,
+    )?;
+
+    // 7. Создание состояния приложения (добавляем cache)
+    let state = AppState::new(
+        Arc::new(config.clone()),
+        Arc::new(pool),
+        click_tx,
+        cache,
+        rate_limiter,
+        metadata_tx,
+        Arc::new(domain_verifier),
+    );
+
+    // Изменения доменов и зарезервированных кодов на других инстансах и в CLI
+    // сбрасывают кэши хостов и кодов
+    if let Some(redis_url) = &config.redis_url
+        && config.domain_cache_ttl_secs > 0
+    {
+        let host_cache = state.host_cache.clone();
+        spawn_changes_listener(redis_url.clone(), DOMAINS_CHANGED_CHANNEL, move || {
+            host_cache.clear()
+        });
+    }
+
+    if let Some(redis_url) = &config.redis_url
+        && config.reserved_codes_cache_ttl_secs > 0
+    {
+        let reserved_codes = state.reserved_code_service.clone();
+        spawn_changes_listener(
+            redis_url.clone(),
+            RESERVED_CODES_CHANGED_CHANNEL,
+            move || reserved_codes.invalidate(),
+        );
+    }
 
-    // 6. Создание роутера
+    // 8. Создание роутера
     let app = app_router(state);
 
-    // 7. Запуск HTTP сервера
+    // 9. Запуск HTTP сервера
     let addr: SocketAddr = config.listen_addr.parse()?;
     let listener = tokio::net::TcpListener::bind(addr).await?;
     tracing::info!("Listening on http://{addr}");
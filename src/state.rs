@@ -2,53 +2,179 @@ use sqlx::PgPool;
 use std::sync::Arc;
 use tokio::sync::mpsc;
 
-use crate::application::services::{AuthService, DomainService, LinkService, StatsService};
+use crate::application::services::{
+    AuthService, DomainService, LinkService, ReservedCodeService, SessionService, StatsService,
+    TagService, UserService,
+};
+use crate::config::Config;
 use crate::domain::click_event::ClickEvent;
-use crate::infrastructure::cache::CacheService;
+use crate::domain::code_generator::CodeGenerators;
+use crate::domain::entities::Domain;
+use crate::domain::metadata_job::MetadataJob;
+use crate::error::AppError;
+use crate::infrastructure::cache::{CacheService, HostCache};
+use crate::infrastructure::code_generation::{
+    RandomCodeGenerator, SequentialCodeGenerator, WordCodeGenerator,
+};
 use crate::infrastructure::persistence::{
-    PgDomainRepository, PgLinkRepository, PgStatsRepository, PgTokenRepository,
+    PgDomainRepository, PgLinkRepository, PgReservedCodeRepository, PgSessionRepository,
+    PgStatsRepository, PgTagRepository, PgTokenRepository, PgUserRepository,
 };
+use crate::infrastructure::rate_limit::RateLimiter;
+use crate::infrastructure::verification::DomainVerifier;
+use crate::routes::reserved_route_codes;
+use crate::utils::code_generator::ReservedCodes;
+use crate::utils::token_hash::TokenHasher;
 
 #[derive(Clone)]
 pub struct AppState {
+    // Конфигурация
+    pub config: Arc<Config>,
+
     // Сервисы
-    pub link_service: Arc<LinkService<PgLinkRepository, PgDomainRepository>>,
+    pub link_service:
+        Arc<LinkService<PgLinkRepository, PgDomainRepository, PgReservedCodeRepository>>,
     pub stats_service: Arc<StatsService<PgStatsRepository>>,
-    pub auth_service: Arc<AuthService<PgTokenRepository>>,
+    pub auth_service: Arc<AuthService<PgTokenRepository, PgUserRepository>>,
+    pub user_service: Arc<UserService<PgUserRepository>>,
+    pub session_service: Arc<SessionService<PgSessionRepository>>,
     pub domain_service: Arc<DomainService<PgDomainRepository>>,
+    pub tag_service: Arc<TagService<PgTagRepository>>,
+    pub reserved_code_service: Arc<ReservedCodeService<PgReservedCodeRepository>>,
 
     // Кэш
     pub cache: Arc<dyn CacheService>,
 
+    // Кэш разрешения хоста запроса в домен
+    pub host_cache: Arc<HostCache>,
+
+    // Хранилище rate limiting
+    pub rate_limiter: Arc<dyn RateLimiter>,
+
     // Очередь для кликов
     pub click_sender: mpsc::Sender<ClickEvent>,
+
+    // Очередь для загрузки метаданных страниц (None, если выключено)
+    pub metadata_sender: Option<mpsc::Sender<MetadataJob>>,
+
+    // Проверка владения доменами
+    pub domain_verifier: Arc<DomainVerifier>,
 }
 
 impl AppState {
     pub fn new(
+        config: Arc<Config>,
         pool: Arc<PgPool>,
         click_sender: mpsc::Sender<ClickEvent>,
         cache: Arc<dyn CacheService>,
+        rate_limiter: Arc<dyn RateLimiter>,
+        metadata_sender: Option<mpsc::Sender<MetadataJob>>,
+        domain_verifier: Arc<DomainVerifier>,
     ) -> Self {
         // Создаём репозитории
         let link_repo = Arc::new(PgLinkRepository::new(pool.clone()));
         let stats_repo = Arc::new(PgStatsRepository::new(pool.clone()));
         let token_repo = Arc::new(PgTokenRepository::new(pool.clone()));
         let domain_repo = Arc::new(PgDomainRepository::new(pool.clone()));
+        let tag_repo = Arc::new(PgTagRepository::new(pool.clone()));
+        let session_repo = Arc::new(PgSessionRepository::new(pool.clone()));
+        let user_repo = Arc::new(PgUserRepository::new(pool.clone()));
+        let reserved_code_repo = Arc::new(PgReservedCodeRepository::new(pool.clone()));
+
+        // Генераторы кодов ссылок
+        let code_generators = CodeGenerators {
+            default_strategy: config.code_strategy,
+            random: Arc::new(RandomCodeGenerator),
+            sequential: Arc::new(SequentialCodeGenerator::new(
+                link_repo.clone(),
+                config
+                    .code_sequence_secret
+                    .as_deref()
+                    .unwrap_or(&config.token_pepper),
+            )),
+            words: Arc::new(WordCodeGenerator),
+        };
+
+        // Маршруты приложения и RESERVED_CODES недоступны для ссылок
+        let reserved_codes = ReservedCodes::new(
+            reserved_route_codes()
+                .map(str::to_string)
+                .chain(config.reserved_codes.iter().cloned()),
+        );
 
         // Создаём сервисы
-        let link_service = Arc::new(LinkService::new(link_repo, domain_repo.clone()));
+        let reserved_code_service = Arc::new(ReservedCodeService::new(
+            reserved_code_repo,
+            reserved_codes,
+            std::time::Duration::from_secs(config.reserved_codes_cache_ttl_secs),
+        ));
+        let link_service = Arc::new(LinkService::new(
+            link_repo,
+            domain_repo.clone(),
+            reserved_code_service.clone(),
+            code_generators,
+        ));
         let stats_service = Arc::new(StatsService::new(stats_repo));
-        let auth_service = Arc::new(AuthService::new(token_repo));
+        let auth_service = Arc::new(AuthService::new(
+            token_repo,
+            user_repo.clone(),
+            TokenHasher::new(&config.token_pepper),
+        ));
+        let session_service = Arc::new(SessionService::new(
+            session_repo,
+            config.session_secret.as_deref(),
+            &config.token_pepper,
+            chrono::Duration::hours(config.session_ttl_hours),
+        ));
+        let user_service = Arc::new(UserService::new(user_repo));
         let domain_service = Arc::new(DomainService::new(domain_repo));
+        let tag_service = Arc::new(TagService::new(tag_repo));
+        let host_cache = Arc::new(HostCache::new(std::time::Duration::from_secs(
+            config.domain_cache_ttl_secs,
+        )));
 
         Self {
+            config,
             link_service,
             stats_service,
             auth_service,
+            user_service,
+            session_service,
             domain_service,
+            tag_service,
+            reserved_code_service,
             cache,
+            host_cache,
+            rate_limiter,
             click_sender,
+            metadata_sender,
+            domain_verifier,
+        }
+    }
+
+    /// Сбросить кэш хостов на этом и (через Redis) остальных инстансах
+    /// после изменения доменов или алиасов
+    pub async fn domains_changed(&self) {
+        self.host_cache.clear();
+        if let Err(e) = self.cache.publish_domains_changed().await {
+            tracing::warn!("Failed to notify instances about domain changes: {}", e);
         }
     }
+
+    /// Домен, который обслуживает хост запроса (с учётом алиасов и wildcard).
+    /// Хост должен быть нормализован (`extract_domain_from_headers`).
+    pub async fn domain_for_host(&self, host: &str) -> Result<Domain, AppError> {
+        let domain = match self.host_cache.get(host) {
+            Some(domain) => domain,
+            None => {
+                let domain = self.domain_service.resolve_host(host).await?;
+                self.host_cache.insert(host, domain.clone());
+                domain
+            }
+        };
+
+        domain.ok_or_else(|| {
+            AppError::not_found("Domain not found", serde_json::json!({"domain": host}))
+        })
+    }
 }
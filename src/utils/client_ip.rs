@@ -0,0 +1,426 @@
+use axum::http::{HeaderMap, HeaderName, header};
+use ipnet::IpNet;
+use std::net::IpAddr;
+use std::str::FromStr;
+
+const X_FORWARDED_FOR: HeaderName = HeaderName::from_static("x-forwarded-for");
+const X_FORWARDED_HOST: HeaderName = HeaderName::from_static("x-forwarded-host");
+const X_REAL_IP: HeaderName = HeaderName::from_static("x-real-ip");
+
+/// Ошибка разбора списка доверенных прокси
+#[derive(Debug, thiserror::Error)]
+#[error("Invalid proxy address or CIDR: {0}")]
+pub struct InvalidProxyError(String);
+
+/// Заголовок, который выставляет доверенный прокси. Остальные заголовки
+/// игнорируются: прокси их не перезаписывает, и клиент может подставить свои.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ForwardedHeader {
+    /// `X-Forwarded-For`, хост - из `X-Forwarded-Host`
+    #[default]
+    XForwardedFor,
+    /// `Forwarded` (RFC 7239), параметры `for=` и `host=`
+    Forwarded,
+    /// `X-Real-IP`, хост - из `X-Forwarded-Host`
+    XRealIp,
+}
+
+impl FromStr for ForwardedHeader {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "x-forwarded-for" => Ok(Self::XForwardedFor),
+            "forwarded" => Ok(Self::Forwarded),
+            "x-real-ip" => Ok(Self::XRealIp),
+            other => Err(format!(
+                "Unknown forwarded header '{}' (x-forwarded-for, forwarded, x-real-ip)",
+                other
+            )),
+        }
+    }
+}
+
+/// Доверенные reverse proxy (CIDR). Заголовок с адресом клиента (`header`)
+/// и `X-Forwarded-Host` / `Forwarded: host=` учитываются только от них.
+#[derive(Debug, Clone, Default)]
+pub struct TrustedProxies {
+    networks: Vec<IpNet>,
+    header: ForwardedHeader,
+}
+
+impl TrustedProxies {
+    pub fn new(networks: Vec<IpNet>, header: ForwardedHeader) -> Self {
+        Self { networks, header }
+    }
+
+    /// Те же сети с другим заголовком
+    pub fn with_header(self, header: ForwardedHeader) -> Self {
+        Self { header, ..self }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.networks.is_empty()
+    }
+
+    /// Входит ли адрес в одну из доверенных сетей
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        let ip = canonical(ip);
+        self.networks.iter().any(|net| net.contains(&ip))
+    }
+
+    /// Реальный IP клиента.
+    ///
+    /// Если соединение пришло не от доверенного прокси - адрес соединения.
+    /// Иначе цепочка адресов из настроенного заголовка проходится справа налево,
+    /// доверенные прокси пропускаются; первый недоверенный адрес считается клиентом.
+    pub fn client_ip(&self, peer: IpAddr, headers: &HeaderMap) -> IpAddr {
+        let peer = canonical(peer);
+        if !self.contains(peer) {
+            return peer;
+        }
+
+        let chain: Vec<Option<IpAddr>> = match self.header {
+            ForwardedHeader::XForwardedFor => header_values(headers, &X_FORWARDED_FOR)
+                .map(parse_node)
+                .collect(),
+            ForwardedHeader::Forwarded => forwarded_elements(headers)
+                .iter()
+                .map(|element| element.node.as_deref().and_then(parse_node))
+                .collect(),
+            // Прокси перезаписывает X-Real-IP, значение одно
+            ForwardedHeader::XRealIp => header_values(headers, &X_REAL_IP)
+                .last()
+                .map(parse_node)
+                .into_iter()
+                .collect(),
+        };
+
+        let mut client = peer;
+        for hop in chain.into_iter().rev() {
+            // Мусор в цепочке: дальше доверять нельзя, клиент - последний известный хоп
+            let Some(ip) = hop else {
+                break;
+            };
+
+            client = canonical(ip);
+            if !self.contains(client) {
+                break;
+            }
+        }
+
+        client
+    }
+
+    /// Исходный Host, переданный доверенным прокси. Для `Forwarded` элементы
+    /// проходятся справа налево, как в `client_ip`, и берётся `host=` элемента,
+    /// добавленного крайним доверенным прокси; иначе - последнее значение
+    /// `X-Forwarded-Host` (его выставляет ближайший прокси).
+    pub fn forwarded_host(&self, peer: IpAddr, headers: &HeaderMap) -> Option<String> {
+        if !self.contains(peer) {
+            return None;
+        }
+
+        let host = match self.header {
+            ForwardedHeader::Forwarded => {
+                let mut host = None;
+                for element in forwarded_elements(headers).into_iter().rev() {
+                    host = element.host;
+
+                    let trusted = element
+                        .node
+                        .as_deref()
+                        .and_then(parse_node)
+                        .is_some_and(|ip| self.contains(ip));
+                    if !trusted {
+                        break;
+                    }
+                }
+                host
+            }
+            ForwardedHeader::XForwardedFor | ForwardedHeader::XRealIp => {
+                header_values(headers, &X_FORWARDED_HOST)
+                    .last()
+                    .map(str::to_string)
+            }
+        };
+
+        host.filter(|host| !host.is_empty())
+    }
+}
+
+impl FromStr for TrustedProxies {
+    type Err = InvalidProxyError;
+
+    /// Список через запятую: CIDR (`10.0.0.0/8`) или отдельные адреса (`127.0.0.1`)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(|item| {
+                item.parse::<IpNet>()
+                    .or_else(|_| item.parse::<IpAddr>().map(IpNet::from))
+                    .map_err(|_| InvalidProxyError(item.to_string()))
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(|networks| Self::new(networks, ForwardedHeader::default()))
+    }
+}
+
+/// IPv4-mapped IPv6 (::ffff:a.b.c.d) приводится к IPv4
+fn canonical(ip: IpAddr) -> IpAddr {
+    match ip {
+        IpAddr::V6(v6) => v6
+            .to_ipv4_mapped()
+            .map(IpAddr::V4)
+            .unwrap_or(IpAddr::V6(v6)),
+        v4 => v4,
+    }
+}
+
+/// Все значения заголовка через запятую (с учётом повторов заголовка)
+fn header_values<'a>(headers: &'a HeaderMap, name: &HeaderName) -> impl Iterator<Item = &'a str> {
+    headers
+        .get_all(name)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .map(str::trim)
+}
+
+/// Элемент `Forwarded` (RFC 7239): параметры `for=` и `host=` (кавычки сняты)
+struct ForwardedElement {
+    node: Option<String>,
+    host: Option<String>,
+}
+
+/// Элементы всех заголовков `Forwarded` слева направо
+fn forwarded_elements(headers: &HeaderMap) -> Vec<ForwardedElement> {
+    header_values(headers, &header::FORWARDED)
+        .map(|element| {
+            let param = |name: &str| {
+                element.split(';').find_map(|pair| {
+                    let (key, value) = pair.split_once('=')?;
+                    key.trim()
+                        .eq_ignore_ascii_case(name)
+                        .then(|| value.trim().trim_matches('"').to_string())
+                })
+            };
+
+            ForwardedElement {
+                node: param("for"),
+                host: param("host"),
+            }
+        })
+        .collect()
+}
+
+/// Адрес узла: `1.2.3.4`, `1.2.3.4:80`, `2001:db8::1`, `[2001:db8::1]:80`.
+/// `unknown` и обфусцированные идентификаторы не являются адресом.
+fn parse_node(node: &str) -> Option<IpAddr> {
+    if let Some(rest) = node.strip_prefix('[') {
+        return rest.split(']').next()?.parse().ok();
+    }
+
+    node.parse().ok().or_else(|| {
+        let (host, port) = node.rsplit_once(':')?;
+        port.parse::<u16>().ok()?;
+        host.parse::<std::net::Ipv4Addr>().ok().map(IpAddr::V4)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn proxies(list: &str, header: ForwardedHeader) -> TrustedProxies {
+        list.parse::<TrustedProxies>().unwrap().with_header(header)
+    }
+
+    fn headers(pairs: &[(&str, &str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.append(
+                HeaderName::from_bytes(name.as_bytes()).unwrap(),
+                HeaderValue::from_str(value).unwrap(),
+            );
+        }
+        headers
+    }
+
+    fn ip(s: &str) -> IpAddr {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn ignores_headers_from_untrusted_peer() {
+        let proxies = proxies("10.0.0.0/8", ForwardedHeader::XForwardedFor);
+        let headers = headers(&[
+            ("x-forwarded-for", "203.0.113.7"),
+            ("x-forwarded-host", "evil.example"),
+        ]);
+
+        assert_eq!(
+            proxies.client_ip(ip("198.51.100.1"), &headers),
+            ip("198.51.100.1")
+        );
+        assert_eq!(proxies.forwarded_host(ip("198.51.100.1"), &headers), None);
+    }
+
+    #[test]
+    fn walks_x_forwarded_for_right_to_left_over_trusted_chain() {
+        let proxies = proxies("10.0.0.0/8, 192.0.2.10", ForwardedHeader::XForwardedFor);
+        let headers = headers(&[
+            ("x-forwarded-for", "203.0.113.7, 192.0.2.10"),
+            ("x-forwarded-for", "10.1.1.1"),
+        ]);
+
+        assert_eq!(
+            proxies.client_ip(ip("10.0.0.2"), &headers),
+            ip("203.0.113.7")
+        );
+    }
+
+    #[test]
+    fn ignores_spoofed_leftmost_x_forwarded_for_entry() {
+        let proxies = proxies("10.0.0.0/8", ForwardedHeader::XForwardedFor);
+        // Клиент прислал свой X-Forwarded-For, прокси дописал реальный адрес
+        let headers = headers(&[("x-forwarded-for", "1.2.3.4, 203.0.113.7")]);
+
+        assert_eq!(
+            proxies.client_ip(ip("10.0.0.2"), &headers),
+            ip("203.0.113.7")
+        );
+    }
+
+    #[test]
+    fn all_trusted_chain_ends_at_leftmost_entry() {
+        let proxies = proxies("10.0.0.0/8", ForwardedHeader::XForwardedFor);
+        let headers = headers(&[("x-forwarded-for", "10.9.9.9, 10.1.1.1")]);
+
+        assert_eq!(proxies.client_ip(ip("10.0.0.2"), &headers), ip("10.9.9.9"));
+    }
+
+    #[test]
+    fn stops_at_garbage_entries() {
+        let proxies = proxies("10.0.0.0/8", ForwardedHeader::XForwardedFor);
+
+        let garbage_last = headers(&[("x-forwarded-for", "203.0.113.7, not-an-ip")]);
+        assert_eq!(
+            proxies.client_ip(ip("10.0.0.2"), &garbage_last),
+            ip("10.0.0.2")
+        );
+
+        let garbage_behind_proxy =
+            headers(&[("x-forwarded-for", "203.0.113.7, unknown, 10.1.1.1")]);
+        assert_eq!(
+            proxies.client_ip(ip("10.0.0.2"), &garbage_behind_proxy),
+            ip("10.1.1.1")
+        );
+    }
+
+    #[test]
+    fn parses_forwarded_quoting_and_ports() {
+        let proxies = proxies("10.0.0.0/8", ForwardedHeader::Forwarded);
+
+        let ipv6 = headers(&[(
+            "forwarded",
+            r#"for="[2001:db8::1]:4711";proto=https;host=s.example.com"#,
+        )]);
+        assert_eq!(proxies.client_ip(ip("10.0.0.2"), &ipv6), ip("2001:db8::1"));
+        assert_eq!(
+            proxies.forwarded_host(ip("10.0.0.2"), &ipv6).as_deref(),
+            Some("s.example.com")
+        );
+
+        let ipv4 = headers(&[("forwarded", "for=203.0.113.7:8080, For=\"10.1.1.1\"")]);
+        assert_eq!(proxies.client_ip(ip("10.0.0.2"), &ipv4), ip("203.0.113.7"));
+
+        let obfuscated = headers(&[("forwarded", "for=_hidden, for=203.0.113.7")]);
+        assert_eq!(
+            proxies.client_ip(ip("10.0.0.2"), &obfuscated),
+            ip("203.0.113.7")
+        );
+    }
+
+    #[test]
+    fn takes_forwarded_host_from_outermost_trusted_element() {
+        let proxies = proxies("10.0.0.0/8", ForwardedHeader::Forwarded);
+        let trusted_hop = headers(&[(
+            "forwarded",
+            "for=203.0.113.7;host=spoofed.example, for=10.1.1.1;host=s.example.com",
+        )]);
+        assert_eq!(
+            proxies
+                .forwarded_host(ip("10.0.0.2"), &trusted_hop)
+                .as_deref(),
+            Some("spoofed.example")
+        );
+
+        let untrusted_hop = headers(&[(
+            "forwarded",
+            "for=1.2.3.4;host=spoofed.example, for=203.0.113.7;host=s.example.com",
+        )]);
+        assert_eq!(
+            proxies
+                .forwarded_host(ip("10.0.0.2"), &untrusted_hop)
+                .as_deref(),
+            Some("s.example.com")
+        );
+    }
+
+    #[test]
+    fn reads_x_real_ip_when_configured() {
+        let real_ip_proxies = proxies("10.0.0.0/8", ForwardedHeader::XRealIp);
+        let xff_proxies = proxies("10.0.0.0/8", ForwardedHeader::XForwardedFor);
+
+        let real_ip = headers(&[("x-real-ip", "203.0.113.7"), ("x-forwarded-for", "1.2.3.4")]);
+        assert_eq!(
+            real_ip_proxies.client_ip(ip("10.0.0.2"), &real_ip),
+            ip("203.0.113.7")
+        );
+
+        // Без заголовка клиентом остаётся адрес соединения
+        assert_eq!(
+            real_ip_proxies.client_ip(ip("10.0.0.2"), &HeaderMap::new()),
+            ip("10.0.0.2")
+        );
+
+        // X-Real-IP не учитывается, если настроен другой заголовок
+        assert_eq!(
+            xff_proxies.client_ip(ip("10.0.0.2"), &real_ip),
+            ip("1.2.3.4")
+        );
+    }
+
+    #[test]
+    fn uses_x_forwarded_host_only_behind_trusted_peer() {
+        let proxies = proxies("10.0.0.0/8", ForwardedHeader::XForwardedFor);
+        let headers = headers(&[
+            ("x-forwarded-host", "spoofed.example"),
+            ("x-forwarded-host", "s.example.com"),
+        ]);
+
+        assert_eq!(
+            proxies.forwarded_host(ip("10.0.0.2"), &headers).as_deref(),
+            Some("s.example.com")
+        );
+        assert_eq!(proxies.forwarded_host(ip("203.0.113.7"), &headers), None);
+        assert_eq!(
+            TrustedProxies::default().forwarded_host(ip("10.0.0.2"), &headers),
+            None
+        );
+    }
+
+    #[test]
+    fn treats_ipv4_mapped_peer_as_ipv4() {
+        let proxies = proxies("10.0.0.0/8", ForwardedHeader::XForwardedFor);
+        let headers = headers(&[("x-forwarded-for", "203.0.113.7")]);
+
+        assert_eq!(
+            proxies.client_ip(ip("::ffff:10.0.0.2"), &headers),
+            ip("203.0.113.7")
+        );
+    }
+}
@@ -1,62 +1,119 @@
+use crate::domain::entities::CodePolicy;
 use crate::error::AppError;
-use base64::Engine as _;
 use rand_core::{OsRng, TryRngCore};
 use serde_json::json;
+use std::collections::HashSet;
 
-const CODE_LENGTH_BYTES: usize = 9;
+/// Зарезервированные коды по умолчанию (если не задан RESERVED_CODES)
+pub const DEFAULT_RESERVED_CODES: &[&str] = &["admin", "domains", "health", "login", "stats"];
 
-// Зарезервированные коды
-const RESERVED_CODES: &[&str] = &["stats", "health", "domains", "admin", "api", "dashboard"];
+/// Пути, которые часто запрашивают боты и браузеры
+const PROBE_PATHS: &[&str] = &[
+    "favicon.ico",
+    "robots.txt",
+    "sitemap.xml",
+    "apple-touch-icon.png",
+    "humans.txt",
+];
 
-/// Генерирует уникальный код для короткой ссылки
-pub fn generate_code() -> String {
-    let mut buffer = [0u8; CODE_LENGTH_BYTES];
+/// Коды, зарезервированные без записи в БД: маршруты приложения,
+/// служебные пути и RESERVED_CODES. Сравнение без учёта регистра.
+#[derive(Debug, Clone, Default)]
+pub struct ReservedCodes {
+    codes: HashSet<String>,
+}
+
+impl ReservedCodes {
+    pub fn new<I, S>(codes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let codes = codes
+            .into_iter()
+            .map(|code| code.as_ref().trim().to_lowercase())
+            .chain(PROBE_PATHS.iter().map(|path| path.to_string()))
+            .filter(|code| !code.is_empty())
+            .collect();
 
-    // Используем OsRng для криптографически стойкой случайности
-    OsRng
-        .try_fill_bytes(&mut buffer)
-        .expect("Failed to generate random bytes");
+        Self { codes }
+    }
 
-    // Кодируем в URL-safe base64 без padding
-    base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(buffer)
+    pub fn contains(&self, code: &str) -> bool {
+        self.codes.contains(&code.to_lowercase())
+    }
+
+    /// Отсортированный список (для вывода в CLI)
+    pub fn to_sorted_vec(&self) -> Vec<&str> {
+        let mut codes: Vec<&str> = self.codes.iter().map(String::as_str).collect();
+        codes.sort_unstable();
+        codes
+    }
 }
 
-/// Валидация кастомного кода
-pub fn validate_custom_code(code: &str) -> Result<(), AppError> {
-    // Проверка длины
-    if code.len() < 8 || code.len() > 15 {
-        return Err(AppError::bad_request(
-            "Custom code must be 8-15 characters",
-            json!({ "provided_length": code.len() }),
-        ));
+/// Генерирует код по политике домена (длина и алфавит)
+pub fn generate_code(policy: &CodePolicy) -> String {
+    let alphabet = policy.alphabet.chars();
+
+    // Байты из хвоста диапазона отбрасываем, чтобы символы были равновероятны
+    let limit = 256 - 256 % alphabet.len();
+
+    let mut code = String::with_capacity(policy.length);
+    let mut buffer = [0u8; 64];
+
+    while code.len() < policy.length {
+        // Используем OsRng для криптографически стойкой случайности
+        OsRng
+            .try_fill_bytes(&mut buffer)
+            .expect("Failed to generate random bytes");
+
+        for &byte in buffer.iter().filter(|&&b| (b as usize) < limit) {
+            if code.len() == policy.length {
+                break;
+            }
+            code.push(alphabet[byte as usize % alphabet.len()] as char);
+        }
     }
 
+    code
+}
+
+/// Формат кастомного кода без учёта длины: a-z, 0-9 и дефисы, не с дефиса
+/// по краям. Общая проверка для DTO и `LinkService`.
+pub fn check_custom_code_format(code: &str) -> Result<(), &'static str> {
     // Проверка формата
     if !code
         .chars()
         .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
     {
-        return Err(AppError::bad_request(
-            "Custom code can only contain lowercase letters, digits, and hyphens",
-            json!({ "code": code }),
-        ));
+        return Err("Custom code can only contain lowercase letters, digits, and hyphens");
     }
 
     // Проверка на начало/конец с дефиса
     if code.starts_with('-') || code.ends_with('-') {
-        return Err(AppError::bad_request(
-            "Custom code cannot start or end with a hyphen",
-            json!({ "code": code }),
-        ));
+        return Err("Custom code cannot start or end with a hyphen");
     }
 
-    // Проверка зарезервированных слов
-    if RESERVED_CODES.contains(&code) {
+    Ok(())
+}
+
+/// Валидация кастомного кода по политике домена (резерв проверяет `ReservedCodeService`)
+pub fn validate_custom_code(code: &str, policy: &CodePolicy) -> Result<(), AppError> {
+    // Проверка длины
+    if !policy.custom_length().contains(&code.len()) {
         return Err(AppError::bad_request(
-            "This code is reserved",
-            json!({ "code": code }),
+            format!(
+                "Custom code must be {}-{} characters",
+                policy.custom_min_length, policy.custom_max_length
+            ),
+            json!({
+                "provided_length": code.len(),
+                "min": policy.custom_min_length,
+                "max": policy.custom_max_length
+            }),
         ));
     }
 
-    Ok(())
+    check_custom_code_format(code)
+        .map_err(|message| AppError::bad_request(message, json!({ "code": code })))
 }
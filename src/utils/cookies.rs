@@ -0,0 +1,34 @@
+use axum::http::{HeaderMap, HeaderValue, header};
+
+/// Значение cookie из заголовка `Cookie`
+pub fn get_cookie<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .find_map(|cookie| {
+            let (key, value) = cookie.trim().split_once('=')?;
+            (key == name).then_some(value)
+        })
+}
+
+/// Заголовок `Set-Cookie` для HttpOnly cookie на весь сайт
+pub fn session_cookie(name: &str, value: &str, max_age_secs: i64, secure: bool) -> HeaderValue {
+    let mut cookie = format!(
+        "{}={}; Path=/; Max-Age={}; HttpOnly; SameSite=Strict",
+        name,
+        value,
+        max_age_secs.max(0)
+    );
+    if secure {
+        cookie.push_str("; Secure");
+    }
+
+    HeaderValue::from_str(&cookie).expect("cookie value must be a valid header")
+}
+
+/// Заголовок `Set-Cookie`, удаляющий cookie
+pub fn removal_cookie(name: &str, secure: bool) -> HeaderValue {
+    session_cookie(name, "", 0, secure)
+}
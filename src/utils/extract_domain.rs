@@ -1,15 +1,43 @@
 use crate::AppError;
+use crate::utils::client_ip::TrustedProxies;
+use crate::utils::hostname::normalize_hostname;
 use axum::http::{HeaderMap, header};
+use std::net::IpAddr;
+
+/// Домен запроса: `X-Forwarded-Host` от доверенного прокси, иначе `Host`.
+/// Имя приводится к той же форме, что и домены в БД (нижний регистр, punycode).
+pub fn extract_domain_from_headers(
+    headers: &HeaderMap,
+    peer: IpAddr,
+    trusted_proxies: &TrustedProxies,
+) -> Result<String, AppError> {
+    if let Some(host) = trusted_proxies.forwarded_host(peer, headers) {
+        return normalize(strip_port(&host));
+    }
 
-pub fn extract_domain_from_headers(headers: &HeaderMap) -> Result<String, AppError> {
     let host = headers
         .get(header::HOST)
         .ok_or_else(|| AppError::bad_request("Missing Host header", serde_json::json!({})))?
         .to_str()
         .map_err(|_| AppError::bad_request("Invalid Host header", serde_json::json!({})))?;
 
-    // Убираем порт, если есть (example.com:3000 -> example.com)
-    let domain = host.split(':').next().unwrap_or(host);
+    normalize(strip_port(host))
+}
+
+fn normalize(host: &str) -> Result<String, AppError> {
+    normalize_hostname(host).map_err(|e| {
+        AppError::bad_request(
+            "Invalid Host header",
+            serde_json::json!({"reason": e.to_string()}),
+        )
+    })
+}
+
+/// Убираем порт, если есть (example.com:3000 -> example.com, [::1]:3000 -> [::1])
+fn strip_port(host: &str) -> &str {
+    if host.starts_with('[') {
+        return host.find(']').map_or(host, |end| &host[..=end]);
+    }
 
-    Ok(domain.to_string())
+    host.split(':').next().unwrap_or(host)
 }
@@ -0,0 +1,216 @@
+use idna::AsciiDenyList;
+
+/// Максимальная длина имени в DNS (без завершающей точки)
+const MAX_HOSTNAME_LEN: usize = 253;
+/// Максимальная длина метки
+const MAX_LABEL_LEN: usize = 63;
+
+/// Ошибки разбора имени хоста
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum HostnameError {
+    #[error("Hostname is empty")]
+    Empty,
+
+    #[error("Hostname is not a valid IDNA name")]
+    Idna,
+
+    #[error("Hostname is longer than {MAX_HOSTNAME_LEN} characters")]
+    TooLong,
+
+    #[error("Hostname must contain at least two labels")]
+    SingleLabel,
+
+    #[error("Label '{0}' must be 1-{MAX_LABEL_LEN} characters long")]
+    LabelLength(String),
+
+    #[error("Label '{0}' must not start or end with a hyphen")]
+    LabelHyphen(String),
+
+    #[error("Label '{0}' contains characters other than a-z, 0-9 and hyphens")]
+    LabelCharacters(String),
+
+    #[error("Top-level label '{0}' must not be numeric")]
+    NumericTld(String),
+}
+
+/// Каноническая форма имени хоста: IDNA/UTS-46 (нижний регистр, punycode),
+/// без завершающей точки. `Пример.РФ` -> `xn--e1afmkfd.xn--p1ai`.
+/// Литералы IPv6 (`[::1]`) возвращаются как есть в нижнем регистре.
+pub fn normalize_hostname(host: &str) -> Result<String, HostnameError> {
+    let host = host.trim();
+    let host = host.strip_suffix('.').unwrap_or(host);
+
+    if host.is_empty() {
+        return Err(HostnameError::Empty);
+    }
+
+    if host.starts_with('[') {
+        return Ok(host.to_ascii_lowercase());
+    }
+
+    idna::domain_to_ascii_cow(host.as_bytes(), AsciiDenyList::URL)
+        .map(|ascii| ascii.into_owned())
+        .map_err(|_| HostnameError::Idna)
+}
+
+/// Нормализовать и проверить имя домена по правилам DNS (LDH):
+/// метки 1-63 символа из a-z, 0-9 и дефиса, не начинаются и не заканчиваются
+/// дефисом, минимум две метки, общая длина до 253 символов
+pub fn parse_domain_name(domain: &str) -> Result<String, HostnameError> {
+    let ascii = normalize_hostname(domain)?;
+
+    if ascii.len() > MAX_HOSTNAME_LEN {
+        return Err(HostnameError::TooLong);
+    }
+
+    let labels: Vec<&str> = ascii.split('.').collect();
+    if labels.len() < 2 {
+        return Err(HostnameError::SingleLabel);
+    }
+
+    for label in &labels {
+        if label.is_empty() || label.len() > MAX_LABEL_LEN {
+            return Err(HostnameError::LabelLength(label.to_string()));
+        }
+
+        if label.starts_with('-') || label.ends_with('-') {
+            return Err(HostnameError::LabelHyphen(label.to_string()));
+        }
+
+        if !label
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-')
+        {
+            return Err(HostnameError::LabelCharacters(label.to_string()));
+        }
+    }
+
+    // Числовая зона верхнего уровня - это IP адрес, а не домен
+    let tld = labels[labels.len() - 1];
+    if tld.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(HostnameError::NumericTld(tld.to_string()));
+    }
+
+    Ok(ascii)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_empty_labels_in_unicode_name() {
+        assert!(parse_domain_name("ÄÖ..").is_err());
+        assert!(parse_domain_name("äö..com").is_err());
+    }
+
+    #[test]
+    fn rejects_leading_and_trailing_hyphens() {
+        assert_eq!(
+            parse_domain_name("-foo.com"),
+            Err(HostnameError::LabelHyphen("-foo".to_string()))
+        );
+        assert_eq!(
+            parse_domain_name("foo-.com"),
+            Err(HostnameError::LabelHyphen("foo-".to_string()))
+        );
+        assert_eq!(parse_domain_name("f-o-o.com").as_deref(), Ok("f-o-o.com"));
+    }
+
+    #[test]
+    fn lowercases_mixed_case_names() {
+        assert_eq!(
+            normalize_hostname("S.Example.com").as_deref(),
+            Ok("s.example.com")
+        );
+        assert_eq!(
+            parse_domain_name("S.Example.com").as_deref(),
+            Ok("s.example.com")
+        );
+    }
+
+    #[test]
+    fn converts_unicode_to_punycode_and_back() {
+        let ascii = parse_domain_name("Пример.РФ").unwrap();
+        assert_eq!(ascii, "xn--e1afmkfd.xn--p1ai");
+
+        // Уже закодированное имя не меняется
+        assert_eq!(parse_domain_name(&ascii).as_deref(), Ok(ascii.as_str()));
+
+        let (unicode, result) = idna::domain_to_unicode(&ascii);
+        assert!(result.is_ok());
+        assert_eq!(unicode, "пример.рф");
+        assert_eq!(parse_domain_name(&unicode).as_deref(), Ok(ascii.as_str()));
+    }
+
+    #[test]
+    fn enforces_label_length_limit() {
+        let label = "a".repeat(MAX_LABEL_LEN);
+        let name = format!("{}.com", label);
+        assert_eq!(parse_domain_name(&name).as_deref(), Ok(name.as_str()));
+
+        let long_label = "a".repeat(MAX_LABEL_LEN + 1);
+        assert!(parse_domain_name(&format!("{}.com", long_label)).is_err());
+    }
+
+    #[test]
+    fn enforces_name_length_limit() {
+        // 3 метки по 63 + 1 метка из 61 символа + 3 точки = 253
+        let labels = [
+            "a".repeat(63),
+            "b".repeat(63),
+            "c".repeat(63),
+            "d".repeat(61),
+        ];
+        let name = labels.join(".");
+        assert_eq!(name.len(), MAX_HOSTNAME_LEN);
+        assert_eq!(parse_domain_name(&name).as_deref(), Ok(name.as_str()));
+
+        let too_long = format!("e{}", name);
+        assert!(parse_domain_name(&too_long).is_err());
+    }
+
+    #[test]
+    fn strips_trailing_dot() {
+        assert_eq!(
+            normalize_hostname("example.com.").as_deref(),
+            Ok("example.com")
+        );
+        assert_eq!(
+            parse_domain_name("Example.COM.").as_deref(),
+            Ok("example.com")
+        );
+        assert_eq!(normalize_hostname("."), Err(HostnameError::Empty));
+    }
+
+    #[test]
+    fn rejects_numeric_tld() {
+        assert_eq!(
+            parse_domain_name("example.123"),
+            Err(HostnameError::NumericTld("123".to_string()))
+        );
+        assert!(parse_domain_name("192.168.1.1").is_err());
+        assert_eq!(
+            parse_domain_name("123.example").as_deref(),
+            Ok("123.example")
+        );
+    }
+
+    #[test]
+    fn rejects_single_label_and_bad_characters() {
+        assert_eq!(
+            parse_domain_name("localhost"),
+            Err(HostnameError::SingleLabel)
+        );
+        assert!(parse_domain_name("foo_bar.com").is_err());
+        assert!(parse_domain_name("foo bar.com").is_err());
+    }
+
+    #[test]
+    fn keeps_ipv6_literals() {
+        assert_eq!(
+            normalize_hostname("[2001:DB8::1]").as_deref(),
+            Ok("[2001:db8::1]")
+        );
+    }
+}
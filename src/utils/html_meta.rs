@@ -0,0 +1,196 @@
+use crate::domain::entities::PageMetadata;
+use regex::Regex;
+use url::Url;
+
+const MAX_TITLE_CHARS: usize = 255;
+const MAX_DESCRIPTION_CHARS: usize = 1000;
+const MAX_IMAGE_URL_LEN: usize = 2048;
+
+lazy_static::lazy_static! {
+    static ref TITLE_REGEX: Regex =
+        Regex::new(r"(?is)<title[^>]*>(.*?)</title\s*>").unwrap();
+    static ref META_REGEX: Regex =
+        Regex::new(r"(?is)<meta\s[^>]*>").unwrap();
+    static ref ATTR_REGEX: Regex =
+        Regex::new(r#"(?is)([a-z][a-z0-9:_-]*)\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s"'>]+))"#).unwrap();
+    static ref ENTITY_REGEX: Regex =
+        Regex::new(r"&(#[0-9]{1,7}|#[xX][0-9a-fA-F]{1,6}|[a-zA-Z]{2,8});").unwrap();
+}
+
+/// Извлекает `<title>` и OpenGraph-теги из HTML.
+///
+/// Относительный `og:image` разрешается относительно `page_url`,
+/// принимаются только http(s) ссылки на изображение.
+pub fn extract_page_metadata(html: &str, page_url: &Url) -> PageMetadata {
+    let title = TITLE_REGEX
+        .captures(html)
+        .and_then(|c| clean_text(&c[1], MAX_TITLE_CHARS));
+
+    let mut metadata = PageMetadata {
+        title,
+        ..Default::default()
+    };
+
+    for tag in META_REGEX.find_iter(html) {
+        let mut key = None;
+        let mut content = None;
+
+        for attr in ATTR_REGEX.captures_iter(tag.as_str()) {
+            let value = attr
+                .get(2)
+                .or_else(|| attr.get(3))
+                .or_else(|| attr.get(4))
+                .map(|m| m.as_str())
+                .unwrap_or_default();
+
+            match attr[1].to_ascii_lowercase().as_str() {
+                "property" | "name" if key.is_none() => key = Some(value.to_ascii_lowercase()),
+                "content" => content = Some(value),
+                _ => {}
+            }
+        }
+
+        let (Some(key), Some(content)) = (key, content) else {
+            continue;
+        };
+
+        match key.as_str() {
+            "og:title" if metadata.og_title.is_none() => {
+                metadata.og_title = clean_text(content, MAX_TITLE_CHARS);
+            }
+            "og:description" if metadata.og_description.is_none() => {
+                metadata.og_description = clean_text(content, MAX_DESCRIPTION_CHARS);
+            }
+            "og:image" if metadata.og_image.is_none() => {
+                metadata.og_image = resolve_image_url(content, page_url);
+            }
+            _ => {}
+        }
+    }
+
+    metadata
+}
+
+/// Декодирует HTML-сущности, схлопывает пробелы и обрезает до `max_chars`
+fn clean_text(raw: &str, max_chars: usize) -> Option<String> {
+    let decoded = decode_entities(raw);
+    let collapsed = decoded.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    if collapsed.is_empty() {
+        return None;
+    }
+
+    Some(collapsed.chars().take(max_chars).collect())
+}
+
+fn resolve_image_url(raw: &str, page_url: &Url) -> Option<String> {
+    let decoded = decode_entities(raw.trim());
+    let url = page_url.join(&decoded).ok()?;
+
+    match url.scheme() {
+        "http" | "https" if url.as_str().len() <= MAX_IMAGE_URL_LEN => Some(url.to_string()),
+        _ => None,
+    }
+}
+
+fn decode_entities(input: &str) -> String {
+    ENTITY_REGEX
+        .replace_all(input, |caps: &regex::Captures| {
+            let entity = &caps[1];
+            let decoded = if let Some(hex) = entity
+                .strip_prefix("#x")
+                .or_else(|| entity.strip_prefix("#X"))
+            {
+                u32::from_str_radix(hex, 16).ok().and_then(char::from_u32)
+            } else if let Some(dec) = entity.strip_prefix('#') {
+                dec.parse().ok().and_then(char::from_u32)
+            } else {
+                match entity {
+                    "amp" => Some('&'),
+                    "lt" => Some('<'),
+                    "gt" => Some('>'),
+                    "quot" => Some('"'),
+                    "apos" => Some('\''),
+                    "nbsp" => Some(' '),
+                    _ => None,
+                }
+            };
+
+            decoded
+                .map(String::from)
+                .unwrap_or_else(|| caps[0].to_string())
+        })
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page_url() -> Url {
+        Url::parse("https://example.com/blog/post").unwrap()
+    }
+
+    #[test]
+    fn extracts_title_and_open_graph() {
+        let html = r#"
+            <html><head>
+                <TITLE>  Rust &amp; Axum
+                    guide </TITLE>
+                <meta property="og:title" content="OG &quot;title&quot;">
+                <meta content='Short description' name='og:description'>
+                <meta property=og:image content=/img/cover.png>
+            </head></html>
+        "#;
+
+        let metadata = extract_page_metadata(html, &page_url());
+
+        assert_eq!(metadata.title.as_deref(), Some("Rust & Axum guide"));
+        assert_eq!(metadata.og_title.as_deref(), Some("OG \"title\""));
+        assert_eq!(
+            metadata.og_description.as_deref(),
+            Some("Short description")
+        );
+        assert_eq!(
+            metadata.og_image.as_deref(),
+            Some("https://example.com/img/cover.png")
+        );
+    }
+
+    #[test]
+    fn keeps_first_open_graph_value() {
+        let html = r#"
+            <meta property="og:title" content="First">
+            <meta property="og:title" content="Second">
+        "#;
+
+        let metadata = extract_page_metadata(html, &page_url());
+
+        assert_eq!(metadata.og_title.as_deref(), Some("First"));
+    }
+
+    #[test]
+    fn rejects_non_http_image_and_empty_values() {
+        let html = r#"
+            <title>   </title>
+            <meta property="og:image" content="javascript:alert(1)">
+            <meta property="og:description" content="">
+        "#;
+
+        let metadata = extract_page_metadata(html, &page_url());
+
+        assert!(metadata.is_empty());
+    }
+
+    #[test]
+    fn decodes_numeric_entities_and_truncates_title() {
+        let long_title = "a".repeat(MAX_TITLE_CHARS + 10);
+        let html = format!("<title>&#8220;{}&#x201D;</title>", long_title);
+
+        let metadata = extract_page_metadata(&html, &page_url());
+        let title = metadata.title.unwrap();
+
+        assert!(title.starts_with('\u{201C}'));
+        assert_eq!(title.chars().count(), MAX_TITLE_CHARS);
+    }
+}
@@ -1,3 +1,10 @@
+pub mod client_ip;
 pub mod code_generator;
+pub mod cookies;
 pub mod extract_domain;
+pub mod hostname;
+pub mod html_meta;
+pub mod password;
+pub mod qr;
+pub mod token_hash;
 pub mod url_normalizer;
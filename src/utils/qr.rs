@@ -0,0 +1,281 @@
+use crate::error::AppError;
+use qrcode::{Color, EcLevel, QrCode};
+use serde_json::json;
+use std::fmt::Write as _;
+use std::str::FromStr;
+
+/// Формат изображения QR кода
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum QrImageFormat {
+    #[default]
+    Png,
+    Svg,
+}
+
+impl QrImageFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Png => "image/png",
+            Self::Svg => "image/svg+xml",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Png => "png",
+            Self::Svg => "svg",
+        }
+    }
+}
+
+/// Уровень коррекции ошибок (L ~7%, M ~15%, Q ~25%, H ~30%)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum QrErrorCorrection {
+    Low,
+    #[default]
+    Medium,
+    Quartile,
+    High,
+}
+
+impl From<QrErrorCorrection> for EcLevel {
+    fn from(value: QrErrorCorrection) -> Self {
+        match value {
+            QrErrorCorrection::Low => EcLevel::L,
+            QrErrorCorrection::Medium => EcLevel::M,
+            QrErrorCorrection::Quartile => EcLevel::Q,
+            QrErrorCorrection::High => EcLevel::H,
+        }
+    }
+}
+
+/// RGB цвет в формате `#rrggbb` / `rrggbb` / `#rgb`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QrColor {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl QrColor {
+    pub const BLACK: Self = Self { r: 0, g: 0, b: 0 };
+    pub const WHITE: Self = Self {
+        r: 255,
+        g: 255,
+        b: 255,
+    };
+
+    pub fn to_hex(self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+}
+
+impl FromStr for QrColor {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hex = s.trim().trim_start_matches('#');
+
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(format!("Invalid color: {}", s));
+        }
+
+        let expanded = match hex.len() {
+            3 => hex.chars().flat_map(|c| [c, c]).collect::<String>(),
+            6 => hex.to_string(),
+            _ => return Err(format!("Invalid color: {}", s)),
+        };
+
+        let channel = |i: usize| u8::from_str_radix(&expanded[i..i + 2], 16).unwrap_or(0);
+
+        Ok(Self {
+            r: channel(0),
+            g: channel(2),
+            b: channel(4),
+        })
+    }
+}
+
+/// Параметры рендеринга QR кода
+#[derive(Debug, Clone)]
+pub struct QrOptions {
+    pub format: QrImageFormat,
+    /// Желаемый размер стороны изображения в пикселях
+    pub size: u32,
+    /// Отступ вокруг кода в модулях (quiet zone)
+    pub margin: u32,
+    pub ecc: QrErrorCorrection,
+    pub foreground: QrColor,
+    pub background: QrColor,
+}
+
+/// Рендерит QR код для `data` в PNG или SVG.
+///
+/// Для PNG модуль занимает целое число пикселей, поэтому итоговый размер
+/// может быть немного меньше `size` (но не меньше 1px на модуль).
+pub fn render_qr(data: &str, options: &QrOptions) -> Result<Vec<u8>, AppError> {
+    let code = QrCode::with_error_correction_level(data, options.ecc.into()).map_err(|e| {
+        AppError::bad_request(
+            "Failed to encode QR code",
+            json!({ "reason": e.to_string() }),
+        )
+    })?;
+
+    let modules = code.width() as u32;
+    let colors = code.to_colors();
+    let is_dark = |x: u32, y: u32| {
+        x >= options.margin
+            && y >= options.margin
+            && x < options.margin + modules
+            && y < options.margin + modules
+            && colors[((y - options.margin) * modules + (x - options.margin)) as usize]
+                == Color::Dark
+    };
+
+    let total = modules + options.margin * 2;
+
+    match options.format {
+        QrImageFormat::Svg => Ok(render_svg(total, options, is_dark).into_bytes()),
+        QrImageFormat::Png => render_png(total, options, is_dark),
+    }
+}
+
+fn render_svg(total: u32, options: &QrOptions, is_dark: impl Fn(u32, u32) -> bool) -> String {
+    let mut path = String::new();
+    for y in 0..total {
+        for x in 0..total {
+            if is_dark(x, y) {
+                let _ = write!(path, "M{x} {y}h1v1h-1z");
+            }
+        }
+    }
+
+    format!(
+        concat!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" version="1.1" width="{size}" height="{size}" "#,
+            r#"viewBox="0 0 {total} {total}" shape-rendering="crispEdges">"#,
+            r#"<rect width="{total}" height="{total}" fill="{bg}"/>"#,
+            r#"<path d="{path}" fill="{fg}"/></svg>"#
+        ),
+        size = options.size,
+        total = total,
+        bg = options.background.to_hex(),
+        fg = options.foreground.to_hex(),
+        path = path,
+    )
+}
+
+fn render_png(
+    total: u32,
+    options: &QrOptions,
+    is_dark: impl Fn(u32, u32) -> bool,
+) -> Result<Vec<u8>, AppError> {
+    let scale = (options.size / total).max(1);
+    let dimension = total * scale;
+
+    let mut pixels = Vec::with_capacity((dimension * dimension * 3) as usize);
+    for py in 0..dimension {
+        for px in 0..dimension {
+            let color = if is_dark(px / scale, py / scale) {
+                options.foreground
+            } else {
+                options.background
+            };
+            pixels.extend_from_slice(&[color.r, color.g, color.b]);
+        }
+    }
+
+    let mut buffer = Vec::new();
+    let mut encoder = png::Encoder::new(&mut buffer, dimension, dimension);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+
+    encoder
+        .write_header()
+        .and_then(|mut writer| writer.write_image_data(&pixels))
+        .map_err(|e| {
+            AppError::internal("Failed to encode PNG", json!({ "reason": e.to_string() }))
+        })?;
+
+    Ok(buffer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+    fn options(format: QrImageFormat, size: u32) -> QrOptions {
+        QrOptions {
+            format,
+            size,
+            margin: 4,
+            ecc: QrErrorCorrection::Medium,
+            foreground: "#123456".parse().unwrap(),
+            background: "#fed".parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn renders_png_with_requested_dimensions() {
+        let image = render_qr(
+            "https://s.example.com/abc123",
+            &options(QrImageFormat::Png, 512),
+        )
+        .unwrap();
+
+        assert_eq!(image[..8], PNG_SIGNATURE);
+
+        let decoder = png::Decoder::new(image.as_slice());
+        let reader = decoder.read_info().unwrap();
+        let info = reader.info();
+        assert_eq!(info.width, info.height);
+        assert_eq!(info.color_type, png::ColorType::Rgb);
+
+        // Модуль - целое число пикселей: размер не больше запрошенного и отстаёт меньше чем на модуль
+        let code = QrCode::new("https://s.example.com/abc123").unwrap();
+        let total = code.width() as u32 + 8;
+        assert!(info.width <= 512);
+        assert!(info.width > 512 - total);
+        assert_eq!(info.width % total, 0);
+    }
+
+    #[test]
+    fn renders_svg_with_colors() {
+        let image = render_qr(
+            "https://s.example.com/abc123",
+            &options(QrImageFormat::Svg, 256),
+        )
+        .unwrap();
+        let svg = String::from_utf8(image).unwrap();
+
+        assert!(svg.contains("<svg "));
+        assert!(svg.contains(r#"width="256" height="256""#));
+        assert!(svg.contains(r##"fill="#123456""##));
+        assert!(svg.contains(r##"fill="#ffeedd""##));
+        assert!(svg.ends_with("</svg>"));
+    }
+
+    #[test]
+    fn parses_colors() {
+        assert_eq!(
+            "#ff8000".parse::<QrColor>(),
+            Ok(QrColor {
+                r: 255,
+                g: 128,
+                b: 0
+            })
+        );
+        assert_eq!("000".parse::<QrColor>(), Ok(QrColor::BLACK));
+
+        for invalid in ["", "#12", "#12345", "#gggggg", "red", "#1234567"] {
+            assert!(
+                invalid.parse::<QrColor>().is_err(),
+                "{} must be rejected",
+                invalid
+            );
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use hmac::{Hmac, Mac};
+use rand::Rng;
+use sha2::{Digest, Sha256};
+
+/// Префикс API токенов: `ush_<lookup id>_<секрет>`
+pub const TOKEN_PREFIX: &str = "ush_";
+
+const LOOKUP_ID_LEN: usize = 8;
+const SECRET_LEN: usize = 40;
+const CHARSET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Новый токен: значение показывается один раз, lookup id хранится открыто
+#[derive(Debug, Clone)]
+pub struct GeneratedToken {
+    pub token: String,
+    pub lookup_id: String,
+}
+
+/// Хэширование API токенов: HMAC-SHA256 с серверным pepper.
+/// Общий для сервиса аутентификации и admin CLI.
+#[derive(Clone)]
+pub struct TokenHasher {
+    pepper: Vec<u8>,
+}
+
+impl TokenHasher {
+    pub fn new(pepper: &str) -> Self {
+        Self {
+            pepper: pepper.as_bytes().to_vec(),
+        }
+    }
+
+    /// Хэш токена для хранения в БД (hex)
+    pub fn hash(&self, token: &str) -> String {
+        let mut mac =
+            HmacSha256::new_from_slice(&self.pepper).expect("HMAC accepts keys of any length");
+        mac.update(token.as_bytes());
+        hex::encode(mac.finalize().into_bytes())
+    }
+
+    /// Старый формат хэша (SHA-256 без соли) - только для миграции
+    pub fn legacy_hash(token: &str) -> String {
+        hex::encode(Sha256::digest(token.as_bytes()))
+    }
+}
+
+/// Сгенерировать токен `ush_<lookup id>_<секрет>`
+pub fn generate_token() -> GeneratedToken {
+    let lookup_id = random_string(LOOKUP_ID_LEN);
+    let token = format!(
+        "{}{}_{}",
+        TOKEN_PREFIX,
+        lookup_id,
+        random_string(SECRET_LEN)
+    );
+
+    GeneratedToken { token, lookup_id }
+}
+
+/// Lookup id из токена нового формата (None - старый или чужой формат).
+/// Безопасен для логов: по нему нельзя восстановить секрет.
+pub fn lookup_id(token: &str) -> Option<&str> {
+    let (lookup_id, secret) = token.strip_prefix(TOKEN_PREFIX)?.split_once('_')?;
+
+    let valid = lookup_id.len() == LOOKUP_ID_LEN
+        && !secret.is_empty()
+        && lookup_id.bytes().all(|b| b.is_ascii_alphanumeric());
+
+    valid.then_some(lookup_id)
+}
+
+fn random_string(len: usize) -> String {
+    let mut rng = rand::rng();
+
+    (0..len)
+        .map(|_| CHARSET[rng.random_range(0..CHARSET.len())] as char)
+        .collect()
+}
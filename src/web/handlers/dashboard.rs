@@ -2,10 +2,14 @@ use askama::Template;
 use askama_web::WebTemplate;
 use axum::response::IntoResponse;
 
+use crate::web::middleware::csrf::CsrfToken;
+
 #[derive(Template, WebTemplate)]
 #[template(path = "dashboard.html")]
-pub struct DashboardTemplate {}
+pub struct DashboardTemplate {
+    pub csrf_token: String,
+}
 
-pub async fn dashboard_handler() -> impl IntoResponse {
-    DashboardTemplate {}
+pub async fn dashboard_handler(CsrfToken(csrf_token): CsrfToken) -> impl IntoResponse {
+    DashboardTemplate { csrf_token }
 }
@@ -2,10 +2,14 @@ use askama::Template;
 use askama_web::WebTemplate;
 use axum::response::IntoResponse;
 
+use crate::web::middleware::csrf::CsrfToken;
+
 #[derive(Template, WebTemplate)]
 #[template(path = "links.html")]
-pub struct LinksTemplate {}
+pub struct LinksTemplate {
+    pub csrf_token: String,
+}
 
-pub async fn links_handler() -> impl IntoResponse {
-    LinksTemplate {}
+pub async fn links_handler(CsrfToken(csrf_token): CsrfToken) -> impl IntoResponse {
+    LinksTemplate { csrf_token }
 }
@@ -1,12 +1,192 @@
 use askama::Template;
 use askama_web::WebTemplate;
-use axum::response::IntoResponse;
+use axum::{
+    Form,
+    extract::{ConnectInfo, State},
+    http::{HeaderMap, StatusCode, header},
+    response::{IntoResponse, Redirect, Response},
+};
+use serde::Deserialize;
+use std::net::{IpAddr, SocketAddr};
+
+use crate::api::middleware::rate_limit::{RateLimitKey, auth_failure_limit};
+use crate::application::services::session_service::SESSION_COOKIE;
+use crate::state::AppState;
+use crate::utils::cookies::{get_cookie, removal_cookie, session_cookie};
+use crate::web::middleware::csrf::CsrfToken;
+
+/// Cookie, в которой раньше хранился сам API токен
+const LEGACY_TOKEN_COOKIE: &str = "auth_token";
 
 // Шаблон для страницы логина
 #[derive(Template, WebTemplate)]
 #[template(path = "login.html")]
-struct LoginTemplate {}
+struct LoginTemplate {
+    csrf_token: String,
+    error: Option<String>,
+}
+
+/// Вход по логину и паролю или по API токену
+#[derive(Debug, Deserialize)]
+pub struct LoginForm {
+    #[serde(default)]
+    username: String,
+    #[serde(default)]
+    password: String,
+    #[serde(default)]
+    token: String,
+}
+
+pub async fn login_handler(CsrfToken(csrf_token): CsrfToken) -> impl IntoResponse {
+    LoginTemplate {
+        csrf_token,
+        error: None,
+    }
+}
+
+/// POST /dashboard/login - проверка пароля или токена и выдача cookie сессии
+pub async fn login_submit_handler(
+    State(st): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    CsrfToken(csrf_token): CsrfToken,
+    Form(form): Form<LoginForm>,
+) -> Response {
+    let ip = st.config.trusted_proxies.client_ip(addr.ip(), &headers);
+    let user_agent = headers
+        .get(header::USER_AGENT)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+
+    let token = form.token.trim();
+    let username = form.username.trim();
+
+    // Перебор паролей и токенов: неудачные попытки считаются по IP и по имени
+    // пользователя, при исчерпании лимита проверка даже не выполняется
+    let failure_keys = login_failure_keys(ip, token.is_empty().then_some(username));
+    let limit = auth_failure_limit(&st.config);
+    for key in &failure_keys {
+        let decision = st.rate_limiter.peek(key, limit).await;
+        if !decision.allowed {
+            metrics::counter!("rate_limit_rejected_total").increment(1);
+            return login_throttled(csrf_token, decision.retry_after.as_secs_f64().ceil() as u64);
+        }
+    }
+
+    let issued = if token.is_empty() {
+        let user = match st.user_service.authenticate(username, &form.password).await {
+            Ok(user) => user,
+            Err(_) => {
+                record_login_failure(&st, &failure_keys).await;
+                return login_failed(csrf_token, "Неверное имя пользователя или пароль");
+            }
+        };
+
+        st.session_service
+            .create_for_user(&user, Some(ip.to_string()), user_agent)
+            .await
+    } else {
+        // Токен отключённого пользователя не открывает дашборд
+        let api_token = match st.auth_service.authenticate(token).await {
+            Ok(api_token) => api_token,
+            Err(_) => {
+                record_login_failure(&st, &failure_keys).await;
+                return login_failed(csrf_token, "Неверный, отозванный или истёкший токен");
+            }
+        };
+        if st.auth_service.principal(api_token.clone()).await.is_err() {
+            record_login_failure(&st, &failure_keys).await;
+            return login_failed(csrf_token, "Неверный, отозванный или истёкший токен");
+        }
+
+        st.session_service
+            .create_for_token(&api_token, Some(ip.to_string()), user_agent)
+            .await
+    };
+
+    let issued = match issued {
+        Ok(issued) => issued,
+        Err(e) => return e.into_response(),
+    };
+
+    let max_age = (issued.session.expires_at - chrono::Utc::now()).num_seconds();
+    let secure = st.config.session_cookie_secure;
+
+    let mut response = Redirect::to("/dashboard").into_response();
+    let response_headers = response.headers_mut();
+    response_headers.append(
+        header::SET_COOKIE,
+        session_cookie(SESSION_COOKIE, &issued.cookie_value, max_age, secure),
+    );
+    response_headers.append(
+        header::SET_COOKIE,
+        removal_cookie(LEGACY_TOKEN_COOKIE, secure),
+    );
+
+    response
+}
+
+/// Ключи лимита неудачных входов: IP клиента и имя пользователя (при входе по паролю)
+fn login_failure_keys(ip: IpAddr, username: Option<&str>) -> Vec<String> {
+    let mut keys = vec![RateLimitKey::Ip(ip).storage_key("login_failures")];
+    if let Some(username) = username.filter(|username| !username.is_empty()) {
+        keys.push(format!(
+            "login_failures:username:{}",
+            username.to_lowercase()
+        ));
+    }
+    keys
+}
+
+async fn record_login_failure(st: &AppState, keys: &[String]) {
+    let limit = auth_failure_limit(&st.config);
+    for key in keys {
+        st.rate_limiter.check(key, limit).await;
+    }
+}
+
+fn login_throttled(csrf_token: String, retry_after_secs: u64) -> Response {
+    let page = LoginTemplate {
+        csrf_token,
+        error: Some(format!(
+            "Слишком много неудачных попыток входа, повторите через {} с",
+            retry_after_secs
+        )),
+    };
+    (
+        StatusCode::TOO_MANY_REQUESTS,
+        [(header::RETRY_AFTER, retry_after_secs.to_string())],
+        page,
+    )
+        .into_response()
+}
+
+fn login_failed(csrf_token: String, error: &str) -> Response {
+    metrics::counter!("dashboard_login_failed_total").increment(1);
+    let page = LoginTemplate {
+        csrf_token,
+        error: Some(error.to_string()),
+    };
+    (StatusCode::UNAUTHORIZED, page).into_response()
+}
+
+/// POST /dashboard/logout - отзыв сессии и удаление cookie
+pub async fn logout_handler(State(st): State<AppState>, headers: HeaderMap) -> Response {
+    if let Some(session) = get_cookie(&headers, SESSION_COOKIE)
+        && let Err(e) = st.session_service.revoke(session).await
+    {
+        return e.into_response();
+    }
+
+    let secure = st.config.session_cookie_secure;
+
+    let mut response = Redirect::to("/dashboard/login").into_response();
+    let response_headers = response.headers_mut();
+    response_headers.append(header::SET_COOKIE, removal_cookie(SESSION_COOKIE, secure));
+    response_headers.append(
+        header::SET_COOKIE,
+        removal_cookie(LEGACY_TOKEN_COOKIE, secure),
+    );
 
-pub async fn login_handler() -> impl IntoResponse {
-    LoginTemplate {}
+    response
 }
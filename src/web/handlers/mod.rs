@@ -5,5 +5,5 @@ mod stats;
 
 pub use dashboard::dashboard_handler;
 pub use links::links_handler;
-pub use login::login_handler;
+pub use login::{login_handler, login_submit_handler, logout_handler};
 pub use stats::stats_handler;
@@ -2,12 +2,18 @@ use askama::Template;
 use askama_web::WebTemplate;
 use axum::{extract::Path, response::IntoResponse};
 
+use crate::web::middleware::csrf::CsrfToken;
+
 #[derive(Template, WebTemplate)]
 #[template(path = "stats.html")]
 pub struct StatsTemplate {
     pub code: String,
+    pub csrf_token: String,
 }
 
-pub async fn stats_handler(Path(code): Path<String>) -> impl IntoResponse {
-    StatsTemplate { code }
+pub async fn stats_handler(
+    Path(code): Path<String>,
+    CsrfToken(csrf_token): CsrfToken,
+) -> impl IntoResponse {
+    StatsTemplate { code, csrf_token }
 }
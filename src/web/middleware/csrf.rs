@@ -0,0 +1,188 @@
+use askama::Template;
+use askama_web::WebTemplate;
+use axum::{
+    body::{Body, to_bytes},
+    extract::{FromRequestParts, Request, State},
+    http::{HeaderMap, HeaderName, Method, StatusCode, header, request::Parts},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use base64::Engine as _;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use rand_core::{OsRng, TryRngCore};
+use serde_json::json;
+
+use crate::application::services::session_service::SESSION_COOKIE;
+use crate::error::AppError;
+use crate::state::AppState;
+use crate::utils::cookies::{get_cookie, session_cookie};
+
+/// Cookie с CSRF токеном (double-submit)
+pub const CSRF_COOKIE: &str = "csrf_token";
+/// Поле HTML формы с CSRF токеном
+pub const CSRF_FORM_FIELD: &str = "csrf_token";
+/// Заголовок с CSRF токеном для fetch запросов
+pub const CSRF_HEADER: HeaderName = HeaderName::from_static("x-csrf-token");
+
+const CSRF_TOKEN_BYTES: usize = 32;
+const MAX_FORM_BYTES: usize = 64 * 1024;
+
+/// CSRF токен текущего запроса (для встраивания в шаблоны)
+#[derive(Debug, Clone)]
+pub struct CsrfToken(pub String);
+
+impl<S: Send + Sync> FromRequestParts<S> for CsrfToken {
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts
+            .extensions
+            .get::<CsrfToken>()
+            .cloned()
+            .ok_or_else(|| AppError::internal("CSRF middleware is not configured", json!({})))
+    }
+}
+
+#[derive(Template, WebTemplate)]
+#[template(path = "error.html")]
+struct CsrfErrorTemplate {
+    csrf_token: String,
+    title: String,
+    message: String,
+}
+
+/// CSRF защита страниц дашборда: изменяющие запросы (POST/PUT/PATCH/DELETE)
+/// должны передать токен из cookie в поле формы или заголовке `X-CSRF-Token`
+pub async fn layer(State(st): State<AppState>, mut req: Request, next: Next) -> Response {
+    let (token, is_new) = current_token(req.headers());
+
+    if is_unsafe(req.method()) {
+        // Без cookie сравнивать не с чем
+        let (checked, valid) = if is_new {
+            (req, false)
+        } else {
+            verify(req, &token).await
+        };
+        req = checked;
+
+        if !valid {
+            metrics::counter!("csrf_rejected_total").increment(1);
+
+            let page = CsrfErrorTemplate {
+                csrf_token: token.clone(),
+                title: "Форма устарела".to_string(),
+                message: "Запрос отклонён защитой от CSRF. Обновите страницу и повторите действие."
+                    .to_string(),
+            };
+            let response = (StatusCode::FORBIDDEN, page).into_response();
+            return with_cookie(&st, response, &token, is_new);
+        }
+    }
+
+    req.extensions_mut().insert(CsrfToken(token.clone()));
+    let response = next.run(req).await;
+
+    with_cookie(&st, response, &token, is_new)
+}
+
+/// CSRF защита API: проверяется только для запросов, аутентифицированных
+/// cookie сессии дашборда (Bearer токены браузер сам не подставляет)
+pub async fn api_layer(req: Request, next: Next) -> Result<Response, AppError> {
+    let needs_check = is_unsafe(req.method())
+        && !req.headers().contains_key(header::AUTHORIZATION)
+        && get_cookie(req.headers(), SESSION_COOKIE).is_some();
+
+    if needs_check {
+        let expected = get_cookie(req.headers(), CSRF_COOKIE);
+        let provided = req.headers().get(CSRF_HEADER).and_then(|v| v.to_str().ok());
+
+        let valid = matches!((expected, provided), (Some(e), Some(p)) if tokens_match(e, p));
+        if !valid {
+            metrics::counter!("csrf_rejected_total").increment(1);
+            return Err(AppError::forbidden(
+                "CSRF token missing or invalid",
+                json!({"header": CSRF_HEADER.as_str()}),
+            ));
+        }
+    }
+
+    Ok(next.run(req).await)
+}
+
+/// Токен из cookie или новый (флаг - нужно выставить cookie)
+fn current_token(headers: &HeaderMap) -> (String, bool) {
+    match get_cookie(headers, CSRF_COOKIE).filter(|t| !t.is_empty()) {
+        Some(token) => (token.to_string(), false),
+        None => (generate_token(), true),
+    }
+}
+
+/// Сравнить токен запроса с ожидаемым. Тело формы читается и возвращается
+/// обратно в запрос, чтобы обработчик мог его разобрать.
+async fn verify(req: Request, expected: &str) -> (Request, bool) {
+    if let Some(provided) = req.headers().get(CSRF_HEADER).and_then(|v| v.to_str().ok()) {
+        let valid = tokens_match(expected, provided);
+        return (req, valid);
+    }
+
+    let is_form = req
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|ct| ct.starts_with("application/x-www-form-urlencoded"));
+    if !is_form {
+        return (req, false);
+    }
+
+    let (parts, body) = req.into_parts();
+    let bytes = match to_bytes(body, MAX_FORM_BYTES).await {
+        Ok(bytes) => bytes,
+        Err(_) => return (Request::from_parts(parts, Body::empty()), false),
+    };
+
+    let valid = url::form_urlencoded::parse(&bytes)
+        .find(|(key, _)| key == CSRF_FORM_FIELD)
+        .is_some_and(|(_, provided)| tokens_match(expected, &provided));
+
+    (Request::from_parts(parts, Body::from(bytes)), valid)
+}
+
+fn is_unsafe(method: &Method) -> bool {
+    !matches!(
+        *method,
+        Method::GET | Method::HEAD | Method::OPTIONS | Method::TRACE
+    )
+}
+
+/// Сравнение за постоянное время
+fn tokens_match(expected: &str, provided: &str) -> bool {
+    expected.len() == provided.len()
+        && expected
+            .bytes()
+            .zip(provided.bytes())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
+
+fn generate_token() -> String {
+    let mut buffer = [0u8; CSRF_TOKEN_BYTES];
+    OsRng
+        .try_fill_bytes(&mut buffer)
+        .expect("Failed to generate random bytes");
+    URL_SAFE_NO_PAD.encode(buffer)
+}
+
+fn with_cookie(st: &AppState, mut response: Response, token: &str, is_new: bool) -> Response {
+    if is_new {
+        // Срок жизни как у сессии дашборда; в JS токен попадает из шаблона
+        let cookie = session_cookie(
+            CSRF_COOKIE,
+            token,
+            st.config.session_ttl_hours * 3600,
+            st.config.session_cookie_secure,
+        );
+        response.headers_mut().append(header::SET_COOKIE, cookie);
+    }
+
+    response
+}
@@ -1,50 +1,30 @@
 use axum::{
     extract::{Request, State},
-    http::header::COOKIE,
     middleware::Next,
     response::{Redirect, Response},
 };
 
+use crate::api::middleware::auth::authenticate_session;
+use crate::application::services::session_service::SESSION_COOKIE;
 use crate::state::AppState;
+use crate::utils::cookies::get_cookie;
 
 pub async fn layer(
     State(st): State<AppState>,
-    req: Request,
+    mut req: Request,
     next: Next,
 ) -> Result<Response, Redirect> {
-    // Извлекаем cookie из заголовка
-    let token = req
-        .headers()
-        .get(COOKIE)
-        .and_then(|cookie_header| cookie_header.to_str().ok())
-        .and_then(|cookie_str| {
-            // Парсим cookie строку
-            cookie_str.split(';').find_map(|cookie| {
-                let mut parts = cookie.trim().splitn(2, '=');
-                match (parts.next(), parts.next()) {
-                    (Some("auth_token"), Some(value)) => Some(value.to_string()),
-                    _ => None,
-                }
-            })
-        });
+    // Нет cookie сессии - редирект на login
+    let Some(session) = get_cookie(req.headers(), SESSION_COOKIE) else {
+        return Err(Redirect::to("/dashboard/login"));
+    };
 
-    match token {
-        Some(token) => {
-            // Проверяем токен через auth_service
-            match st.auth_service.authenticate(&token).await {
-                Ok(_) => {
-                    // Токен валиден - пропускаем запрос
-                    Ok(next.run(req).await)
-                }
-                Err(_) => {
-                    // Токен невалиден - редирект на login
-                    Err(Redirect::to("/dashboard/login"))
-                }
-            }
-        }
-        None => {
-            // Нет токена - редирект на login
-            Err(Redirect::to("/dashboard/login"))
-        }
-    }
+    // Сессия отозвана/истекла или токен больше не активен - редирект на login
+    let principal = authenticate_session(&st, session)
+        .await
+        .map_err(|_| Redirect::to("/dashboard/login"))?;
+
+    req.extensions_mut().insert(principal);
+
+    Ok(next.run(req).await)
 }
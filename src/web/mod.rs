@@ -1,3 +1,4 @@
 pub mod handlers;
 pub mod middleware;
+pub mod pages;
 pub mod routes;
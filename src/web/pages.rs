@@ -0,0 +1,63 @@
+//! Страницы для посетителей коротких ссылок (вне дашборда)
+
+use askama::Template;
+use askama_web::WebTemplate;
+use axum::{
+    http::{HeaderMap, StatusCode, header},
+    response::{IntoResponse, Response},
+};
+
+use crate::domain::entities::DomainBranding;
+use crate::error::AppError;
+
+#[derive(Template, WebTemplate)]
+#[template(path = "link_error.html")]
+struct LinkErrorTemplate {
+    domain: String,
+    title: String,
+    message: String,
+    logo_url: Option<String>,
+    fallback_url: Option<String>,
+}
+
+/// Запрос из браузера (Accept содержит text/html)
+pub fn wants_html(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::ACCEPT)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|accept| accept.contains("text/html"))
+}
+
+/// Страница 404/410 с оформлением домена. Остальные ошибки отдаются как обычно.
+pub fn link_error_page(
+    error: AppError,
+    domain: &str,
+    branding: Option<&DomainBranding>,
+) -> Response {
+    let (status, title, default_message) = match &error {
+        AppError::NotFound { .. } => (
+            StatusCode::NOT_FOUND,
+            "Ссылка не найдена",
+            "Такой короткой ссылки не существует. Проверьте адрес.",
+        ),
+        AppError::Gone { .. } => (
+            StatusCode::GONE,
+            "Ссылка больше недоступна",
+            "Эта короткая ссылка отключена.",
+        ),
+        _ => return error.into_response(),
+    };
+
+    let branding = branding.cloned().unwrap_or_default();
+    let page = LinkErrorTemplate {
+        domain: domain.to_string(),
+        title: title.to_string(),
+        message: branding
+            .not_found_message
+            .unwrap_or_else(|| default_message.to_string()),
+        logo_url: branding.logo_url,
+        fallback_url: branding.fallback_url,
+    };
+
+    (status, page).into_response()
+}
@@ -1,6 +1,12 @@
 use crate::state::AppState;
-use crate::web::handlers::{dashboard_handler, links_handler, login_handler, stats_handler};
-use axum::{Router, routing::get};
+use crate::web::handlers::{
+    dashboard_handler, links_handler, login_handler, login_submit_handler, logout_handler,
+    stats_handler,
+};
+use axum::{
+    Router,
+    routing::{get, post},
+};
 
 /// Защищённые маршруты (требуют аутентификацию)
 pub fn protected_routes() -> Router<AppState> {
@@ -12,5 +18,7 @@ pub fn protected_routes() -> Router<AppState> {
 
 /// Публичные маршруты
 pub fn public_routes() -> Router<AppState> {
-    Router::new().route("/login", get(login_handler))
+    Router::new()
+        .route("/login", get(login_handler).post(login_submit_handler))
+        .route("/logout", post(logout_handler))
 }
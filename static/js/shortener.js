@@ -11,28 +11,31 @@
 
 let Auth = {
     /**
-     * Получение токена из cookie
+     * Выход из системы (сессия хранится в HttpOnly cookie)
      */
-    getToken() {
-        const cookies = document.cookie.split(';');
-        for (let cookie of cookies) {
-            const [name, value] = cookie.trim().split('=');
-            if (name === 'auth_token') {
-                return value;
-            }
-        }
-        return null;
+    async logout() {
+        await fetch('/dashboard/logout', {
+            method: 'POST',
+            headers: { 'X-CSRF-Token': Auth.csrfToken() }
+        });
+
+        // Редирект на login
+        window.location.href = '/dashboard/login';
     },
 
     /**
-     * Выход из системы
+     * Сессия истекла - на страницу входа
      */
-    logout() {
-        // Удаляем cookie
-        document.cookie = 'auth_token=; path=/; max-age=0';
-
-        // Редирект на login
+    redirectToLogin() {
         window.location.href = '/dashboard/login';
+    },
+
+    /**
+     * CSRF токен страницы (встроен в base.html)
+     */
+    csrfToken() {
+        const meta = document.querySelector('meta[name="csrf-token"]');
+        return meta ? meta.content : '';
     }
 };
 
@@ -122,6 +125,41 @@ const Utils = {
         container.innerHTML = '<div class="loading">Загрузка...</div>';
     },
 
+    /**
+     * Экранирование строки для вставки в HTML (текст и значения атрибутов)
+     * @param {*} value - Значение
+     * @returns {string} Экранированная строка
+     */
+    escapeHtml(value) {
+        return String(value ?? '')
+            .replace(/&/g, '&amp;')
+            .replace(/</g, '&lt;')
+            .replace(/>/g, '&gt;')
+            .replace(/"/g, '&quot;')
+            .replace(/'/g, '&#39;');
+    },
+
+    /**
+     * Отрисовка тегов ссылки
+     * @param {Array} tags - Список тегов
+     * @returns {string} HTML
+     */
+    renderTags(tags) {
+        if (!tags || tags.length === 0) return '—';
+        return tags.map(tag => `<span class="tag">${Utils.escapeHtml(tag)}</span>`).join(' ');
+    },
+
+    /**
+     * Отрисовка ячейки с оригинальной ссылкой (заголовок, если есть)
+     * @param {object} link - Ссылка из API
+     * @returns {string} HTML
+     */
+    renderLinkTitle(link) {
+        const longUrl = Utils.escapeHtml(link.long_url);
+        if (!link.title) return longUrl;
+        return `<strong>${Utils.escapeHtml(link.title)}</strong><br><small>${longUrl}</small>`;
+    },
+
     /**
      * Отображение пустого состояния
      * @param {string} message - Сообщение
@@ -145,21 +183,21 @@ const API = {
      * @returns {Promise} Промис с данными
      */
     async request(endpoint, options = {}) {
-        const token = Auth.getToken();
-
+        // Аутентификация - cookie сессии, которую браузер отправляет сам
         const response = await fetch(endpoint, {
+            credentials: 'same-origin',
             headers: {
                 'Content-Type': 'application/json',
-                'Authorization': token ? `Bearer ${token}` : '',
+                'X-CSRF-Token': Auth.csrfToken(),
                 ...options.headers
             },
             ...options
         });
 
-        // Если 401 - токен невалиден
+        // Если 401 - сессия истекла или отозвана
         if (response.status === 401) {
             alert('Сессия истекла. Необходимо войти заново.');
-            Auth.logout();
+            Auth.redirectToLogin();
             return null;
         }
 
@@ -183,7 +221,7 @@ const API = {
 
     /**
      * Получение списка ссылок с фильтрами
-     * @param {object} params - Параметры (page, page_size, from, to, domain)
+     * @param {object} params - Параметры (page, page_size, from, to, domain, q, tag, sort, order)
      * @returns {Promise} Промис со списком ссылок
      */
     async getLinks(params = {}) {
@@ -194,14 +232,26 @@ const API = {
         if (params.from) queryParams.append('from', params.from);
         if (params.to) queryParams.append('to', params.to);
         if (params.domain) queryParams.append('domain', params.domain);
+        if (params.q) queryParams.append('q', params.q);
+        if (params.tag) queryParams.append('tag', params.tag);
+        if (params.sort) queryParams.append('sort', params.sort);
+        if (params.order) queryParams.append('order', params.order);
 
         const query = queryParams.toString();
         return this.request(`/api/stats${query ? '?' + query : ''}`);
     },
 
+    /**
+     * Получение статистики по тегам
+     * @returns {Promise} Промис со списком тегов
+     */
+    async getTags() {
+        return this.request('/api/tags');
+    },
+
     /**
      * Создание коротких ссылок (множественное)
-     * @param {Array} urls - Массив объектов {url, custom_code?, domain?}
+     * @param {Array} urls - Массив объектов {url, custom_code?, domain?, tags?}
      * @returns {Promise} Промис с результатами
      */
     async createLinks(urls) {
@@ -274,6 +324,7 @@ const Dashboard = {
                             <th>Короткая ссылка</th>
                             <th>Оригинальная ссылка</th>
                             <th>Домен</th>
+                            <th>Теги</th>
                             <th>Переходы</th>
                             <th>Создана</th>
                             <th>Действия</th>
@@ -288,10 +339,11 @@ const Dashboard = {
                                     </a>
                                 </td>
                                 <td style="max-width: 300px; overflow: hidden; text-overflow: ellipsis; white-space: nowrap;" 
-                                    title="${link.long_url}">
-                                    ${link.long_url}
+                                    title="${Utils.escapeHtml(link.long_url)}">
+                                    ${Utils.renderLinkTitle(link)}
                                 </td>
                                 <td><code>${link.domain}</code></td>
+                                <td>${Utils.renderTags(link.tags)}</td>
                                 <td>${link.total || 0}</td>
                                 <td>${Utils.formatDate(link.created_at)}</td>
                                 <td>
@@ -356,6 +408,32 @@ const Dashboard = {
                             <small>Только буквы, цифры, дефис и подчеркивание</small>
                         </div>
                     </div>
+                    <div class="form-row">
+                        <div class="form-group">
+                            <label>Заголовок</label>
+                            <input type="text"
+                                   class="link-title"
+                                   data-field-id="${fieldId}"
+                                   maxlength="255"
+                                   placeholder="Весенняя распродажа">
+                        </div>
+                        <div class="form-group">
+                            <label>Заметки</label>
+                            <input type="text"
+                                   class="link-notes"
+                                   data-field-id="${fieldId}"
+                                   maxlength="2000"
+                                   placeholder="Для рассылки от 1 марта">
+                        </div>
+                    </div>
+                    <div class="form-group">
+                        <label>Теги</label>
+                        <input type="text"
+                               class="link-tags"
+                               data-field-id="${fieldId}"
+                               placeholder="spring-sale, newsletter">
+                        <small>Через запятую, до 10 тегов</small>
+                    </div>
                 </div>
             </div>
         `;
@@ -416,6 +494,25 @@ const Dashboard = {
                     linkData.custom_code = customCode;
                 }
 
+                const tagsInput = document.querySelector(`.link-tags[data-field-id="${fieldId}"]`);
+                const tags = (tagsInput?.value || '')
+                    .split(',')
+                    .map(t => t.trim())
+                    .filter(t => t);
+                if (tags.length > 0) {
+                    linkData.tags = tags;
+                }
+
+                const title = document.querySelector(`.link-title[data-field-id="${fieldId}"]`)?.value.trim();
+                if (title) {
+                    linkData.title = title;
+                }
+
+                const notes = document.querySelector(`.link-notes[data-field-id="${fieldId}"]`)?.value.trim();
+                if (notes) {
+                    linkData.notes = notes;
+                }
+
                 urls.push(linkData);
             }
         });
@@ -442,7 +539,7 @@ const Dashboard = {
                     <div class="result-item result-error">
                         <div class="result-url-display">
                             <span class="result-icon">✗</span>
-                            <span class="result-long-url">${item.long_url}</span>
+                            <span class="result-long-url">${Utils.escapeHtml(item.long_url)}</span>
                         </div>
                         <div class="result-message error">
                             ${item.error.message}
@@ -455,7 +552,7 @@ const Dashboard = {
                     <div class="result-item result-success">
                         <div class="result-url-display">
                             <span class="result-icon">✓</span>
-                            <span class="result-long-url">${item.long_url}</span>
+                            <span class="result-long-url">${Utils.escapeHtml(item.long_url)}</span>
                         </div>
                         <div class="result-short">
                             <input type="text" value="${item.short_url}" readonly>
@@ -554,7 +651,11 @@ const Links = {
         pageSize: 25,
         fromDate: '',
         toDate: '',
-        selectedDomain: ''
+        selectedDomain: '',
+        selectedTag: '',
+        search: '',
+        sort: 'created_at',
+        order: 'desc'
     },
     domains: [],
 
@@ -571,6 +672,24 @@ const Links = {
         }
     },
 
+    /**
+     * Загрузка тегов для фильтра
+     */
+    async loadTags() {
+        try {
+            const data = await API.getTags();
+            const tagSelect = document.getElementById('tagFilter');
+            if (!tagSelect) return;
+
+            tagSelect.innerHTML = '<option value="">Все теги</option>' +
+                (data.items || [])
+                    .map(t => `<option value="${t.name}">${t.name} (${t.links_count})</option>`)
+                    .join('');
+        } catch (error) {
+            console.error('Ошибка загрузки тегов:', error);
+        }
+    },
+
     /**
      * Заполнение фильтра доменов
      */
@@ -601,6 +720,10 @@ const Links = {
             if (this.state.fromDate) params.from = this.state.fromDate;
             if (this.state.toDate) params.to = this.state.toDate;
             if (this.state.selectedDomain) params.domain = this.state.selectedDomain;
+            if (this.state.search) params.q = this.state.search;
+            if (this.state.selectedTag) params.tag = this.state.selectedTag;
+            params.sort = this.state.sort;
+            params.order = this.state.order;
 
             const data = await API.getLinks(params);
 
@@ -618,6 +741,7 @@ const Links = {
                             <th>Код</th>
                             <th>Домен</th>
                             <th>Оригинальная ссылка</th>
+                            <th>Теги</th>
                             <th>Переходы</th>
                             <th>Создана</th>
                             <th>Действия</th>
@@ -633,9 +757,10 @@ const Links = {
                                 </td>
                                 <td><code>${link.domain}</code></td>
                                 <td style="max-width: 400px; overflow: hidden; text-overflow: ellipsis; white-space: nowrap;"
-                                    title="${link.long_url}">
-                                    ${link.long_url}
+                                    title="${Utils.escapeHtml(link.long_url)}">
+                                    ${Utils.renderLinkTitle(link)}
                                 </td>
+                                <td>${Utils.renderTags(link.tags)}</td>
                                 <td>${link.total || 0}</td>
                                 <td>${Utils.formatDate(link.created_at)}</td>
                                 <td>
@@ -717,11 +842,18 @@ const Links = {
         const fromDate = document.getElementById('fromDate')?.value || '';
         const toDate = document.getElementById('toDate')?.value || '';
         const domain = document.getElementById('domainFilter')?.value || '';
+        const tag = document.getElementById('tagFilter')?.value || '';
+        const search = document.getElementById('searchInput')?.value.trim() || '';
+        const [sort, order] = (document.getElementById('sortSelect')?.value || 'created_at:desc').split(':');
 
         this.state.pageSize = parseInt(pageSize);
+        this.state.search = search;
+        this.state.sort = sort;
+        this.state.order = order;
         this.state.fromDate = fromDate ? new Date(fromDate).toISOString() : '';
         this.state.toDate = toDate ? new Date(toDate).toISOString() : '';
         this.state.selectedDomain = domain;
+        this.state.selectedTag = tag;
         this.state.currentPage = 1;
 
         await this.loadLinks();
@@ -734,6 +866,9 @@ const Links = {
         document.getElementById('fromDate').value = '';
         document.getElementById('toDate').value = '';
         document.getElementById('domainFilter').value = '';
+        document.getElementById('tagFilter').value = '';
+        document.getElementById('searchInput').value = '';
+        document.getElementById('sortSelect').value = 'created_at:desc';
         await this.applyFilters();
     },
 
@@ -742,6 +877,7 @@ const Links = {
      */
     async init() {
         await this.loadDomains();
+        await this.loadTags();
         await this.loadLinks();
 
         // Обработчики фильтров
@@ -749,6 +885,9 @@ const Links = {
         const fromDate = document.getElementById('fromDate');
         const toDate = document.getElementById('toDate');
         const domainFilter = document.getElementById('domainFilter');
+        const tagFilter = document.getElementById('tagFilter');
+        const searchInput = document.getElementById('searchInput');
+        const sortSelect = document.getElementById('sortSelect');
         const applyBtn = document.getElementById('applyFiltersBtn');
         const resetBtn = document.getElementById('resetFiltersBtn');
 
@@ -756,6 +895,11 @@ const Links = {
         if (fromDate) fromDate.addEventListener('change', () => this.applyFilters());
         if (toDate) toDate.addEventListener('change', () => this.applyFilters());
         if (domainFilter) domainFilter.addEventListener('change', () => this.applyFilters());
+        if (tagFilter) tagFilter.addEventListener('change', () => this.applyFilters());
+        if (sortSelect) sortSelect.addEventListener('change', () => this.applyFilters());
+        if (searchInput) searchInput.addEventListener('keydown', (e) => {
+            if (e.key === 'Enter') this.applyFilters();
+        });
         if (applyBtn) applyBtn.addEventListener('click', () => this.applyFilters());
         if (resetBtn) resetBtn.addEventListener('click', () => this.resetFilters());
     }
@@ -890,6 +1034,11 @@ const Stats = {
                 document.getElementById('domain').textContent = tableData.domain;
                 document.getElementById('totalClicks').textContent = tableData.total || 0;
                 document.getElementById('createdAt').textContent = Utils.formatDateTime(tableData.created_at);
+                document.getElementById('linkTitle').textContent = tableData.title || '—';
+                document.getElementById('linkNotes').textContent = tableData.notes || '—';
+                document.getElementById('createdBy').textContent = tableData.created_by
+                    ? (tableData.created_by.token_name || `#${tableData.created_by.token_id}`)
+                    : '—';
 
                 // Кнопка копирования
                 const copyBtn = document.getElementById('copyBtn');
@@ -1177,64 +1326,6 @@ const Stats = {
     }
 };
 
-/**
- * ============================================
- * LOGIN MODULE
- * ============================================
- */
-let Login = {
-    init() {
-        const loginForm = document.getElementById('loginForm');
-        if (!loginForm) return;
-        loginForm.addEventListener('submit', this.handleLogin.bind(this));
-    },
-
-    async handleLogin(e) {
-        e.preventDefault();
-
-        const token = document.getElementById('token').value;
-        const errorDiv = document.getElementById('error');
-        const submitBtn = e.target.querySelector('button[type="submit"]');
-
-        submitBtn.disabled = true;
-        submitBtn.textContent = 'Проверка...';
-        errorDiv.style.display = 'none';
-
-        try {
-            const response = await fetch('/api/health/', {
-                headers: {
-                    'Authorization': `Bearer ${token}`
-                }
-            });
-
-            if (response.ok) {
-                // Сохраняем токен ТОЛЬКО в cookie
-                document.cookie = `auth_token=${token}; path=/; max-age=2592000; SameSite=Strict`;
-
-                // Перенаправляем на dashboard
-                window.location.href = '/dashboard';
-                return;
-            }
-
-            const errorMessage = response.status === 401
-                ? 'Неверный токен'
-                : 'Ошибка проверки токена';
-
-            this.showError(errorDiv, submitBtn, errorMessage);
-
-        } catch (error) {
-            this.showError(errorDiv, submitBtn, 'Ошибка сети: ' + error.message);
-        }
-    },
-
-    showError(errorDiv, submitBtn, message) {
-        errorDiv.textContent = '❌ ' + message;
-        errorDiv.style.display = 'block';
-        submitBtn.disabled = false;
-        submitBtn.textContent = 'Войти';
-    }
-};
-
 // ============================================
 // ЭКСПОРТ В ГЛОБАЛЬНУЮ ОБЛАСТЬ
 // ============================================
@@ -1245,4 +1336,7 @@ window.API = API;
 window.Dashboard = Dashboard;
 window.Links = Links;
 window.Stats = Stats;
-window.Login = Login;
+
+if (typeof module !== 'undefined') {
+    module.exports = { Utils };
+}
@@ -0,0 +1,33 @@
+// Тесты клиентской библиотеки дашборда: node --test tests/js
+const test = require('node:test');
+const assert = require('node:assert');
+
+globalThis.window = {};
+const { Utils } = require('../../static/js/shortener.js');
+
+test('renderLinkTitle экранирует HTML в заголовке и URL', () => {
+    const html = Utils.renderLinkTitle({
+        title: '<img src=x onerror=alert(1)>',
+        long_url: 'https://example.com/?q=<script>',
+    });
+
+    assert.ok(!html.includes('<img'));
+    assert.ok(!html.includes('<script>'));
+    assert.ok(html.includes('&lt;img src=x onerror=alert(1)&gt;'));
+    assert.ok(html.includes('https://example.com/?q=&lt;script&gt;'));
+});
+
+test('renderLinkTitle без заголовка возвращает экранированный URL', () => {
+    assert.strictEqual(
+        Utils.renderLinkTitle({ title: null, long_url: 'https://example.com/?a="b"' }),
+        'https://example.com/?a=&quot;b&quot;'
+    );
+});
+
+test('renderTags экранирует теги', () => {
+    assert.strictEqual(
+        Utils.renderTags(['<b>x</b>']),
+        '<span class="tag">&lt;b&gt;x&lt;/b&gt;</span>'
+    );
+    assert.strictEqual(Utils.renderTags([]), '—');
+});